@@ -0,0 +1,1007 @@
+use crate::*;
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_categorical_event(
+    ctx: Context<CreateCategoricalEvent>,
+    title: String,
+    category: u8,
+    bet_end_time: i64,
+    commit_end_time: i64,
+    reveal_end_time: i64,
+    truth_questions: Vec<Pubkey>,
+    fee_schedule: Option<FeeSchedule>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!((10..=150).contains(&title.len()), PredictError::InvalidTitleLength);
+    require!(bet_end_time > now, PredictError::InvalidBetEndTime);
+    require!(bet_end_time < commit_end_time, PredictError::InvalidTimeOrder);
+    require!(commit_end_time < reveal_end_time, PredictError::InvalidTimeOrder);
+    require!(category <= 3, PredictError::InvalidCategory);
+
+    let n = truth_questions.len();
+    require!(
+        (MIN_CATEGORICAL_OUTCOMES as usize..=MAX_CATEGORICAL_OUTCOMES as usize).contains(&n),
+        PredictError::InvalidOutcomeCount
+    );
+    for (i, q) in truth_questions.iter().enumerate() {
+        require!(!truth_questions[..i].contains(q), PredictError::DuplicateTruthQuestion);
+    }
+
+    let counter = &mut ctx.accounts.counter;
+    let event_id = counter.count;
+
+    let cev = &mut ctx.accounts.categorical_event;
+    cev.creator = ctx.accounts.creator.key();
+    cev.event_id = event_id;
+    cev.title = title;
+    cev.category = category;
+    cev.bet_end_time = bet_end_time;
+    cev.commit_end_time = commit_end_time;
+    cev.reveal_end_time = reveal_end_time;
+    cev.created_at = now;
+    cev.outcome_count = n as u8;
+    cev.collateral_vault = Pubkey::default();
+    cev.outcome_mints = vec![Pubkey::default(); n];
+    cev.truth_questions = truth_questions;
+    cev.outcome_results = vec![OUTCOME_PENDING; n];
+    cev.votes_yes = vec![0; n];
+    cev.votes_no = vec![0; n];
+    cev.outstanding = vec![0; n];
+    cev.total_collateral_lamports = 0;
+    cev.total_issued_per_outcome = 0;
+    cev.pending_truth_commission = 0;
+    cev.total_truth_commission_sent = 0;
+    cev.pending_creator_commission = 0;
+    cev.pending_house_commission = 0;
+    cev.consensus_threshold_bps = ctx.accounts.config.consensus_threshold_bps;
+    cev.creator_clawback = ctx.accounts.config.creator_clawback;
+    cev.forfeited_commission = 0;
+    cev.forfeit_base_units = 0;
+    cev.resolved = false;
+    cev.winning_outcome = 0;
+    cev.result_status = RESULT_PENDING;
+    cev.resolved_at = 0;
+    cev.unclaimed_swept = false;
+    cev.swept_at = 0;
+
+    // snapshot of the protocol config in force at creation
+    let config = &ctx.accounts.config;
+    cev.house_wallet = config.house_wallet;
+    cev.fee_schedule = fee_schedule_or_default(config, fee_schedule)?;
+    cev.redeem_fee_bps = config.redeem_fee_bps;
+    cev.unclaimed_sweep_delay_secs = config.unclaimed_sweep_delay_secs;
+    cev.emergency_exit_delay_secs = config.emergency_exit_delay_secs;
+    cev.house_lamports_paid = 0;
+    cev.pause = PauseState::default();
+
+    counter.count = counter.count.checked_add(1).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// One outcome mint per call; the first call also creates the collateral and commission vaults.
+pub(crate) fn create_categorical_mint(ctx: Context<CreateCategoricalMint>, index: u8) -> Result<()> {
+    let cev_key = ctx.accounts.categorical_event.key();
+    require!(index < ctx.accounts.categorical_event.outcome_count, PredictError::InvalidOutcomeIndex);
+
+    let payer_ai = ctx.accounts.creator.to_account_info();
+    let system_ai = ctx.accounts.system_program.to_account_info();
+
+    let vault_bump = ctx.bumps.collateral_vault;
+    let vault_seeds: [&[u8]; 3] = [
+        SEED_COLLATERAL_VAULT,
+        cev_key.as_ref(),
+        &[vault_bump],
+    ];
+    create_system_pda_0space(
+        &payer_ai,
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &system_ai,
+        &vault_seeds,
+    )?;
+
+    // commission vault PDA, holds creator + house commissions as for binary events
+    let commission_bump = ctx.bumps.commission_vault;
+    let commission_seeds: [&[u8]; 3] = [
+        SEED_COMMISSION_VAULT,
+        cev_key.as_ref(),
+        &[commission_bump],
+    ];
+    create_system_pda_0space(
+        &payer_ai,
+        &ctx.accounts.commission_vault.to_account_info(),
+        &system_ai,
+        &commission_seeds,
+    )?;
+
+    let mint_bump = ctx.bumps.outcome_mint;
+    let mint_seeds: [&[u8]; 4] = [
+        SEED_CATEGORICAL_MINT,
+        cev_key.as_ref(),
+        &[index],
+        &[mint_bump],
+    ];
+    create_and_init_spl_mint_pda(
+        &payer_ai,
+        &ctx.accounts.outcome_mint.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &system_ai,
+        &ctx.accounts.rent.to_account_info(),
+        &ctx.accounts.mint_authority.key(),
+        &mint_seeds,
+        9,
+    )?;
+
+    let cev = &mut ctx.accounts.categorical_event;
+    cev.collateral_vault = ctx.accounts.collateral_vault.key();
+    cev.outcome_mints[index as usize] = ctx.accounts.outcome_mint.key();
+
+    Ok(())
+}
+
+// Mints `net` of every outcome. remaining_accounts = [mint_i, user_ata_i] pairs.
+pub(crate) fn buy_categorical_set<'info>(
+    ctx: Context<'_, '_, 'info, 'info, BuyCategoricalSet<'info>>,
+    lamports: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.categorical_event.pause, PAUSE_BUY)?;
+    require!(lamports > 0, PredictError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+
+    let cev = &ctx.accounts.categorical_event;
+    require!(now < cev.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!cev.resolved, PredictError::EventResolved);
+    require!(
+        cev.outcome_mints.iter().all(|m| *m != Pubkey::default()),
+        PredictError::OutcomeMintsNotCreated
+    );
+
+    // fee split, discounted by the buyer's 30-day volume tier; the truth cut is held until
+    // each outcome question is finalized
+    let discount_bps = fee_tier_discount_bps(&ctx.accounts.config, rolling_volume_30d(&ctx.accounts.user_stats, now));
+    let fee_bps = cev.fee_schedule.fee_bps as u64;
+    let fee_bps = fee_bps.saturating_sub(mul_div_floor(fee_bps, discount_bps as u64, BPS_DENOM)?);
+    let (_fee, truth_cut, creator_cut, house_cut, net) = compute_fee_splits(
+        lamports,
+        fee_bps as u16,
+        cev.fee_schedule.truth_share_bps,
+        cev.fee_schedule.creator_share_bps,
+    )?;
+
+    // creator + house cut -> commission vault, the rest -> collateral vault
+    let commission = creator_cut.checked_add(house_cut).ok_or(PredictError::MathOverflow)?;
+    let deposited = lamports.checked_sub(commission).ok_or(PredictError::MathOverflow)?;
+    transfer_in(&ctx.accounts.user, &ctx.accounts.collateral_vault, &ctx.accounts.system_program, deposited)?;
+    transfer_in(&ctx.accounts.user, &ctx.accounts.commission_vault, &ctx.accounts.system_program, commission)?;
+
+    let outcomes = categorical_outcome_accounts(cev, ctx.remaining_accounts, &ctx.accounts.user.key())?;
+    let cev_key = cev.key();
+    for (mint, ata) in outcomes.iter() {
+        mint_position_tokens(
+            &ctx.accounts.token_program.to_account_info(),
+            mint,
+            &ata.to_account_info(),
+            &ctx.accounts.mint_authority.to_account_info(),
+            &cev_key,
+            ctx.bumps.mint_authority,
+            net,
+        )?;
+    }
+
+    let cev = &mut ctx.accounts.categorical_event;
+    cev.total_collateral_lamports = cev.total_collateral_lamports.checked_add(deposited).ok_or(PredictError::MathOverflow)?;
+    cev.total_issued_per_outcome = cev.total_issued_per_outcome.checked_add(net).ok_or(PredictError::MathOverflow)?;
+    for o in cev.outstanding.iter_mut() {
+        *o = o.checked_add(net).ok_or(PredictError::MathOverflow)?;
+    }
+    cev.pending_truth_commission = cev.pending_truth_commission.checked_add(truth_cut).ok_or(PredictError::MathOverflow)?;
+    cev.pending_creator_commission = cev.pending_creator_commission.checked_add(creator_cut).ok_or(PredictError::MathOverflow)?;
+    cev.pending_house_commission = cev.pending_house_commission.checked_add(house_cut).ok_or(PredictError::MathOverflow)?;
+
+    // UserPosition only tracks TRUE / FALSE, so a categorical buy counts as volume alone
+    record_buy(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.user_position,
+        &ctx.accounts.user.key(),
+        &cev_key,
+        [ctx.bumps.user_stats, ctx.bumps.user_position],
+        lamports,
+        0,
+        [0, 0],
+        now,
+    )?;
+
+    Ok(())
+}
+
+// Burns `amount` of every outcome for `amount` lamports while betting is open.
+pub(crate) fn redeem_categorical_set<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RedeemCategoricalSet<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.categorical_event.pause, PAUSE_PAIR_REDEEM)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+
+    let cev = &ctx.accounts.categorical_event;
+    require!(now < cev.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!cev.resolved, PredictError::EventAlreadyResolved);
+
+    let outcomes = categorical_outcome_accounts(cev, ctx.remaining_accounts, &ctx.accounts.user.key())?;
+    for (mint, ata) in outcomes.iter() {
+        require!(ata.amount >= amount, PredictError::InsufficientOutcomeBalance);
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: (*mint).clone(),
+                    from: ata.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    let payout = payout_after_fee(amount, cev.redeem_fee_bps)?;
+
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
+    require!(
+        vault_lamports >= keep.saturating_add(payout),
+        PredictError::VaultInsufficientFunds
+    );
+
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &cev.key(),
+        ctx.bumps.collateral_vault,
+        payout,
+    )?;
+
+    let cev = &mut ctx.accounts.categorical_event;
+    cev.total_collateral_lamports = cev.total_collateral_lamports.checked_sub(payout).ok_or(PredictError::MathOverflow)?;
+    for o in cev.outstanding.iter_mut() {
+        *o = o.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+    }
+
+    Ok(())
+}
+
+// Finalizes the Truth question of one outcome and pays that question its share of the truth cut.
+pub(crate) fn finalize_categorical_outcome(ctx: Context<FinalizeCategoricalOutcome>, index: u8) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.categorical_event.pause, PAUSE_RESOLVE)?;
+    let cev = &mut ctx.accounts.categorical_event;
+    let q = &mut ctx.accounts.truth_network_question;
+    let now = Clock::get()?.unix_timestamp;
+    let i = index as usize;
+
+    require!(index < cev.outcome_count, PredictError::InvalidOutcomeIndex);
+    require!(now >= cev.bet_end_time, PredictError::BettingStillActive);
+    require!(!cev.resolved, PredictError::EventAlreadyResolved);
+    require!(cev.outcome_results[i] == OUTCOME_PENDING, PredictError::OutcomeAlreadyFinalized);
+    require_keys_eq!(cev.truth_questions[i], q.key(), PredictError::TruthQuestionMismatch);
+    require_keys_eq!(ctx.accounts.truth_network_vault.key(), q.vault_address, PredictError::InvalidTruthVault);
+    require!(now >= q.reveal_end_time, PredictError::TruthVotingStillActive);
+
+    // CPI: finalize voting on Truth Network
+    let question_id = q.id;
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.truth_network_program.to_account_info(),
+        FinalizeVoting { question: q.to_account_info() },
+    );
+    finalize_voting(cpi_ctx, question_id)?;
+    q.reload()?;
+
+    let v_yes = q.votes_option_1;
+    let v_no = q.votes_option_2;
+    let total_votes = v_yes.checked_add(v_no).ok_or(PredictError::MathOverflow)?;
+
+    cev.votes_yes[i] = v_yes;
+    cev.votes_no[i] = v_no;
+    cev.outcome_results[i] = if total_votes == 0 {
+        OUTCOME_NO_VOTES
+    } else {
+        let yes_bps = v_yes
+            .checked_mul(BPS_DENOM)
+            .ok_or(PredictError::MathOverflow)?
+            .checked_div(total_votes)
+            .ok_or(PredictError::MathOverflow)?;
+        if q.winning_option == 1 && yes_bps >= cev.consensus_threshold_bps as u64 {
+            OUTCOME_YES
+        } else {
+            OUTCOME_NO
+        }
+    };
+
+    // even share of the truth cut, the last question gets the remainder
+    let unfinalized = cev
+        .outcome_results
+        .iter()
+        .filter(|r| **r == OUTCOME_PENDING)
+        .count() as u64
+        + 1;
+    let share = cev.pending_truth_commission / unfinalized;
+
+    transfer_from_vault_to_truth(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.truth_network_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &cev.key(),
+        ctx.bumps.collateral_vault,
+        share,
+    )?;
+
+    cev.pending_truth_commission = cev.pending_truth_commission.checked_sub(share).ok_or(PredictError::MathOverflow)?;
+    cev.total_truth_commission_sent = cev.total_truth_commission_sent.checked_add(share).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// Once every outcome is finalized: exactly one YES is the winner, anything else pays 1/N per token.
+pub(crate) fn settle_categorical_event(ctx: Context<SettleCategoricalEvent>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.categorical_event.pause, PAUSE_RESOLVE)?;
+    let cev = &mut ctx.accounts.categorical_event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!cev.resolved, PredictError::EventAlreadyResolved);
+    require!(
+        cev.outcome_results.iter().all(|r| *r != OUTCOME_PENDING),
+        PredictError::OutcomesNotFinalized
+    );
+
+    let yes: Vec<usize> = cev
+        .outcome_results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| **r == OUTCOME_YES)
+        .map(|(i, _)| i)
+        .collect();
+
+    if yes.len() == 1 {
+        cev.winning_outcome = yes[0] as u8;
+        cev.result_status = RESULT_RESOLVED_WINNER;
+    } else if yes.len() > 1 {
+        cev.result_status = RESULT_FINALIZED_TIE;
+    } else if cev.outcome_results.iter().all(|r| *r == OUTCOME_NO_VOTES) {
+        cev.result_status = RESULT_FINALIZED_NO_VOTES;
+    } else {
+        cev.result_status = RESULT_FINALIZED_BELOW_THRESHOLD;
+    }
+
+    cev.resolved = true;
+    cev.resolved_at = now;
+
+    // sweep house commission once, regardless of outcome
+    sweep_categorical_house_commission(
+        cev,
+        &ctx.accounts.commission_vault.to_account_info(),
+        &ctx.accounts.house_treasury.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.bumps.commission_vault,
+    )?;
+
+    // no winner: the creator commission goes to the outcome holders, as for binary events
+    if cev.result_status != RESULT_RESOLVED_WINNER {
+        forfeit_categorical_creator_commission(
+            cev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub(crate) fn redeem_categorical_after_final(ctx: Context<RedeemCategoricalAfterFinal>, index: u8, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.categorical_event.pause, PAUSE_FINAL_REDEEM)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let cev = &mut ctx.accounts.categorical_event;
+    require!(cev.resolved, PredictError::EventNotResolved);
+    require!(!cev.unclaimed_swept, PredictError::RedemptionExpired);
+    // emergency exits pay pro rata through emergency_exit_categorical
+    require!(cev.result_status != RESULT_EMERGENCY_EXIT, PredictError::InvalidResultStatus);
+    require!(index < cev.outcome_count, PredictError::InvalidOutcomeIndex);
+    require_keys_eq!(ctx.accounts.mint.key(), cev.outcome_mints[index as usize], PredictError::InvalidMint);
+
+    // winner pays 1 per token, no-winner pays 1/N per token on every outcome plus its
+    // share of the forfeited creator commission
+    let payout = if cev.result_status == RESULT_RESOLVED_WINNER {
+        require!(index == cev.winning_outcome, PredictError::NotWinningToken);
+        payout_after_fee(amount, cev.redeem_fee_bps)?
+    } else {
+        let share = if cev.forfeit_base_units == 0 {
+            0
+        } else {
+            mul_div_floor(cev.forfeited_commission, amount, cev.forfeit_base_units)?
+        };
+        payout_after_fee(amount, cev.redeem_fee_bps)?
+            .checked_div(cev.outcome_count as u64)
+            .and_then(|p| p.checked_add(share))
+            .ok_or(PredictError::MathOverflow)?
+    };
+
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientOutcomeBalance);
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let outstanding = &mut cev.outstanding[index as usize];
+    *outstanding = outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    // rent safety
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
+    require!(
+        vault_lamports >= keep.saturating_add(payout),
+        PredictError::VaultInsufficientFunds
+    );
+
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &cev.key(),
+        ctx.bumps.collateral_vault,
+        payout,
+    )?;
+
+    cev.total_collateral_lamports = cev.total_collateral_lamports
+        .checked_sub(payout)
+        .ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// Categorical counterpart of emergency_exit. Once the deadline passes on an unsettled
+// event, any holder burns outcome tokens for a pro-rata share of the collateral vault.
+pub(crate) fn emergency_exit_categorical(ctx: Context<EmergencyExitCategorical>, index: u8, amount: u64) -> Result<()> {
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let cev = &mut ctx.accounts.categorical_event;
+    let now = Clock::get()?.unix_timestamp;
+    require!(index < cev.outcome_count, PredictError::InvalidOutcomeIndex);
+    require_keys_eq!(ctx.accounts.mint.key(), cev.outcome_mints[index as usize], PredictError::InvalidMint);
+
+    if cev.resolved {
+        require!(cev.result_status == RESULT_EMERGENCY_EXIT, PredictError::EventAlreadyResolved);
+        require!(!cev.unclaimed_swept, PredictError::RedemptionExpired);
+    } else {
+        require!(
+            now >= cev.reveal_end_time.saturating_add(cev.emergency_exit_delay_secs),
+            PredictError::EmergencyExitNotAvailable
+        );
+
+        cev.resolved = true;
+        cev.resolved_at = now;
+        cev.result_status = RESULT_EMERGENCY_EXIT;
+        cev.winning_outcome = 0;
+        // the unfinalized questions never earn their held Truth cut; it stays with the holders
+        cev.pending_truth_commission = 0;
+
+        // commissions settle as on a no-winner result
+        sweep_categorical_house_commission(
+            cev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.house_treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+        forfeit_categorical_creator_commission(
+            cev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+    }
+
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientOutcomeBalance);
+
+    // pro rata over every outstanding outcome token, before this burn
+    let keep = vault_keep_lamports()?;
+    let available = ctx.accounts.collateral_vault.lamports().saturating_sub(keep);
+    let outstanding = cev
+        .outstanding
+        .iter()
+        .try_fold(0u64, |acc, o| acc.checked_add(*o))
+        .ok_or(PredictError::MathOverflow)?;
+    require!(outstanding > 0, PredictError::NothingToClaim);
+    let payout = mul_div_floor(available, amount, outstanding)?;
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let index_outstanding = &mut cev.outstanding[index as usize];
+    *index_outstanding = index_outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &cev.key(),
+        ctx.bumps.collateral_vault,
+        payout,
+    )?;
+
+    cev.total_collateral_lamports = cev.total_collateral_lamports.saturating_sub(payout);
+
+    Ok(())
+}
+
+pub(crate) fn claim_categorical_creator_commission(ctx: Context<ClaimCategoricalCreatorCommission>) -> Result<()> {
+    let cev = &mut ctx.accounts.categorical_event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now >= cev.bet_end_time, PredictError::BettingStillActive);
+    require_keys_eq!(ctx.accounts.creator.key(), cev.creator, PredictError::Unauthorized);
+    // with clawback the commission is only earned once a winner is settled
+    require!(!cev.creator_clawback || cev.resolved, PredictError::EventNotResolved);
+
+    let amount = cev.pending_creator_commission;
+    require!(amount > 0, PredictError::NothingToClaim);
+
+    // rent safety
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.commission_vault.to_account_info().lamports();
+    require!(vault_lamports >= keep.saturating_add(amount), PredictError::VaultInsufficientFunds);
+
+    transfer_from_commission_vault(
+        &ctx.accounts.commission_vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &cev.key(),
+        ctx.bumps.commission_vault,
+        amount,
+    )?;
+
+    cev.pending_creator_commission = 0;
+    Ok(())
+}
+
+pub(crate) fn sweep_categorical_unclaimed_to_house(ctx: Context<SweepCategoricalUnclaimedToHouse>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.categorical_event.pause, PAUSE_SWEEP)?;
+    let cev = &mut ctx.accounts.categorical_event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(cev.resolved, PredictError::EventNotResolved);
+    require!(!cev.unclaimed_swept, PredictError::AlreadySwept);
+    require!(
+        now >= cev.resolved_at.saturating_add(cev.unclaimed_sweep_delay_secs),
+        PredictError::SweepNotYetAvailable
+    );
+
+    // unclaimed creator commission sits in the commission vault, out of reach of the sweep
+    let keep = vault_keep_lamports()?;
+    let amount = ctx.accounts.collateral_vault.lamports().saturating_sub(keep);
+    require!(amount > 0, PredictError::NothingToSweep);
+
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.house_treasury.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &cev.key(),
+        ctx.bumps.collateral_vault,
+        amount,
+    )?;
+    record_treasury_inflow(&ctx.accounts.house_treasury.to_account_info(), &cev.key(), amount)?;
+
+    cev.total_collateral_lamports = cev.total_collateral_lamports.saturating_sub(amount);
+    cev.house_lamports_paid = cev.house_lamports_paid.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    cev.unclaimed_swept = true;
+    cev.swept_at = now;
+
+    Ok(())
+}
+
+pub(crate) fn delete_categorical_event(ctx: Context<DeleteCategoricalEvent>) -> Result<()> {
+    let cev = &ctx.accounts.categorical_event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(cev.resolved, PredictError::EventNotResolved);
+    require!(cev.pending_creator_commission == 0, PredictError::CreatorCommissionNotClaimed);
+    require!(cev.pending_house_commission == 0, PredictError::HouseCommissionNotCleared);
+
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.collateral_vault.lamports();
+    let vault_empty = vault_lamports <= keep.saturating_add(VAULT_DUST_TOLERANCE_LAMPORTS);
+
+    // before the sweep window, or if never swept, every payable token must be redeemed
+    let after_window = now >= cev.resolved_at.saturating_add(cev.unclaimed_sweep_delay_secs);
+    if !after_window || !cev.unclaimed_swept {
+        require!(no_outstanding_categorical(cev), PredictError::OutstandingTokens);
+    }
+    require!(vault_empty, PredictError::VaultNotEmpty);
+
+    let commission_lamports = ctx.accounts.commission_vault.lamports();
+    require!(
+        commission_lamports <= keep.saturating_add(VAULT_DUST_TOLERANCE_LAMPORTS),
+        PredictError::VaultNotEmpty
+    );
+
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &cev.key(),
+        ctx.bumps.collateral_vault,
+        vault_lamports,
+    )?;
+    transfer_from_commission_vault(
+        &ctx.accounts.commission_vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &cev.key(),
+        ctx.bumps.commission_vault,
+        commission_lamports,
+    )?;
+
+    Ok(())
+}
+
+// Same for a categorical event.
+pub(crate) fn set_categorical_pause(
+    ctx: Context<SetCategoricalPause>,
+    flags: u8,
+    reason: String,
+    duration_secs: i64,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let cev = &mut ctx.accounts.categorical_event;
+    cev.pause = event_pause_update(&ctx.accounts.config, &cev.pause, cev.creator, authority, flags, reason, duration_secs)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateCategoricalEvent<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(mut)]
+    pub counter: Account<'info, EventCounter>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 8 + (4 + 150) + 1 + 8 * 4 + 1 + 32
+            + 2 * (4 + 32 * MAX_CATEGORICAL_OUTCOMES as usize)
+            + (4 + MAX_CATEGORICAL_OUTCOMES as usize)
+            + 3 * (4 + 8 * MAX_CATEGORICAL_OUTCOMES as usize)
+            + 8 * 6 + 2 + 1 + 8 * 2 + 1 + 1 + 1 + 8 + 1 + 8
+            + 32 + 6 + 2 + 8 + 8 + 8 + PAUSE_STATE_SPACE,
+        seeds = [SEED_CATEGORICAL_EVENT, creator.key().as_ref(), &counter.count.to_le_bytes()],
+        bump
+    )]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct CreateCategoricalMint<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = categorical_event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    /// CHECK: PDA signer
+    #[account(seeds = [SEED_MINT_AUTH, categorical_event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA outcome mint
+    #[account(mut, seeds = [SEED_CATEGORICAL_MINT, categorical_event.key().as_ref(), &[index]], bump)]
+    pub outcome_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA system account
+    #[account(mut, seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA system account
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, categorical_event.key().as_ref()], bump)]
+    pub commission_vault: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyCategoricalSet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()],
+        bump,
+        constraint = categorical_event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, categorical_event.key().as_ref()], bump)]
+    pub commission_vault: SystemAccount<'info>,
+
+    /// CHECK: PDA mint authority signer
+    #[account(seeds = [SEED_MINT_AUTH, categorical_event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    // ---- trading stats ----
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, categorical_event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemCategoricalSet<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()],
+        bump,
+        constraint = categorical_event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeCategoricalOutcome<'info> {
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub truth_network_question: Box<Account<'info, Question>>,
+
+    /// CHECK: vault is system-owned PDA in Truth-Network (no data), but must be mutable
+    #[account(mut)]
+    pub truth_network_vault: UncheckedAccount<'info>,
+
+    pub truth_network_program: Program<'info, TruthNetwork>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleCategoricalEvent<'info> {
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Fixed house wallet
+    #[account(mut, address = categorical_event.house_wallet)]
+    pub house_treasury: AccountInfo<'info>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: AccountInfo<'info>,
+
+    /// CHECK: Commission vault PDA
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, categorical_event.key().as_ref()], bump)]
+    pub commission_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemCategoricalAfterFinal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()],
+        bump,
+        constraint = categorical_event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    // Outcome mint, checked against `index`
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyExitCategorical<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()],
+        bump,
+        constraint = categorical_event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, categorical_event.key().as_ref()], bump)]
+    pub commission_vault: SystemAccount<'info>,
+
+    /// CHECK: Fixed house wallet, receives the pending house commission on the first exit
+    #[account(mut, address = categorical_event.house_wallet)]
+    pub house_treasury: AccountInfo<'info>,
+
+    // Outcome mint, checked against `index`
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCategoricalCreatorCommission<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, categorical_event.key().as_ref()], bump)]
+    pub commission_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepCategoricalUnclaimedToHouse<'info> {
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Fixed house wallet
+    #[account(mut, address = categorical_event.house_wallet)]
+    pub house_treasury: AccountInfo<'info>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteCategoricalEvent<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        constraint = categorical_event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, categorical_event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: AccountInfo<'info>,
+
+    /// CHECK: Commission vault PDA
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, categorical_event.key().as_ref()], bump)]
+    pub commission_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetCategoricalPause<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub categorical_event: Box<Account<'info, CategoricalEvent>>,
+}
@@ -0,0 +1,522 @@
+use crate::*;
+
+// Turns a freshly created event into a child of `parent_event`, collateralised by the
+// parent's TRUE (parent_side = 1) or FALSE (parent_side = 2) token.
+pub(crate) fn init_conditional_market(ctx: Context<InitConditionalMarket>, parent_side: u8) -> Result<()> {
+    require_native_collateral(&ctx.accounts.event)?;
+    let parent = &ctx.accounts.parent_event;
+    let child = &ctx.accounts.event;
+
+    require!(child.true_mint == Pubkey::default(), PredictError::MintsAlreadyCreated);
+    require!(child.kind == EVENT_KIND_BINARY, PredictError::InvalidEventKind);
+    require!(parent.kind == EVENT_KIND_BINARY, PredictError::InvalidEventKind);
+    require!(!parent.resolved, PredictError::EventAlreadyResolved);
+    require_keys_neq!(parent.key(), child.key(), PredictError::InvalidParentEvent);
+
+    let expected_mint = mint_for_side(parent, parent_side)?;
+    require_keys_eq!(ctx.accounts.collateral_mint.key(), expected_mint, PredictError::InvalidMint);
+
+    let market = &mut ctx.accounts.conditional_market;
+    market.event = child.key();
+    market.parent_event = parent.key();
+    market.parent_side = parent_side;
+    market.collateral_mint = expected_mint;
+    market.token_vault = ctx.accounts.token_vault.key();
+    market.bump = ctx.bumps.conditional_market;
+    market.total_collateral = 0;
+
+    ctx.accounts.event.kind = EVENT_KIND_CONDITIONAL;
+    Ok(())
+}
+
+// Deposits `amount` parent tokens and mints `amount` child TRUE + FALSE.
+// No protocol fee: it was already paid when the parent tokens were bought.
+pub(crate) fn buy_conditional_positions(ctx: Context<BuyConditionalPositions>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_BUY)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!ctx.accounts.event.resolved, PredictError::EventResolved);
+    require!(!ctx.accounts.parent_event.resolved, PredictError::EventResolved);
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_collateral_ata.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.collateral_mint.decimals,
+    )?;
+
+    let event_key = ctx.accounts.event.key();
+    let mint_auth_bump = ctx.bumps.mint_authority;
+    mint_position_tokens(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.true_mint.to_account_info(),
+        &ctx.accounts.user_true_ata.to_account_info(),
+        &ctx.accounts.mint_authority.to_account_info(),
+        &event_key,
+        mint_auth_bump,
+        amount,
+    )?;
+    mint_position_tokens(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.false_mint.to_account_info(),
+        &ctx.accounts.user_false_ata.to_account_info(),
+        &ctx.accounts.mint_authority.to_account_info(),
+        &event_key,
+        mint_auth_bump,
+        amount,
+    )?;
+
+    // parent tokens are tracked on the market; the event keeps SOL amounts only
+    apply_accounting(&mut ctx.accounts.event, 0, amount, 0, 0, 0)?;
+    let market = &mut ctx.accounts.conditional_market;
+    market.total_collateral = market.total_collateral.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+
+    // conditional buys carry no fee to discount; parent tokens build the position, not the tier volume
+    record_buy(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.user_position,
+        &ctx.accounts.user.key(),
+        &event_key,
+        [ctx.bumps.user_stats, ctx.bumps.user_position],
+        0,
+        amount,
+        [amount, amount],
+        now,
+    )?;
+
+    Ok(())
+}
+
+pub(crate) fn redeem_conditional_pair_while_active(ctx: Context<RedeemConditionalPair>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_PAIR_REDEEM)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!ctx.accounts.event.resolved, PredictError::EventAlreadyResolved);
+    require!(ctx.accounts.user_true_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+    require!(ctx.accounts.user_false_ata.amount >= amount, PredictError::InsufficientFalseBalance);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.true_mint.to_account_info(),
+                from: ctx.accounts.user_true_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.false_mint.to_account_info(),
+                from: ctx.accounts.user_false_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    transfer_from_conditional_vault(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.token_vault.to_account_info(),
+
+        &ctx.accounts.collateral_mint.to_account_info(),
+        &ctx.accounts.user_collateral_ata.to_account_info(),
+        &ctx.accounts.conditional_market.to_account_info(),
+        &ctx.accounts.event.key(),
+        ctx.accounts.conditional_market.bump,
+
+        ctx.accounts.collateral_mint.decimals,
+        amount,
+    )?;
+
+    let market = &mut ctx.accounts.conditional_market;
+    market.total_collateral = market.total_collateral.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    let ev = &mut ctx.accounts.event;
+    ev.outstanding_true = ev.outstanding_true.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+    ev.outstanding_false = ev.outstanding_false.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// Parent resolved the other way (or with no winner): every child token refunds
+// half a parent token. Otherwise the child settles normally, paid in parent tokens.
+pub(crate) fn redeem_conditional_after_final(ctx: Context<RedeemConditionalAfterFinal>, side: u8, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_FINAL_REDEEM)?;
+    require_result_final(&ctx.accounts.event)?;
+    require_result_final(&ctx.accounts.parent_event)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let parent = &ctx.accounts.parent_event;
+    let ev = &mut ctx.accounts.event;
+    require!(parent.resolved, PredictError::ParentNotResolved);
+    require!(!ev.unclaimed_swept, PredictError::RedemptionExpired);
+
+    let expected_mint = mint_for_side(ev, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    let payout = if !conditional_parent_met(parent, ctx.accounts.conditional_market.parent_side) {
+        amount / 2
+    } else {
+        require!(ev.resolved, PredictError::EventNotResolved);
+        if ev.result_status == RESULT_RESOLVED_WINNER {
+            require!(ev.winning_option == side, PredictError::NotWinningToken);
+            amount
+        } else {
+            amount / 2
+        }
+    };
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let outstanding = outstanding_for_side_mut(ev, side)?;
+    *outstanding = outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    require!(ctx.accounts.token_vault.amount >= payout, PredictError::VaultInsufficientFunds);
+
+    transfer_from_conditional_vault(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.token_vault.to_account_info(),
+
+        &ctx.accounts.collateral_mint.to_account_info(),
+        &ctx.accounts.user_collateral_ata.to_account_info(),
+        &ctx.accounts.conditional_market.to_account_info(),
+        &ev.key(),
+        ctx.accounts.conditional_market.bump,
+
+        ctx.accounts.collateral_mint.decimals,
+        payout,
+    )?;
+
+    let market = &mut ctx.accounts.conditional_market;
+    market.total_collateral = market.total_collateral.checked_sub(payout).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// Parent tokens still in the conditional vault once the child is settled (the
+// parent missed the condition, or the child resolved) and the sweep delay passed.
+pub(crate) fn sweep_conditional_unclaimed_to_house(ctx: Context<SweepConditionalUnclaimed>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_SWEEP)?;
+    require_result_final(&ctx.accounts.parent_event)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let parent = &ctx.accounts.parent_event;
+    let ev = &mut ctx.accounts.event;
+    require!(parent.resolved, PredictError::ParentNotResolved);
+    require!(!ev.unclaimed_swept, PredictError::AlreadySwept);
+
+    let settled_at = if !conditional_parent_met(parent, ctx.accounts.conditional_market.parent_side) {
+        parent.resolved_at.max(ev.resolved_at)
+    } else {
+        require!(ev.resolved, PredictError::EventNotResolved);
+        require_result_final(ev)?;
+        ev.resolved_at
+    };
+    require!(
+        now >= settled_at.saturating_add(ev.unclaimed_sweep_delay_secs),
+        PredictError::SweepNotYetAvailable
+    );
+
+    let amount = ctx.accounts.token_vault.amount;
+    require!(amount > 0, PredictError::NothingToSweep);
+
+    transfer_from_conditional_vault(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.token_vault.to_account_info(),
+
+        &ctx.accounts.collateral_mint.to_account_info(),
+        &ctx.accounts.house_collateral_ata.to_account_info(),
+        &ctx.accounts.conditional_market.to_account_info(),
+        &ev.key(),
+        ctx.accounts.conditional_market.bump,
+
+        ctx.accounts.collateral_mint.decimals,
+        amount,
+    )?;
+
+    ctx.accounts.conditional_market.total_collateral = 0;
+    ev.unclaimed_swept = true;
+    ev.swept_at = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitConditionalMarket<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Box<Account<'info, Event>>,
+
+    pub parent_event: Box<Account<'info, Event>>,
+
+    // parent TRUE or FALSE mint, checked against `parent_side`
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 32 + 1 + 32 + 32 + 1 + 8,
+        seeds = [SEED_CONDITIONAL_MARKET, event.key().as_ref()],
+        bump
+    )]
+    pub conditional_market: Box<Account<'info, ConditionalMarket>>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [SEED_CONDITIONAL_VAULT, event.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = conditional_market,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyConditionalPositions<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(address = conditional_market.parent_event @ PredictError::InvalidParentEvent)]
+    pub parent_event: Box<Account<'info, Event>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONDITIONAL_MARKET, event.key().as_ref()],
+        bump = conditional_market.bump
+    )]
+    pub conditional_market: Box<Account<'info, ConditionalMarket>>,
+
+    #[account(mut, address = conditional_market.token_vault @ PredictError::InvalidVault)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = conditional_market.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_collateral_ata.mint == conditional_market.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA mint authority signer
+    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint)]
+    pub true_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint)]
+    pub false_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_true_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_true_ata.mint == true_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_true_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_false_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_false_ata.mint == false_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_false_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // ---- trading stats ----
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemConditionalPair<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONDITIONAL_MARKET, event.key().as_ref()],
+        bump = conditional_market.bump
+    )]
+    pub conditional_market: Box<Account<'info, ConditionalMarket>>,
+
+    #[account(mut, address = conditional_market.token_vault @ PredictError::InvalidVault)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = conditional_market.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_collateral_ata.mint == conditional_market.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint)]
+    pub true_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint)]
+    pub false_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_true_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_true_ata.mint == true_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_true_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_false_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_false_ata.mint == false_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_false_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemConditionalAfterFinal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(address = conditional_market.parent_event @ PredictError::InvalidParentEvent)]
+    pub parent_event: Box<Account<'info, Event>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONDITIONAL_MARKET, event.key().as_ref()],
+        bump = conditional_market.bump
+    )]
+    pub conditional_market: Box<Account<'info, ConditionalMarket>>,
+
+    #[account(mut, address = conditional_market.token_vault @ PredictError::InvalidVault)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = conditional_market.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_collateral_ata.mint == conditional_market.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // child TRUE or FALSE mint, checked against `side`
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SweepConditionalUnclaimed<'info> {
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(address = conditional_market.parent_event @ PredictError::InvalidParentEvent)]
+    pub parent_event: Box<Account<'info, Event>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONDITIONAL_MARKET, event.key().as_ref()],
+        bump = conditional_market.bump
+    )]
+    pub conditional_market: Box<Account<'info, ConditionalMarket>>,
+
+    #[account(mut, address = conditional_market.token_vault @ PredictError::InvalidVault)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = conditional_market.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = house_collateral_ata.owner == event.house_wallet @ PredictError::InvalidTokenAccountOwner,
+        constraint = house_collateral_ata.mint == conditional_market.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub house_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
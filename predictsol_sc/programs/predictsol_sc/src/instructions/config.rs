@@ -0,0 +1,150 @@
+use crate::*;
+
+// One-time setup of the protocol config, only the program upgrade authority can call it.
+pub(crate) fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+    validate_config_params(&params)?;
+
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.bump = ctx.bumps.config;
+    apply_config_params(config, params);
+
+    Ok(())
+}
+
+// Queues a config change; anyone can read the PendingChange PDA before it takes effect.
+pub(crate) fn propose_config_change(ctx: Context<ProposeConfigChange>, params: ConfigParams) -> Result<()> {
+    validate_config_params(&params)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let pending = &mut ctx.accounts.pending_change;
+    pending.params = params;
+    pending.proposed_at = now;
+    pending.eta = now.checked_add(CONFIG_TIMELOCK_SECS).ok_or(PredictError::MathOverflow)?;
+    pending.bump = ctx.bumps.pending_change;
+
+    Ok(())
+}
+
+// Only affects events created afterwards; existing events keep their snapshot.
+pub(crate) fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pending = &ctx.accounts.pending_change;
+    require!(now >= pending.eta, PredictError::TimelockNotElapsed);
+
+    let params = pending.params.clone();
+    validate_config_params(&params)?;
+    apply_config_params(&mut ctx.accounts.config, params);
+    Ok(())
+}
+
+pub(crate) fn cancel_config_change(_ctx: Context<CancelConfigChange>) -> Result<()> {
+    Ok(())
+}
+
+// Two-step admin handover: the current admin nominates, the nominee accepts.
+pub(crate) fn nominate_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.config.pending_admin = new_admin;
+    Ok(())
+}
+
+pub(crate) fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.new_admin.key();
+    config.pending_admin = Pubkey::default();
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 32 + CONFIG_PARAMS_SPACE + 1 + 32 + PAUSE_STATE_SPACE,
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()) @ PredictError::Unauthorized)]
+    pub program: Program<'info, crate::program::PredictolSc>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PredictError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProposeConfigChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump = config.bump,
+        has_one = admin @ PredictError::Unauthorized
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    // one change at a time
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CONFIG_PARAMS_SPACE + 8 + 8 + 1,
+        seeds = [SEED_PENDING_CHANGE],
+        bump
+    )]
+    pub pending_change: Box<Account<'info, PendingChange>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump = config.bump,
+        has_one = admin @ PredictError::Unauthorized
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut, close = admin, seeds = [SEED_PENDING_CHANGE], bump = pending_change.bump)]
+    pub pending_change: Box<Account<'info, PendingChange>>,
+}
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump = config.bump,
+        has_one = admin @ PredictError::Unauthorized
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut, close = admin, seeds = [SEED_PENDING_CHANGE], bump = pending_change.bump)]
+    pub pending_change: Box<Account<'info, PendingChange>>,
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump = config.bump,
+        constraint = config.pending_admin == new_admin.key() @ PredictError::Unauthorized
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+}
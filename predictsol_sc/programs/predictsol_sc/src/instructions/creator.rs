@@ -0,0 +1,139 @@
+use crate::*;
+
+// Creator sets the fee curve and early-bird discount before the first buy, so every buyer sees the same schedule.
+pub(crate) fn set_fee_curve(
+    ctx: Context<SetFeeCurve>,
+    curve: u8,
+    curve_start: i64,
+    max_fee_bps: u16,
+    steps: u8,
+    early_bird_end_time: i64,
+    early_bird_discount_bps: u16,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    let ev = &mut ctx.accounts.event;
+
+    require!(now < ev.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!ev.has_buys, PredictError::FeeCurveLocked);
+
+    require!(curve <= FEE_CURVE_STEP, PredictError::InvalidFeeCurve);
+    if curve != FEE_CURVE_FLAT {
+        require!(curve_start < ev.bet_end_time, PredictError::InvalidFeeCurve);
+        require!(
+            max_fee_bps >= ev.buy_fee_bps && max_fee_bps <= config.max_buy_fee_bps,
+            PredictError::InvalidFeeCurve
+        );
+    }
+    if curve == FEE_CURVE_STEP {
+        require!((1..=FEE_CURVE_MAX_STEPS).contains(&steps), PredictError::InvalidFeeCurve);
+    }
+    require!(early_bird_discount_bps as u64 <= BPS_DENOM, PredictError::InvalidFeeCurve);
+    require!(
+        early_bird_discount_bps == 0 || early_bird_end_time <= curve_start || curve == FEE_CURVE_FLAT,
+        PredictError::InvalidFeeCurve
+    );
+    require!(early_bird_end_time <= ev.bet_end_time, PredictError::InvalidFeeCurve);
+
+    ev.fee_curve = curve;
+    ev.fee_curve_start = curve_start;
+    ev.fee_curve_max_bps = if curve == FEE_CURVE_FLAT { ev.buy_fee_bps } else { max_fee_bps };
+    ev.fee_curve_steps = if curve == FEE_CURVE_STEP { steps } else { 0 };
+    ev.early_bird_end_time = early_bird_end_time;
+    ev.early_bird_discount_bps = early_bird_discount_bps;
+
+    Ok(())
+}
+
+// Creator picks who receives the creator commission; locked once the first buy happens.
+pub(crate) fn set_creator_payouts(
+    ctx: Context<SetCreatorPayouts>,
+    payout: Pubkey,
+    co_creators: Vec<CreatorShare>,
+) -> Result<()> {
+    let ev = &mut ctx.accounts.event;
+
+    require!(!ev.has_buys, PredictError::CreatorPayoutsLocked);
+    require!(payout != Pubkey::default(), PredictError::InvalidCreatorShares);
+    require!(co_creators.len() <= MAX_CO_CREATORS, PredictError::InvalidCreatorShares);
+
+    let mut total_bps: u64 = 0;
+    for co in co_creators.iter() {
+        require!(co.wallet != Pubkey::default() && co.share_bps > 0, PredictError::InvalidCreatorShares);
+        total_bps = total_bps.saturating_add(co.share_bps as u64);
+    }
+    require!(total_bps <= BPS_DENOM, PredictError::InvalidCreatorShares);
+
+    let mut shares = [CreatorShare::default(); MAX_CO_CREATORS];
+    shares[..co_creators.len()].copy_from_slice(&co_creators);
+
+    ev.creator_payout = payout;
+    ev.co_creator_count = co_creators.len() as u8;
+    ev.co_creators = shares;
+
+    Ok(())
+}
+
+// Lets the creator draw commission while betting is open; locked once the first buy happens.
+pub(crate) fn set_creator_vesting(
+    ctx: Context<SetCreatorVesting>,
+    vesting: u8,
+    tranches: u8,
+    holdback_bps: u16,
+) -> Result<()> {
+    let ev = &mut ctx.accounts.event;
+
+    require!(!ev.has_buys, PredictError::CreatorPayoutsLocked);
+    require!(vesting <= VESTING_TRANCHES, PredictError::InvalidVesting);
+    if vesting == VESTING_TRANCHES {
+        require!((1..=VESTING_MAX_TRANCHES).contains(&tranches), PredictError::InvalidVesting);
+    }
+    if vesting != VESTING_NONE {
+        require!(
+            holdback_bps >= MIN_VESTING_HOLDBACK_BPS && holdback_bps as u64 <= BPS_DENOM,
+            PredictError::InvalidVesting
+        );
+    }
+
+    ev.creator_vesting = vesting;
+    ev.vesting_tranches = if vesting == VESTING_TRANCHES { tranches } else { 0 };
+    ev.vesting_holdback_bps = if vesting == VESTING_NONE { 0 } else { holdback_bps };
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeCurve<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorPayouts<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorVesting<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+}
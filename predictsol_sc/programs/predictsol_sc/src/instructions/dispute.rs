@@ -0,0 +1,193 @@
+use crate::*;
+
+// Anyone can challenge a provisional result inside the dispute window by posting the
+// event's bond. The result is then frozen until the admin decides (resolve_dispute).
+pub(crate) fn challenge_result(ctx: Context<ChallengeResult>, proposed_option: u8) -> Result<()> {
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require!(ev.kind != EVENT_KIND_SCALAR, PredictError::InvalidEventKind);
+    require!(ev.dispute_status == DISPUTE_NONE, PredictError::DisputeOpen);
+    require!(now < ev.dispute_deadline, PredictError::DisputeWindowClosed);
+    require!(proposed_option <= 2, PredictError::InvalidWinningOption);
+    require!(proposed_option != ev.winning_option, PredictError::InvalidWinningOption);
+
+    // the bond sits on the dispute account next to its rent
+    let bond = ev.dispute_bond_lamports;
+    anchor_lang::solana_program::program::invoke(
+        &system_instruction::transfer(&ctx.accounts.challenger.key(), &ctx.accounts.dispute.key(), bond),
+        &[
+            ctx.accounts.challenger.to_account_info(),
+            ctx.accounts.dispute.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    let dispute = &mut ctx.accounts.dispute;
+    dispute.event = ev.key();
+    dispute.challenger = ctx.accounts.challenger.key();
+    dispute.bond_lamports = bond;
+    dispute.proposed_option = proposed_option;
+    dispute.challenged_at = now;
+    dispute.bump = ctx.bumps.dispute;
+
+    ev.dispute_status = DISPUTE_OPEN;
+    Ok(())
+}
+
+// Admin decision on a challenged result. final_option 0 = no winner, 1 / 2 = winning side.
+// An overturned result returns the bond to the challenger; an upheld one slashes it to the house.
+pub(crate) fn resolve_dispute(ctx: Context<ResolveDispute>, final_option: u8) -> Result<()> {
+    require!(final_option <= 2, PredictError::InvalidWinningOption);
+    let now = Clock::get()?.unix_timestamp;
+
+    let ev = &mut ctx.accounts.event;
+    require!(ev.dispute_status == DISPUTE_OPEN, PredictError::NoOpenDispute);
+    require!(
+        now < ctx.accounts.dispute.challenged_at.saturating_add(DISPUTE_RESOLUTION_TIMEOUT_SECS),
+        PredictError::DisputeTimedOut
+    );
+
+    let was_winner = ev.result_status == RESULT_RESOLVED_WINNER;
+    let overturned = final_option != ev.winning_option;
+    if overturned {
+        ev.winning_option = final_option;
+        // admin found no winner: treated like a result without consensus
+        ev.result_status = if final_option == 0 { RESULT_FINALIZED_BELOW_THRESHOLD } else { RESULT_RESOLVED_WINNER };
+
+        let is_winner = ev.result_status == RESULT_RESOLVED_WINNER;
+        if was_winner && !is_winner {
+            forfeit_creator_commission(
+                ev,
+                &ctx.accounts.commission_vault.to_account_info(),
+                &ctx.accounts.collateral_vault.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.bumps.commission_vault,
+            )?;
+        } else if !was_winner && is_winner {
+            reinstate_creator_commission(
+                ev,
+                &ctx.accounts.collateral_vault.to_account_info(),
+                &ctx.accounts.commission_vault.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+                ctx.bumps.collateral_vault,
+            )?;
+        }
+    } else {
+        let bond = ctx.accounts.dispute.bond_lamports;
+        ctx.accounts.dispute.sub_lamports(bond)?;
+        ctx.accounts.house_treasury.add_lamports(bond)?;
+        record_treasury_inflow(&ctx.accounts.house_treasury.to_account_info(), &ev.key(), bond)?;
+        ev.house_lamports_paid = ev.house_lamports_paid.checked_add(bond).ok_or(PredictError::MathOverflow)?;
+    }
+
+    // final from here; sweep and delete windows restart from the decision
+    ev.dispute_status = DISPUTE_SETTLED;
+    ev.dispute_deadline = now;
+    ev.resolved_at = now;
+
+    Ok(())
+}
+
+// Permissionless once the admin let DISPUTE_RESOLUTION_TIMEOUT_SECS pass without a decision:
+// the provisional result stands and the challenger gets the bond back.
+pub(crate) fn settle_expired_dispute(ctx: Context<SettleExpiredDispute>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let ev = &mut ctx.accounts.event;
+    require!(ev.dispute_status == DISPUTE_OPEN, PredictError::NoOpenDispute);
+    require!(
+        now >= ctx.accounts.dispute.challenged_at.saturating_add(DISPUTE_RESOLUTION_TIMEOUT_SECS),
+        PredictError::DisputeNotTimedOut
+    );
+
+    // final from here, as after resolve_dispute; the dispute closes to the challenger
+    ev.dispute_status = DISPUTE_SETTLED;
+    ev.dispute_deadline = now;
+    ev.resolved_at = now;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ChallengeResult<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + 32 + 32 + 8 + 1 + 8 + 1,
+        seeds = [SEED_DISPUTE, event.key().as_ref()],
+        bump
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleExpiredDispute<'info> {
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    // rent and the bond go back to the challenger
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [SEED_DISPUTE, event.key().as_ref()],
+        bump = dispute.bump,
+        has_one = challenger @ PredictError::Unauthorized
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: challenger wallet recorded on the dispute
+    #[account(mut)]
+    pub challenger: AccountInfo<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump = config.bump,
+        has_one = admin @ PredictError::Unauthorized
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    // rent (and a returned bond) goes back to the challenger
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [SEED_DISPUTE, event.key().as_ref()],
+        bump = dispute.bump,
+        has_one = challenger @ PredictError::Unauthorized
+    )]
+    pub dispute: Account<'info, Dispute>,
+
+    /// CHECK: challenger wallet recorded on the dispute
+    #[account(mut)]
+    pub challenger: AccountInfo<'info>,
+
+    /// CHECK: Fixed house wallet, receives a slashed bond
+    #[account(mut, address = event.house_wallet)]
+    pub house_treasury: AccountInfo<'info>,
+
+    /// CHECK: Commission vault PDA
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()], bump)]
+    pub commission_vault: AccountInfo<'info>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(mut, seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
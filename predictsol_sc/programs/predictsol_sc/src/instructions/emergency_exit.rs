@@ -0,0 +1,338 @@
+use crate::*;
+
+// Escape hatch for an event that never resolved (e.g. its Truth question was never
+// finalized). Once the deadline passes any holder burns either side for a pro-rata
+// share of the collateral vault, like the no-winner payout. No admin involved.
+// The first exit settles the event as RESULT_EMERGENCY_EXIT, so the unclaimed sweep
+// and delete_event run as for any other result.
+pub(crate) fn emergency_exit(ctx: Context<EmergencyExit>, side: u8, amount: u64) -> Result<()> {
+    require_native_collateral(&ctx.accounts.event)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+    // conditional events hold parent tokens, see emergency_exit_conditional
+    require!(ev.kind != EVENT_KIND_CONDITIONAL, PredictError::InvalidEventKind);
+    if open_emergency_exit(ev, now)? {
+        // commissions settle as on a no-winner result
+        sweep_house_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.house_treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+        forfeit_creator_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+    }
+
+    let expected_mint = mint_for_side(ev, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    // pro rata over every outstanding token of both sides, before this burn; the LMSR
+    // maker's surplus stays for the creator
+    let keep = vault_keep_lamports()?;
+    let lmsr_surplus = lmsr_creator_surplus(ev, ctx.accounts.lmsr_market.as_ref())?;
+    let available = ctx
+        .accounts
+        .collateral_vault
+        .lamports()
+        .saturating_sub(keep)
+        .saturating_sub(lmsr_surplus);
+    let outstanding = ev
+        .outstanding_true
+        .checked_add(ev.outstanding_false)
+        .ok_or(PredictError::MathOverflow)?;
+    require!(outstanding > 0, PredictError::NothingToClaim);
+    let payout = mul_div_floor(available, amount, outstanding)?;
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let side_outstanding = outstanding_for_side_mut(ev, side)?;
+    *side_outstanding = side_outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ev.key(),
+        ctx.bumps.collateral_vault,
+        payout,
+    )?;
+
+    ev.total_collateral_lamports = ev.total_collateral_lamports.saturating_sub(payout);
+
+    Ok(())
+}
+
+// Token-collateral counterpart of emergency_exit: a pro-rata share of the token vault,
+// plus the holder's part of any forfeited creator commission in the commission token vault.
+// The house commission stays pending for sweep_house_commission_token.
+pub(crate) fn emergency_exit_token(ctx: Context<EmergencyExitToken>, side: u8, amount: u64) -> Result<()> {
+    require_token_collateral(&ctx.accounts.event)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+    require!(ev.kind != EVENT_KIND_CONDITIONAL, PredictError::InvalidEventKind);
+    if open_emergency_exit(ev, now)? && ev.creator_clawback {
+        let forfeited = ev.pending_creator_commission;
+        record_forfeited_commission(ev, forfeited)?;
+        ev.pending_creator_commission = 0;
+    }
+
+    let expected_mint = mint_for_side(ev, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    // pro rata over every outstanding token of both sides, before this burn
+    let outstanding = ev
+        .outstanding_true
+        .checked_add(ev.outstanding_false)
+        .ok_or(PredictError::MathOverflow)?;
+    require!(outstanding > 0, PredictError::NothingToClaim);
+    let base = mul_div_floor(ctx.accounts.token_vault.amount, amount, outstanding)?;
+    let share = forfeited_commission_share(ev, amount)?;
+    require!(ctx.accounts.commission_token_vault.amount >= share, PredictError::VaultInsufficientFunds);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let side_outstanding = outstanding_for_side_mut(ev, side)?;
+    *side_outstanding = side_outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    for (vault, part) in [(&ctx.accounts.token_vault, base), (&ctx.accounts.commission_token_vault, share)] {
+        if part == 0 {
+            continue;
+        }
+        transfer_from_token_vault(
+            &ctx.accounts.token_program.to_account_info(),
+            &vault.to_account_info(),
+
+            &ctx.accounts.collateral_mint.to_account_info(),
+            &ctx.accounts.user_collateral_ata.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ev.key(),
+            ctx.bumps.collateral_vault,
+
+            ctx.accounts.collateral_mint.decimals,
+            part,
+        )?;
+    }
+
+    let payout = base.checked_add(share).ok_or(PredictError::MathOverflow)?;
+    ev.total_collateral_lamports = ev.total_collateral_lamports.saturating_sub(payout);
+
+    Ok(())
+}
+
+// Conditional counterpart of emergency_exit, paid in parent tokens from the conditional
+// vault. Conditional buys carry no fee, so there is no commission to settle.
+pub(crate) fn emergency_exit_conditional(ctx: Context<EmergencyExitConditional>, side: u8, amount: u64) -> Result<()> {
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+    require!(ev.kind == EVENT_KIND_CONDITIONAL, PredictError::InvalidEventKind);
+    open_emergency_exit(ev, now)?;
+
+    let expected_mint = mint_for_side(ev, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    let outstanding = ev
+        .outstanding_true
+        .checked_add(ev.outstanding_false)
+        .ok_or(PredictError::MathOverflow)?;
+    require!(outstanding > 0, PredictError::NothingToClaim);
+    let payout = mul_div_floor(ctx.accounts.token_vault.amount, amount, outstanding)?;
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let side_outstanding = outstanding_for_side_mut(ev, side)?;
+    *side_outstanding = side_outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    transfer_from_conditional_vault(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.token_vault.to_account_info(),
+
+        &ctx.accounts.collateral_mint.to_account_info(),
+        &ctx.accounts.user_collateral_ata.to_account_info(),
+        &ctx.accounts.conditional_market.to_account_info(),
+        &ev.key(),
+        ctx.accounts.conditional_market.bump,
+
+        ctx.accounts.collateral_mint.decimals,
+        payout,
+    )?;
+
+    let market = &mut ctx.accounts.conditional_market;
+    market.total_collateral = market.total_collateral.checked_sub(payout).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct EmergencyExit<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump,
+        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub commission_vault: SystemAccount<'info>,
+
+    /// CHECK: Fixed house wallet, receives the pending house commission on the first exit
+    #[account(mut, address = event.house_wallet)]
+    pub house_treasury: AccountInfo<'info>,
+
+    // Required when the event has an LMSR maker
+    #[account(seeds = [SEED_LMSR_MARKET, event.key().as_ref()], bump = lmsr_market.bump)]
+    pub lmsr_market: Option<Account<'info, LmsrMarket>>,
+
+    // TRUE or FALSE mint, checked against `side`
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyExitToken<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    /// CHECK: collateral vault PDA, authority of the token vaults
+    #[account(seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [SEED_TOKEN_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub commission_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = event.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_collateral_ata.mint == event.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // TRUE or FALSE mint, checked against `side`
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyExitConditional<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_CONDITIONAL_MARKET, event.key().as_ref()],
+        bump = conditional_market.bump
+    )]
+    pub conditional_market: Box<Account<'info, ConditionalMarket>>,
+
+    #[account(mut, address = conditional_market.token_vault @ PredictError::InvalidVault)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = conditional_market.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_collateral_ata.mint == conditional_market.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // child TRUE or FALSE mint, checked against `side`
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,1735 @@
+use crate::*;
+
+pub(crate) fn initialize_event_counter(ctx: Context<InitializeEventCounter>) -> Result<()> {
+    ctx.accounts.counter.creator = ctx.accounts.creator.key();
+    ctx.accounts.counter.count = 0;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn create_event_core(
+    ctx: Context<CreateEventCore>,
+    title: String,
+    category: u8,
+    bet_end_time: i64,
+    commit_end_time: i64,
+    reveal_end_time: i64,
+    truth_question: Option<Pubkey>,
+    fee_schedule: Option<FeeSchedule>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!((10..=150).contains(&title.len()), PredictError::InvalidTitleLength);
+    require!(bet_end_time > now, PredictError::InvalidBetEndTime);
+    require!(bet_end_time < commit_end_time, PredictError::InvalidTimeOrder);
+    require!(commit_end_time < reveal_end_time, PredictError::InvalidTimeOrder);
+    require!(category <= 3, PredictError::InvalidCategory);
+
+    let counter = &mut ctx.accounts.counter;
+    let event_id = counter.count;
+
+    let ev = &mut ctx.accounts.event;
+    ev.creator = ctx.accounts.creator.key();
+    ev.event_id = event_id;
+    ev.title = title;
+    ev.category = category;
+    ev.bet_end_time = bet_end_time;
+    ev.commit_end_time = commit_end_time;
+    ev.reveal_end_time = reveal_end_time;
+    ev.created_at = now;
+    ev.truth_question = truth_question.unwrap_or_default();
+    ev.total_collateral_lamports = 0;
+    ev.total_issued_per_side = 0;
+    ev.outstanding_true = 0;
+    ev.outstanding_false = 0;
+    ev.resolved = false;
+    ev.winning_option = 0;
+    ev.winning_percent_bps = 0;
+    ev.votes_option_1 = 0;
+    ev.votes_option_2 = 0;
+    ev.consensus_threshold_bps = ctx.accounts.config.consensus_threshold_bps;
+    ev.resolved_at = 0;
+    ev.result_status = RESULT_PENDING;
+    ev.total_truth_commission_sent = 0;
+    ev.pending_creator_commission = 0;
+    ev.pending_house_commission = 0;
+    ev.unclaimed_swept = false;
+    ev.swept_at = 0;
+    ev.raydium_pool = Pubkey::default();
+    ev.kind = EVENT_KIND_BINARY;
+    ev.collateral_mint = Pubkey::default();
+    ev.collateral_decimals = 9;
+
+    // snapshot of the protocol config in force at creation
+    let config = &ctx.accounts.config;
+    ev.house_wallet = config.house_wallet;
+    ev.redeem_fee_bps = config.redeem_fee_bps;
+    ev.unclaimed_sweep_delay_secs = config.unclaimed_sweep_delay_secs;
+    ev.emergency_exit_delay_secs = config.emergency_exit_delay_secs;
+    ev.emergency_exit_active = false;
+    ev.dispute_window_secs = config.dispute_window_secs;
+    ev.dispute_bond_lamports = config.dispute_bond_lamports;
+    ev.dispute_deadline = 0;
+    ev.dispute_status = DISPUTE_NONE;
+    ev.revote_max_rounds = config.revote_max_rounds;
+    ev.revote_funding_bps = config.revote_funding_bps;
+    ev.revote_round = 0;
+    ev.revote_history = [RevoteRound::default(); MAX_REVOTE_ROUNDS];
+    ev.has_buys = false;
+    ev.layout_version = EVENT_LAYOUT_VERSION;
+
+    let schedule = fee_schedule_or_default(config, fee_schedule)?;
+    ev.buy_fee_bps = schedule.fee_bps;
+    ev.truth_share_bps = schedule.truth_share_bps;
+    ev.creator_share_bps = schedule.creator_share_bps;
+    ev.house_share_bps = (BPS_DENOM as u16)
+        .saturating_sub(schedule.truth_share_bps)
+        .saturating_sub(schedule.creator_share_bps);
+
+    ev.fee_curve = FEE_CURVE_FLAT;
+    ev.fee_curve_start = 0;
+    ev.fee_curve_max_bps = schedule.fee_bps;
+    ev.fee_curve_steps = 0;
+    ev.early_bird_end_time = 0;
+    ev.early_bird_discount_bps = 0;
+
+    ev.referral_share_bps = config.referral_share_bps;
+    ev.total_referral_commission = 0;
+
+    ev.creator_clawback = config.creator_clawback;
+    ev.forfeited_commission = 0;
+    ev.forfeit_base_units = 0;
+
+    ev.creator_payout = ctx.accounts.creator.key();
+    ev.co_creator_count = 0;
+    ev.co_creators = [CreatorShare::default(); MAX_CO_CREATORS];
+
+    ev.creator_vesting = VESTING_NONE;
+    ev.vesting_tranches = 0;
+    ev.vesting_holdback_bps = 0;
+    ev.claimed_creator_commission = 0;
+    ev.house_lamports_paid = 0;
+    ev.pause = PauseState::default();
+
+    counter.count = counter.count.checked_add(1).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+pub(crate) fn create_event_mints(ctx: Context<CreateEventMints>) -> Result<()> {
+
+    let event_key = ctx.accounts.event.key();
+    let payer_ai = ctx.accounts.creator.to_account_info();
+    let system_ai = ctx.accounts.system_program.to_account_info();
+    let token_ai = ctx.accounts.token_program.to_account_info();
+    let rent_ai = ctx.accounts.rent.to_account_info();
+    // positions are 1:1 with collateral base units, so they share its decimals
+    let decimals = ctx.accounts.event.collateral_decimals;
+
+    // 1) collateral vault PDA (system-owned, 0 space)
+    let vault_bump = ctx.bumps.collateral_vault;
+    let vault_seeds: [&[u8]; 3] = [
+        SEED_COLLATERAL_VAULT,
+        event_key.as_ref(),
+        &[vault_bump],
+    ];
+    create_system_pda_0space(
+        &payer_ai,
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &system_ai,
+        &vault_seeds,
+    )?;
+
+    // commission vault PDA (system-owned, 0 space), holds creator + house commissions
+    let commission_bump = ctx.bumps.commission_vault;
+    let commission_seeds: [&[u8]; 3] = [
+        SEED_COMMISSION_VAULT,
+        event_key.as_ref(),
+        &[commission_bump],
+    ];
+    create_system_pda_0space(
+        &payer_ai,
+        &ctx.accounts.commission_vault.to_account_info(),
+        &system_ai,
+        &commission_seeds,
+    )?;
+
+    // 2) TRUE mint PDA
+    let true_bump = ctx.bumps.true_mint;
+    let true_seeds: [&[u8]; 3] = [
+        SEED_TRUE_MINT,
+        event_key.as_ref(),
+        &[true_bump],
+    ];
+    create_and_init_spl_mint_pda(
+        &payer_ai,
+        &ctx.accounts.true_mint.to_account_info(),
+        &token_ai,
+        &system_ai,
+        &rent_ai,
+        &ctx.accounts.mint_authority.key(),
+        &true_seeds,
+        decimals,
+    )?;
+
+    // 3) FALSE mint PDA
+    let false_bump = ctx.bumps.false_mint;
+    let false_seeds: [&[u8]; 3] = [
+        SEED_FALSE_MINT,
+        event_key.as_ref(),
+        &[false_bump],
+    ];
+    create_and_init_spl_mint_pda(
+        &payer_ai,
+        &ctx.accounts.false_mint.to_account_info(),
+        &token_ai,
+        &system_ai,
+        &rent_ai,
+        &ctx.accounts.mint_authority.key(),
+        &false_seeds,
+        decimals,
+    )?;
+
+    // ---------- Create Metadata for TRUE ----------
+    let [(true_name, true_symbol, true_uri), (false_name, false_symbol, false_uri)] =
+        position_token_labels(&ctx.accounts.event, &ctx.accounts.config);
+
+    let true_metadata = ctx.accounts.true_metadata.key();
+    let false_metadata = ctx.accounts.false_metadata.key();
+
+
+    // signer seeds for mint authority
+    let auth_bump = ctx.bumps.mint_authority;
+    let auth_seeds = &[
+        SEED_MINT_AUTH,
+        event_key.as_ref(),
+        &[auth_bump],
+    ];
+    let signer = &[&auth_seeds[..]];
+
+    // Build metadata struct
+    let true_data = DataV2 {
+        name: true_name,
+        symbol: true_symbol,
+        uri: true_uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    let false_data = DataV2 {
+        name: false_name,
+        symbol: false_symbol,
+        uri: false_uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    };
+
+    // TRUE metadata CPI
+    let ix = ix_create_metadata_account_v3(
+        true_metadata,
+        ctx.accounts.true_mint.key(),
+        ctx.accounts.mint_authority.key(),
+        ctx.accounts.creator.key(),
+        ctx.accounts.mint_authority.key(), // update authority = same PDA
+        true_data,
+        false, // not_mutable
+    )?;
+
+    invoke_signed(
+        &ix,
+        &[
+            //ctx.accounts.metadata_program.to_account_info(),
+            ctx.accounts.true_metadata.to_account_info(),
+            ctx.accounts.true_mint.to_account_info(),
+            ctx.accounts.mint_authority.to_account_info(),
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.mint_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+        ],
+        signer,
+    )?;
+
+
+    // FALSE metadata CPI
+    let ix = ix_create_metadata_account_v3(
+        false_metadata,
+        ctx.accounts.false_mint.key(),
+        ctx.accounts.mint_authority.key(),
+        ctx.accounts.creator.key(),
+        ctx.accounts.mint_authority.key(),
+        false_data,
+        false, // not_mutable
+    )?;
+
+    invoke_signed(
+        &ix,
+        &[
+            //ctx.accounts.metadata_program.to_account_info(),
+            ctx.accounts.false_metadata.to_account_info(),
+            ctx.accounts.false_mint.to_account_info(),
+            ctx.accounts.mint_authority.to_account_info(),
+            ctx.accounts.creator.to_account_info(),
+            ctx.accounts.mint_authority.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+        ],
+        signer,
+    )?;
+
+
+    // 4) Save addresses to event
+    let ev = &mut ctx.accounts.event;
+    ev.collateral_vault = ctx.accounts.collateral_vault.key();
+    ev.true_mint = ctx.accounts.true_mint.key();
+    ev.false_mint = ctx.accounts.false_mint.key();
+
+    Ok(())
+}
+
+pub(crate) fn buy_positions_with_fee(ctx: Context<BuyPositionsWithFee>, lamports: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_BUY)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let accs = &mut *ctx.accounts;
+    buy_pair_core(
+        PairBuyAccounts {
+            user: &accs.user,
+            event: &mut accs.event,
+            config: &accs.config,
+            collateral_vault: &accs.collateral_vault,
+            commission_vault: &accs.commission_vault,
+            mint_authority: accs.mint_authority.to_account_info(),
+            true_mint: accs.true_mint.to_account_info(),
+            false_mint: accs.false_mint.to_account_info(),
+            user_true_ata: accs.user_true_ata.to_account_info(),
+            user_false_ata: accs.user_false_ata.to_account_info(),
+            truth_network_question: &accs.truth_network_question,
+            truth_network_vault: accs.truth_network_vault.to_account_info(),
+            user_stats: &mut accs.user_stats,
+            user_position: &mut accs.user_position,
+            referral: accs.referral.as_mut(),
+            referral_vault: accs.referral_vault.as_ref(),
+            token_program: accs.token_program.to_account_info(),
+            system_program: &accs.system_program,
+        },
+        PairBuyBumps {
+            collateral_vault: ctx.bumps.collateral_vault,
+            mint_authority: ctx.bumps.mint_authority,
+            user_stats: ctx.bumps.user_stats,
+            user_position: ctx.bumps.user_position,
+        },
+        lamports,
+        now,
+    )?;
+
+    Ok(())
+}
+
+pub(crate) fn redeem_pair_while_active(ctx: Context<RedeemPairWhileActive>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_PAIR_REDEEM)?;
+    require_native_collateral(&ctx.accounts.event)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    // Must be during betting period ("pair redeem"), unless the event was voided:
+    // then pairs redeem at full value until the unclaimed sweep
+    let now = Clock::get()?.unix_timestamp;
+    let voided = ctx.accounts.event.result_status == RESULT_VOIDED;
+    if voided {
+        require!(!ctx.accounts.event.unclaimed_swept, PredictError::RedemptionExpired);
+    } else {
+        require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+
+        // the event should not be resolved
+        require!(!ctx.accounts.event.resolved, PredictError::EventAlreadyResolved);
+    }
+    require!(ctx.accounts.event.kind != EVENT_KIND_CONDITIONAL, PredictError::InvalidEventKind);
+
+    // User wallet must have enough TRUE and FALSE to burn
+    require!(ctx.accounts.user_true_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+    require!(ctx.accounts.user_false_ata.amount >= amount, PredictError::InsufficientFalseBalance);
+
+    // Burn TRUE tokens
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.true_mint.to_account_info(),
+                from: ctx.accounts.user_true_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Burn FALSE tokens
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.false_mint.to_account_info(),
+                from: ctx.accounts.user_false_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let payout = if voided {
+        // full value plus both tokens' share of the returned commission
+        let units = amount.checked_mul(2).ok_or(PredictError::MathOverflow)?;
+        amount
+            .checked_add(forfeited_commission_share(&ctx.accounts.event, units)?)
+            .ok_or(PredictError::MathOverflow)?
+    } else {
+        payout_after_fee(amount, ctx.accounts.event.redeem_fee_bps)?
+    };
+
+    // Identify the "zero-line"— the amount of money that must stay 
+    // in the account so it isn't deleted by the network.
+    // Fetches the current rent configuration from the Solana network
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
+
+    require!(
+        vault_lamports >= keep.saturating_add(payout),
+        PredictError::VaultInsufficientFunds
+    );
+
+    // Vault PDA signs the SOL transfer
+    let event_key = ctx.accounts.event.key();
+    let vault_bump = ctx.bumps.collateral_vault;
+    let vault_seeds: [&[u8]; 3] = [
+        SEED_COLLATERAL_VAULT,
+        event_key.as_ref(),
+        &[vault_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::transfer(
+            &ctx.accounts.collateral_vault.key(),
+            &ctx.accounts.user.key(),
+            payout,
+        ),
+        &[
+            ctx.accounts.collateral_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&vault_seeds],
+    )?;
+
+    // Accounting updates
+    // Keeps event state updated for later redemption stages
+    ctx.accounts.event.total_collateral_lamports = ctx.accounts.event
+        .total_collateral_lamports
+        .checked_sub(payout)
+        .ok_or(PredictError::MathOverflow)?;
+
+    // ctx.accounts.event.total_issued_per_side = ctx.accounts.event
+    //     .total_issued_per_side
+    //     .checked_sub(amount)
+    //     .ok_or(PredictError::MathOverflow)?;
+
+    ctx.accounts.event.outstanding_true = ctx.accounts.event
+        .outstanding_true
+        .checked_sub(amount)
+        .ok_or(PredictError::MathOverflow)?;
+
+    ctx.accounts.event.outstanding_false = ctx.accounts.event
+        .outstanding_false
+        .checked_sub(amount)
+        .ok_or(PredictError::MathOverflow)?;
+
+    let event_key = ctx.accounts.event.key();
+    record_redemption(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.user_position,
+        &ctx.accounts.user.key(),
+        &event_key,
+        [ctx.bumps.user_stats, ctx.bumps.user_position],
+        payout,
+        amount,
+        amount,
+        0,
+        now,
+    )?;
+
+    Ok(())
+}
+
+pub(crate) fn fetch_and_store_winner(ctx: Context<FetchAndStoreWinner>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_RESOLVE)?;
+    let ev = &mut ctx.accounts.event;
+    let q = &mut ctx.accounts.truth_network_question;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Betting must be finished
+    require!(now >= ev.bet_end_time, PredictError::BettingStillActive);
+
+    // Don't allow calling twice
+    require!(!ev.resolved, PredictError::EventAlreadyResolved);
+    require!(!ev.emergency_exit_active, PredictError::EmergencyExitActive);
+    require!(ev.kind != EVENT_KIND_SCALAR, PredictError::InvalidEventKind);
+
+    require_keys_eq!(ev.truth_question, q.key(), PredictError::TruthQuestionMismatch);
+
+    require!(now >= q.reveal_end_time, PredictError::TruthVotingStillActive);
+
+    // CPI: finalize voting on Truth Network
+    let question_id = q.id;
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.truth_network_program.to_account_info(),
+        FinalizeVoting { question: q.to_account_info() },
+    );
+    finalize_voting(cpi_ctx, question_id)?;
+
+    // Refresh the account after CPI
+    q.reload()?;
+
+    // Save votes
+    let v1 = q.votes_option_1;
+    let v2 = q.votes_option_2;
+    let total_votes = v1.checked_add(v2).ok_or(PredictError::MathOverflow)?;
+
+    ev.votes_option_1 = v1;
+    ev.votes_option_2 = v2;
+    ev.resolved_at = now;
+
+    // Mark event as resolved
+    ev.resolved = true;
+
+    // provisional until the dispute window passes unchallenged
+    ev.dispute_status = DISPUTE_NONE;
+    ev.dispute_deadline = if ev.dispute_window_secs > 0 {
+        now.checked_add(ev.dispute_window_secs).ok_or(PredictError::MathOverflow)?
+    } else {
+        0
+    };
+
+    // sweep house commission once, regardless of outcome
+    // (token-collateral events pay it through sweep_house_commission_token)
+    if ev.collateral_mint == Pubkey::default() {
+        let vault_bump = ctx.bumps.commission_vault;
+        sweep_house_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.house_treasury.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            vault_bump,
+        )?;
+    }
+
+    // no votes
+    if total_votes == 0 {
+        ev.winning_option = 0;
+        ev.winning_percent_bps = 0;
+        ev.result_status = RESULT_FINALIZED_NO_VOTES;
+        forfeit_creator_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+        return Ok(());
+    }
+
+    // tie
+    if q.winning_option == 0 {
+        ev.winning_option = 0;
+        ev.winning_percent_bps = 5000;
+        ev.result_status = RESULT_FINALIZED_TIE;
+        forfeit_creator_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+        return Ok(());
+    }
+
+    // winner (1 or 2)
+    require!(
+        q.winning_option == 1 || q.winning_option == 2,
+        PredictError::InvalidWinningOption
+    );
+
+    let winning_votes = if q.winning_option == 1 {v1} else {v2};
+
+    // winning_percent_bps = winning_votes * 10000 / total_votes
+    let wp_bps_u64 = winning_votes
+        .checked_mul(BPS_DENOM)
+        .ok_or(PredictError::MathOverflow)?
+        .checked_div(total_votes)
+        .ok_or(PredictError::MathOverflow)?;
+
+    let wp_bps: u16 = wp_bps_u64.min(10_000) as u16;
+    ev.winning_percent_bps = wp_bps;
+
+    // if winner but below threshold => resolved but NO winner
+    if wp_bps < ev.consensus_threshold_bps {
+        ev.winning_option = 0;
+        ev.result_status = RESULT_FINALIZED_BELOW_THRESHOLD;
+        forfeit_creator_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+        return Ok(());
+    }
+
+    // Store result into PredictSol event
+    ev.winning_option = q.winning_option;
+    ev.result_status = RESULT_RESOLVED_WINNER;
+    ev.swept_at = 0;
+
+    Ok(())
+}
+
+pub(crate) fn redeem_winner_after_final(ctx: Context<RedeemWinnerAfterFinal>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_FINAL_REDEEM)?;
+    require_result_final(&ctx.accounts.event)?;
+    require_native_collateral(&ctx.accounts.event)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let ev = &mut ctx.accounts.event;
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require!(ev.result_status == RESULT_RESOLVED_WINNER, PredictError::InvalidResultStatus);
+    require!(!ev.unclaimed_swept, PredictError::RedemptionExpired);
+    require!(ev.kind != EVENT_KIND_CONDITIONAL, PredictError::InvalidEventKind);
+
+    // Determine which token mint is winning
+    let winning_mint = if ev.winning_option == 1 {
+        ev.true_mint
+    } else if ev.winning_option == 2 {
+        ev.false_mint
+    } else {
+        return err!(PredictError::InvalidWinningOption);
+    };
+
+    // Ensure the provided mint/ata matches the winning side
+    require_keys_eq!(ctx.accounts.mint.key(), winning_mint, PredictError::NotWinningToken);
+
+    // User must have enough winning tokens to burn
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    // Burn winning token
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    match ev.winning_option {
+        1 => {
+            ev.outstanding_true = ev.outstanding_true.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+        }
+        2 => {
+            ev.outstanding_false = ev.outstanding_false.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+        }
+        _ => return err!(PredictError::InvalidWinningOption),
+    }
+
+    // ev.total_issued_per_side = ev
+    //     .total_issued_per_side
+    //     .checked_sub(amount)
+    //     .ok_or(PredictError::MathOverflow)?;
+
+    // payout = amount minus fee (e.g. 1.0 -> 0.99)
+    let payout = payout_after_fee(amount, ev.redeem_fee_bps)?;
+
+    // Rent safety
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
+    require!(
+        vault_lamports >= keep.saturating_add(payout),
+        PredictError::VaultInsufficientFunds
+    );
+
+    // Vault PDA signs SOL transfer
+    let event_key = ev.key();
+    let vault_bump = ctx.bumps.collateral_vault;
+    let vault_seeds: [&[u8]; 3] = [SEED_COLLATERAL_VAULT, event_key.as_ref(), &[vault_bump]];
+
+    invoke_signed(
+        &system_instruction::transfer(&ctx.accounts.collateral_vault.key(), &ctx.accounts.user.key(), payout),
+        &[
+            ctx.accounts.collateral_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&vault_seeds],
+    )?;
+
+    // Update the collateral vault
+    ev.total_collateral_lamports = ev.total_collateral_lamports
+        .checked_sub(payout)
+        .ok_or(PredictError::MathOverflow)?;
+
+    // the losing side's basis is realised along with the first winning redemption
+    let (true_units, false_units, losing_side) = if ev.winning_option == 1 { (amount, 0, 2) } else { (0, amount, 1) };
+    let now = Clock::get()?.unix_timestamp;
+    record_redemption(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.user_position,
+        &ctx.accounts.user.key(),
+        &event_key,
+        [ctx.bumps.user_stats, ctx.bumps.user_position],
+        payout,
+        true_units,
+        false_units,
+        losing_side,
+        now,
+    )?;
+
+    Ok(())
+}
+
+pub(crate) fn redeem_no_winner_after_final(
+    ctx: Context<RedeemNoWinnerAfterFinal>,
+    side: u8,
+    amount: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_FINAL_REDEEM)?;
+    require_result_final(&ctx.accounts.event)?;
+    require_native_collateral(&ctx.accounts.event)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let ev = &mut ctx.accounts.event;
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require!(!ev.unclaimed_swept, PredictError::RedemptionExpired);
+    require!(ev.kind != EVENT_KIND_CONDITIONAL, PredictError::InvalidEventKind);
+
+    // result status must be a no votes, tie or below threshold
+    // must not equal to RESULT_RESOLVED_WINNER or RESULT_RESOLVED_SCALAR
+    require!(ev.result_status != RESULT_RESOLVED_WINNER, PredictError::InvalidResultStatus);
+    require!(ev.result_status != RESULT_RESOLVED_SCALAR, PredictError::InvalidResultStatus);
+    // emergency exits pay pro rata through emergency_exit
+    require!(ev.result_status != RESULT_EMERGENCY_EXIT, PredictError::InvalidResultStatus);
+
+    // side must match the mint provided
+    let expected_mint = match side {
+        1 => ev.true_mint,
+        2 => ev.false_mint,
+        _ => return err!(PredictError::InvalidWinningOption),
+    };
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+
+    // burn the token the user is redeeming
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // ev.total_issued_per_side = ev
+    //     .total_issued_per_side
+    //     .checked_sub(amount)
+    //     .ok_or(PredictError::MathOverflow)?;
+
+    match side {
+        1 => {
+            ev.outstanding_true = ev.outstanding_true.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+        }
+        2 => {
+            ev.outstanding_false = ev.outstanding_false.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+        }
+        _ => return err!(PredictError::InvalidWinningOption),
+    }
+
+    // here we pay half (since redeeming only one side. example 1 TRUE = 0.5 SOL)
+    // plus this token's share of a forfeited creator commission
+    let pair_payout = payout_after_fee(amount, effective_redeem_fee_bps(ev))?;
+    let payout = pair_payout
+        .checked_div(2)
+        .ok_or(PredictError::MathOverflow)?
+        .checked_add(forfeited_commission_share(ev, amount)?)
+        .ok_or(PredictError::MathOverflow)?;
+
+    // rent safety
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
+    require!(
+        vault_lamports >= keep.saturating_add(payout),
+        PredictError::VaultInsufficientFunds
+    );
+
+    // vault signs SOL transfer
+    let event_key = ev.key();
+    let vault_bump = ctx.bumps.collateral_vault;
+    let vault_seeds: [&[u8]; 3] = [SEED_COLLATERAL_VAULT, event_key.as_ref(), &[vault_bump]];
+
+    invoke_signed(
+        &system_instruction::transfer(&ctx.accounts.collateral_vault.key(), &ctx.accounts.user.key(), payout),
+        &[
+            ctx.accounts.collateral_vault.to_account_info(),
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&vault_seeds],
+    )?;
+
+    // update collateral vault 
+    ev.total_collateral_lamports = ev.total_collateral_lamports
+        .checked_sub(payout)
+        .ok_or(PredictError::MathOverflow)?;
+
+    let (true_units, false_units) = if side == 1 { (amount, 0) } else { (0, amount) };
+    let now = Clock::get()?.unix_timestamp;
+    record_redemption(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.user_position,
+        &ctx.accounts.user.key(),
+        &event_key,
+        [ctx.bumps.user_stats, ctx.bumps.user_position],
+        payout,
+        true_units,
+        false_units,
+        0,
+        now,
+    )?;
+
+    Ok(())
+}
+
+// Pays the whole creator commission in one go. remaining_accounts = co-creator wallets, in order.
+pub(crate) fn claim_creator_commission<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimCreatorCommission<'info>>,
+) -> Result<()> {
+    require_native_collateral(&ctx.accounts.event)?;
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require_keys_eq!(ctx.accounts.creator.key(), ev.creator, PredictError::Unauthorized);
+
+    // vested part while betting; with clawback on, the rest only once the event has a winner
+    let amount = claimable_creator_commission(ev, now)?;
+    require!(amount > 0, PredictError::NothingToClaim);
+
+    // rent safety
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.commission_vault.to_account_info().lamports();
+    require!(vault_lamports >= keep.saturating_add(amount), PredictError::VaultInsufficientFunds);
+
+    let co_count = ev.co_creator_count as usize;
+    require!(ctx.remaining_accounts.len() == co_count, PredictError::InvalidRemainingAccounts);
+    let (payout_amount, shares) = creator_commission_splits(ev, amount)?;
+
+    // transfer
+    let event_key = ev.key();
+    let vault_bump = ctx.bumps.commission_vault;
+    let vault_ai = ctx.accounts.commission_vault.to_account_info();
+    let system_ai = ctx.accounts.system_program.to_account_info();
+
+    transfer_from_commission_vault(
+        &vault_ai,
+        &ctx.accounts.creator_payout.to_account_info(),
+        &system_ai,
+        &event_key,
+        vault_bump,
+        payout_amount,
+    )?;
+
+    for (i, dest) in ctx.remaining_accounts.iter().enumerate() {
+        require_keys_eq!(dest.key(), ev.co_creators[i].wallet, PredictError::InvalidRemainingAccounts);
+        transfer_from_commission_vault(&vault_ai, dest, &system_ai, &event_key, vault_bump, shares[i])?;
+    }
+
+    ev.pending_creator_commission = ev
+        .pending_creator_commission
+        .checked_sub(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    ev.claimed_creator_commission = ev
+        .claimed_creator_commission
+        .checked_add(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+pub(crate) fn sweep_unclaimed_to_house(ctx: Context<SweepUnclaimedToHouse>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_SWEEP)?;
+    require_result_final(&ctx.accounts.event)?;
+    require_native_collateral(&ctx.accounts.event)?;
+    // conditional events hold parent tokens, see sweep_conditional_unclaimed_to_house
+    require!(ctx.accounts.event.kind != EVENT_KIND_CONDITIONAL, PredictError::InvalidEventKind);
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+
+    // voided events count as resolved at the void, so holders keep the full window
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require!(!ev.unclaimed_swept, PredictError::AlreadySwept);
+
+    // only after X days
+    require!(
+        now >= ev.resolved_at.saturating_add(ev.unclaimed_sweep_delay_secs),
+        PredictError::SweepNotYetAvailable
+    );
+
+    let vault_ai = ctx.accounts.collateral_vault.to_account_info();
+    let keep = vault_keep_lamports()?;
+
+    let vault_lamports = vault_ai.lamports();
+    require!(vault_lamports > keep, PredictError::NothingToSweep);
+
+    // the LMSR maker's surplus belongs to the creator, not to the house
+    let lmsr_surplus = lmsr_creator_surplus(ev, ctx.accounts.lmsr_market.as_ref())?;
+
+    let amount = vault_lamports.saturating_sub(keep).saturating_sub(lmsr_surplus);
+    require!(amount > 0, PredictError::NothingToSweep);
+    
+    let vault_bump = ctx.bumps.collateral_vault;
+
+    transfer_from_collateral_vault(
+        &vault_ai,
+        &ctx.accounts.house_treasury.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ev.key(),
+        vault_bump,
+        amount,
+    )?;
+    record_treasury_inflow(&ctx.accounts.house_treasury.to_account_info(), &ev.key(), amount)?;
+
+    ev.total_collateral_lamports = ev.total_collateral_lamports.saturating_sub(amount);
+    ev.house_lamports_paid = ev.house_lamports_paid.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    ev.unclaimed_swept = true;
+    ev.swept_at = now;
+
+    Ok(())
+}
+
+pub(crate) fn delete_event(ctx: Context<DeleteEvent>) -> Result<()> {
+    let ev = &ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require_result_final(ev)?;
+    require_keys_eq!(ctx.accounts.creator.key(), ev.creator, PredictError::Unauthorized);
+
+    require!(ev.pending_creator_commission == 0, PredictError::CreatorCommissionNotClaimed);
+    require!(ev.pending_house_commission == 0, PredictError::HouseCommissionNotCleared);
+
+    // compute keep
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
+
+    let resolved_at = ev.resolved_at;
+    require!(resolved_at > 0, PredictError::InvalidResolvedAt);
+
+    let after_window = now >= resolved_at.saturating_add(ev.unclaimed_sweep_delay_secs);
+    let no_outstanding = no_outstanding_tokens(ev);
+    // LMSR subsidy left after paying the maker's liability is refunded to the creator
+    let lmsr_surplus = lmsr_creator_surplus(ev, ctx.accounts.lmsr_market.as_ref())?;
+    let vault_empty = vault_lamports
+        <= keep
+            .saturating_add(VAULT_DUST_TOLERANCE_LAMPORTS)
+            .saturating_add(lmsr_surplus);
+
+    if !after_window {
+        // BEFORE window: strict, only deletable if no one ever bought / all burned if no winner / winning side - all burned
+        require!(no_outstanding, PredictError::OutstandingTokens);
+        require!(vault_empty, PredictError::VaultNotEmpty);
+    } else {
+        // AFTER window:
+        // - if sweep happened, ok (tokens may exist but is expired)
+        // - if sweep not happened, only allow when there is nothing to sweep (vault empty) and no outstanding tokens
+        if !ev.unclaimed_swept {
+            require!(no_outstanding, PredictError::OutstandingTokens);
+            require!(vault_empty, PredictError::VaultNotEmpty);
+        }
+    }
+
+    // Always require vault empty (only rent/keep allowed)
+    require!(vault_empty, PredictError::VaultNotEmpty);
+    
+
+    // drain last lamports (close the vault)
+    if vault_lamports > 0 {
+        let event_key = ctx.accounts.event.key();
+        let vault_bump = ctx.bumps.collateral_vault;
+        let seeds: [&[u8]; 3] = [SEED_COLLATERAL_VAULT, event_key.as_ref(), &[vault_bump]];
+
+        invoke_signed(
+            &system_instruction::transfer(
+                ctx.accounts.collateral_vault.key,
+                ctx.accounts.creator.key,
+                vault_lamports,
+            ),
+            &[
+                ctx.accounts.collateral_vault.to_account_info(),
+                ctx.accounts.creator.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds],
+        )?;
+    }
+
+    // commissions are all paid out by now, only rent is left in the commission vault
+    let commission_lamports = ctx.accounts.commission_vault.lamports();
+    transfer_from_commission_vault(
+        &ctx.accounts.commission_vault.to_account_info(),
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.event.key(),
+        ctx.bumps.commission_vault,
+        commission_lamports,
+    )?;
+
+    // token-collateral events: burn rounding dust and close the token vaults
+    if ctx.accounts.event.collateral_mint != Pubkey::default() {
+        let token_vault = ctx.accounts.token_vault.as_ref().ok_or(PredictError::TokenVaultRequired)?;
+        let commission_token_vault = ctx
+            .accounts
+            .commission_token_vault
+            .as_ref()
+            .ok_or(PredictError::TokenVaultRequired)?;
+        let truth_token_vault = ctx.accounts.truth_token_vault.as_ref().ok_or(PredictError::TokenVaultRequired)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(PredictError::TokenVaultRequired)?;
+
+        // the Truth cut has to be claimed first
+        require!(truth_token_vault.amount == 0, PredictError::VaultNotEmpty);
+
+        let event_key = ctx.accounts.event.key();
+        let vault_bump = ctx.bumps.collateral_vault;
+        let seeds: [&[u8]; 3] = [SEED_COLLATERAL_VAULT, event_key.as_ref(), &[vault_bump]];
+        let mint = ctx.accounts.collateral_mint.as_ref().map(|m| m.to_account_info());
+
+        for vault in [token_vault, commission_token_vault, truth_token_vault] {
+            close_token_vault(
+                &token_program.to_account_info(),
+                vault,
+                mint.as_ref(),
+                &ctx.accounts.creator.to_account_info(),
+                &ctx.accounts.collateral_vault.to_account_info(),
+                &seeds,
+            )?;
+        }
+    }
+
+    // conditional events: parent tokens left over are swept first, then the vault
+    // and the market account close to the creator
+    if ctx.accounts.event.kind == EVENT_KIND_CONDITIONAL {
+        let market = ctx.accounts.conditional_market.as_ref().ok_or(PredictError::TokenVaultRequired)?;
+        let vault = ctx.accounts.conditional_vault.as_ref().ok_or(PredictError::TokenVaultRequired)?;
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(PredictError::TokenVaultRequired)?;
+        require!(vault.amount == 0, PredictError::VaultNotEmpty);
+
+        let event_key = ctx.accounts.event.key();
+        let seeds: [&[u8]; 3] = [SEED_CONDITIONAL_MARKET, event_key.as_ref(), &[market.bump]];
+        token_interface::close_account(
+            CpiContext::new(
+                token_program.to_account_info(),
+                token_interface::CloseAccount {
+                    account: vault.to_account_info(),
+                    destination: ctx.accounts.creator.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+        )?;
+        market.close(ctx.accounts.creator.to_account_info())?;
+    }
+
+    // Token-2022 position mints: recover the mint rent once supply is zero
+    if let (Some(true_mint), Some(false_mint)) = (ctx.accounts.true_mint.as_ref(), ctx.accounts.false_mint.as_ref()) {
+        let token_program = ctx.accounts.token_program.as_ref().ok_or(PredictError::TokenProgramRequired)?;
+        let mint_authority = ctx.accounts.mint_authority.as_ref().ok_or(PredictError::TokenProgramRequired)?;
+
+        let event_key = ctx.accounts.event.key();
+        let auth_bump = ctx.bumps.mint_authority.ok_or(PredictError::TokenProgramRequired)?;
+        let auth_seeds: [&[u8]; 3] = [SEED_MINT_AUTH, event_key.as_ref(), &[auth_bump]];
+
+        for mint in [true_mint, false_mint] {
+            close_position_mint(
+                &token_program.to_account_info(),
+                mint,
+                &ctx.accounts.creator.to_account_info(),
+                &mint_authority.to_account_info(),
+                &auth_seeds,
+            )?;
+        }
+    }
+
+    // close the LMSR market account, rent goes back to the creator
+    if let Some(market) = ctx.accounts.lmsr_market.as_ref() {
+        market.close(ctx.accounts.creator.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+// Brings a legacy event to the current layout: reallocs it, fills the new fields from the
+// protocol config as create_event_core would, and moves its pending creator + house
+// commissions from the collateral vault into a new commission vault.
+pub(crate) fn migrate_event(ctx: Context<MigrateEvent>) -> Result<()> {
+    let event_ai = ctx.accounts.event.to_account_info();
+    require!(event_ai.data_len() == LEGACY_EVENT_SPACE, PredictError::EventAlreadyMigrated);
+
+    let legacy = {
+        let data = event_ai.try_borrow_data()?;
+        require!(data[..8] == *Event::DISCRIMINATOR, PredictError::InvalidEventLayout);
+        LegacyEvent::deserialize(&mut &data[8..]).map_err(|_| PredictError::InvalidEventLayout)?
+    };
+    require_keys_eq!(
+        legacy.collateral_vault,
+        ctx.accounts.collateral_vault.key(),
+        PredictError::InvalidEventLayout
+    );
+
+    let config = &ctx.accounts.config;
+    let payer_ai = ctx.accounts.admin.to_account_info();
+    let system_ai = ctx.accounts.system_program.to_account_info();
+    let event_key = event_ai.key();
+
+    // rent for the larger account comes from the admin
+    let required = Rent::get()?.minimum_balance(EVENT_SPACE);
+    let top_up = required.saturating_sub(event_ai.lamports());
+    if top_up > 0 {
+        anchor_lang::solana_program::program::invoke(
+            &system_instruction::transfer(payer_ai.key, event_ai.key, top_up),
+            &[payer_ai.clone(), event_ai.clone(), system_ai.clone()],
+        )?;
+    }
+    event_ai.resize(EVENT_SPACE)?;
+
+    // legacy events kept commissions in the collateral vault
+    let commission_bump = ctx.bumps.commission_vault;
+    let commission_seeds: [&[u8]; 3] = [
+        SEED_COMMISSION_VAULT,
+        event_key.as_ref(),
+        &[commission_bump],
+    ];
+    create_system_pda_0space(
+        &payer_ai,
+        &ctx.accounts.commission_vault.to_account_info(),
+        &system_ai,
+        &commission_seeds,
+    )?;
+    let commissions = legacy
+        .pending_creator_commission
+        .checked_add(legacy.pending_house_commission)
+        .ok_or(PredictError::MathOverflow)?;
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.commission_vault.to_account_info(),
+        &system_ai,
+        &event_key,
+        ctx.bumps.collateral_vault,
+        commissions,
+    )?;
+
+    let fee_bps = config.buy_fee_bps;
+    let ev = Box::new(Event {
+        creator: legacy.creator,
+        event_id: legacy.event_id,
+        truth_question: legacy.truth_question,
+        title: legacy.title,
+        bet_end_time: legacy.bet_end_time,
+        commit_end_time: legacy.commit_end_time,
+        reveal_end_time: legacy.reveal_end_time,
+        created_at: legacy.created_at,
+        total_collateral_lamports: legacy.total_collateral_lamports,
+        total_issued_per_side: legacy.total_issued_per_side,
+        collateral_vault: legacy.collateral_vault,
+        true_mint: legacy.true_mint,
+        false_mint: legacy.false_mint,
+        resolved: legacy.resolved,
+        winning_option: legacy.winning_option,
+        winning_percent_bps: legacy.winning_percent_bps,
+        votes_option_1: legacy.votes_option_1,
+        votes_option_2: legacy.votes_option_2,
+        consensus_threshold_bps: legacy.consensus_threshold_bps,
+        resolved_at: legacy.resolved_at,
+        result_status: legacy.result_status,
+        total_truth_commission_sent: legacy.total_truth_commission_sent,
+        pending_creator_commission: legacy.pending_creator_commission,
+        pending_house_commission: legacy.pending_house_commission,
+        unclaimed_swept: legacy.unclaimed_swept,
+        swept_at: legacy.swept_at,
+        outstanding_true: legacy.outstanding_true,
+        outstanding_false: legacy.outstanding_false,
+        category: legacy.category,
+
+        has_lmsr: false,
+        raydium_pool: Pubkey::default(),
+        kind: EVENT_KIND_BINARY,
+        collateral_mint: Pubkey::default(),
+        collateral_decimals: 9,
+
+        house_wallet: config.house_wallet,
+        buy_fee_bps: fee_bps,
+        redeem_fee_bps: config.redeem_fee_bps,
+        unclaimed_sweep_delay_secs: config.unclaimed_sweep_delay_secs,
+
+        truth_share_bps: DEFAULT_TRUTH_SHARE_BPS,
+        creator_share_bps: DEFAULT_CREATOR_SHARE_BPS,
+        house_share_bps: (BPS_DENOM as u16)
+            .saturating_sub(DEFAULT_TRUTH_SHARE_BPS)
+            .saturating_sub(DEFAULT_CREATOR_SHARE_BPS),
+
+        fee_curve: FEE_CURVE_FLAT,
+        fee_curve_start: 0,
+        fee_curve_max_bps: fee_bps,
+        fee_curve_steps: 0,
+        early_bird_end_time: 0,
+        early_bird_discount_bps: 0,
+
+        referral_share_bps: config.referral_share_bps,
+        total_referral_commission: 0,
+
+        creator_clawback: config.creator_clawback,
+        forfeited_commission: 0,
+        forfeit_base_units: 0,
+
+        creator_payout: legacy.creator,
+        co_creator_count: 0,
+        co_creators: [CreatorShare::default(); MAX_CO_CREATORS],
+
+        creator_vesting: VESTING_NONE,
+        vesting_tranches: 0,
+        vesting_holdback_bps: 0,
+        claimed_creator_commission: 0,
+        house_lamports_paid: 0,
+        pause: PauseState::default(),
+
+        emergency_exit_delay_secs: config.emergency_exit_delay_secs,
+        emergency_exit_active: false,
+
+        // results reached under the legacy layout are final: no dispute window, no revotes
+        dispute_window_secs: config.dispute_window_secs,
+        dispute_bond_lamports: config.dispute_bond_lamports,
+        dispute_deadline: 0,
+        dispute_status: DISPUTE_NONE,
+        revote_max_rounds: if legacy.resolved { 0 } else { config.revote_max_rounds },
+        revote_funding_bps: config.revote_funding_bps,
+        revote_round: 0,
+        revote_history: [RevoteRound::default(); MAX_REVOTE_ROUNDS],
+
+        has_buys: legacy.total_issued_per_side > 0,
+        layout_version: EVENT_LAYOUT_VERSION,
+    });
+
+    let mut data = event_ai.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    ev.try_serialize(&mut writer)
+}
+
+#[derive(Accounts)]
+pub struct InitializeEventCounter<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(init, payer = creator, space = 8 + 32 + 8, seeds = [SEED_EVENT_COUNTER, creator.key().as_ref()], bump)]
+    pub counter: Account<'info, EventCounter>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateEventCore<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(mut)]
+    pub counter: Account<'info, EventCounter>,
+    #[account(init, payer = creator, space = EVENT_SPACE, seeds = [SEED_EVENT, creator.key().as_ref(), &counter.count.to_le_bytes()], bump)]
+    pub event: Account<'info, Event>,
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CreateEventMints<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    /// CHECK: PDA signer
+    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA true mint account
+    #[account(mut, seeds = [SEED_TRUE_MINT, event.key().as_ref()], bump)]
+    pub true_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA false mint
+    #[account(mut, seeds = [SEED_FALSE_MINT, event.key().as_ref()], bump)]
+    pub false_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA system account
+    #[account(mut, seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA system account for commissions
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()], bump)]
+    pub commission_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Toke Metadata program
+    #[account(address = METADATA_PROGRAM_ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex metadata PDA for TRUE mint
+    #[account(mut, seeds = [b"metadata", METADATA_PROGRAM_ID.as_ref(), true_mint.key().as_ref()], bump, seeds::program = METADATA_PROGRAM_ID)]
+    pub true_metadata: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex metadata PDA for FALSE mint
+    #[account(mut, seeds = [b"metadata", METADATA_PROGRAM_ID.as_ref(), false_mint.key().as_ref()], bump, seeds::program = METADATA_PROGRAM_ID )]
+    pub false_metadata: UncheckedAccount<'info>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyPositionsWithFee<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub commission_vault: SystemAccount<'info>,
+
+    /// CHECK: PDA mint authority signer
+    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint)]
+    pub true_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint)]
+    pub false_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(mut)]
+    pub user_true_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub user_false_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // ---- Truth network read + vault ----
+    #[account(mut)]
+    pub truth_network_question: Account<'info, Question>,
+
+    /// CHECK: vault is system-owned PDA in Truth-Network (no data), but must be mutable
+    #[account(mut)]
+    pub truth_network_vault: UncheckedAccount<'info>,
+
+    // ---- trading stats ----
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    // ---- optional referrer ----
+    #[account(mut, seeds = [SEED_REFERRAL, referral.referrer.as_ref()], bump = referral.bump)]
+    pub referral: Option<Account<'info, ReferralAccount>>,
+
+    // checked against the referral's vault bump in the handler
+    #[account(mut)]
+    pub referral_vault: Option<SystemAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct FetchAndStoreWinner<'info> {
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub truth_network_question: Account<'info, Question>,
+
+    pub truth_network_program: Program<'info, TruthNetwork>,
+
+    /// CHECK: Fixed house wallet
+    #[account(mut, address = event.house_wallet)]
+    pub house_treasury: AccountInfo<'info>,
+
+    /// CHECK: Commission vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub commission_vault: AccountInfo<'info>,
+
+    /// CHECK: Collateral vault PDA, receives a forfeited creator commission
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemPairWhileActive<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump,
+        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint
+    )]
+    pub true_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint
+    )]
+    pub false_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_true_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_true_ata.mint == true_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_true_ata: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_false_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_false_ata.mint == false_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_false_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // updated with the payout and realised PnL
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemWinnerAfterFinal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump,
+        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    // Winning mint (TRUE or FALSE)
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // User ATA for winning mint
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // updated with the payout and realised PnL
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemNoWinnerAfterFinal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump,
+        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    // TRUE or FALSE mint
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // User ATA for that mint
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // updated with the payout and realised PnL
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorCommission<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    /// CHECK: creator commission payout address
+    #[account(mut, address = event.creator_payout)]
+    pub creator_payout: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub commission_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedToHouse<'info> {
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: Fixed house wallet
+    #[account(mut, address = event.house_wallet)]
+    pub house_treasury: AccountInfo<'info>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: AccountInfo<'info>,
+
+    // Required when the event has an LMSR maker
+    #[account(seeds = [SEED_LMSR_MARKET, event.key().as_ref()], bump = lmsr_market.bump)]
+    pub lmsr_market: Option<Account<'info, LmsrMarket>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DeleteEvent<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: AccountInfo<'info>,
+
+    /// CHECK: Commission vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub commission_vault: AccountInfo<'info>,
+
+    // Required when the event has an LMSR maker, closed to the creator
+    #[account(mut, seeds = [SEED_LMSR_MARKET, event.key().as_ref()], bump = lmsr_market.bump)]
+    pub lmsr_market: Option<Account<'info, LmsrMarket>>,
+
+    // Required for token-collateral events, closed to the creator
+    #[account(mut, seeds = [SEED_TOKEN_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut, seeds = [SEED_TRUTH_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub truth_token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub commission_token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut, address = event.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    // Required for conditional events, both closed to the creator
+    #[account(mut, seeds = [SEED_CONDITIONAL_MARKET, event.key().as_ref()], bump = conditional_market.bump)]
+    pub conditional_market: Option<Box<Account<'info, ConditionalMarket>>>,
+    #[account(mut, seeds = [SEED_CONDITIONAL_VAULT, event.key().as_ref()], bump)]
+    pub conditional_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Token-2022 position mints, closed to the creator when their supply is zero
+    #[account(mut, address = event.true_mint @ PredictError::InvalidMint)]
+    pub true_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(mut, address = event.false_mint @ PredictError::InvalidMint)]
+    pub false_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// CHECK: PDA mint authority, close authority of Token-2022 position mints
+    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
+    pub mint_authority: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Option<Interface<'info, TokenInterface>>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateEvent<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump = config.bump,
+        has_one = admin @ PredictError::Unauthorized
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: legacy Event account, length and discriminator are checked in migrate_event
+    #[account(mut, owner = crate::ID)]
+    pub event: UncheckedAccount<'info>,
+
+    /// CHECK: PDA system account
+    #[account(mut, seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA system account for commissions, created here
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()], bump)]
+    pub commission_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,276 @@
+use crate::*;
+
+pub(crate) fn init_lmsr_market(ctx: Context<InitLmsrMarket>, b: u64) -> Result<()> {
+    require_native_collateral(&ctx.accounts.event)?;
+    require!((LMSR_MIN_B..=LMSR_MAX_B).contains(&b), PredictError::InvalidLiquidityParam);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!ctx.accounts.event.resolved, PredictError::EventAlreadyResolved);
+    require_keys_eq!(ctx.accounts.creator.key(), ctx.accounts.event.creator, PredictError::Unauthorized);
+    require!(ctx.accounts.event.kind == EVENT_KIND_BINARY, PredictError::InvalidEventKind);
+
+    // Creator funds the worst-case loss up front, into the same collateral vault
+    let subsidy = lmsr_subsidy(b)?;
+    transfer_in(&ctx.accounts.creator, &ctx.accounts.collateral_vault, &ctx.accounts.system_program, subsidy)?;
+
+    let market = &mut ctx.accounts.lmsr_market;
+    market.event = ctx.accounts.event.key();
+    market.b = b;
+    market.q_true = 0;
+    market.q_false = 0;
+    market.subsidy_lamports = subsidy;
+    market.reserve_lamports = subsidy;
+    market.bump = ctx.bumps.lmsr_market;
+
+    let ev = &mut ctx.accounts.event;
+    ev.has_lmsr = true;
+    ev.total_collateral_lamports = ev
+        .total_collateral_lamports
+        .checked_add(subsidy)
+        .ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+pub(crate) fn buy_outcome(ctx: Context<TradeLmsrOutcome>, side: u8, amount: u64, max_cost: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_BUY)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!ctx.accounts.event.resolved, PredictError::EventResolved);
+
+    let expected_mint = mint_for_side(&ctx.accounts.event, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+
+    let (cost, q_true, q_false) = lmsr_buy_cost(&ctx.accounts.lmsr_market, side, amount)?;
+    require!(cost <= max_cost, PredictError::SlippageExceeded);
+
+    // user -> collateral vault
+    transfer_in(&ctx.accounts.user, &ctx.accounts.collateral_vault, &ctx.accounts.system_program, cost)?;
+
+    // mint the bought side only
+    mint_position_tokens(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.user_ata.to_account_info(),
+        &ctx.accounts.mint_authority.to_account_info(),
+        &ctx.accounts.event.key(),
+        ctx.bumps.mint_authority,
+        amount,
+    )?;
+
+    let market = &mut ctx.accounts.lmsr_market;
+    market.q_true = q_true;
+    market.q_false = q_false;
+    market.reserve_lamports = market.reserve_lamports.checked_add(cost).ok_or(PredictError::MathOverflow)?;
+
+    // Maker must always be able to pay out whichever side wins
+    require!(
+        market.reserve_lamports >= market.q_true.max(market.q_false),
+        PredictError::LmsrInsolvent
+    );
+
+    let ev = &mut ctx.accounts.event;
+    let outstanding = outstanding_for_side_mut(ev, side)?;
+    *outstanding = outstanding.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    ev.total_collateral_lamports = ev
+        .total_collateral_lamports
+        .checked_add(cost)
+        .ok_or(PredictError::MathOverflow)?;
+    ev.has_buys = true;
+
+    // the maker charges no protocol fee, so no tier discount applies; the cost still counts as volume
+    let event_key = ctx.accounts.event.key();
+    record_buy(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.user_position,
+        &ctx.accounts.user.key(),
+        &event_key,
+        [ctx.bumps.user_stats, ctx.bumps.user_position],
+        cost,
+        cost,
+        side_units(side, amount)?,
+        now,
+    )?;
+
+    Ok(())
+}
+
+pub(crate) fn sell_outcome(ctx: Context<TradeLmsrOutcome>, side: u8, amount: u64, min_proceeds: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_PAIR_REDEEM)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!ctx.accounts.event.resolved, PredictError::EventResolved);
+
+    let expected_mint = mint_for_side(&ctx.accounts.event, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    // The maker only buys back what it has sold
+    let (proceeds, q_true, q_false) = lmsr_sell_proceeds(&ctx.accounts.lmsr_market, side, amount)?;
+    require!(proceeds >= min_proceeds, PredictError::SlippageExceeded);
+    require!(proceeds <= ctx.accounts.lmsr_market.reserve_lamports, PredictError::LmsrInsolvent);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // rent safety
+    let keep = vault_keep_lamports()?;
+    let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
+    require!(
+        vault_lamports >= keep.saturating_add(proceeds),
+        PredictError::VaultInsufficientFunds
+    );
+
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.event.key(),
+        ctx.bumps.collateral_vault,
+        proceeds,
+    )?;
+
+    let market = &mut ctx.accounts.lmsr_market;
+    market.q_true = q_true;
+    market.q_false = q_false;
+    market.reserve_lamports = market.reserve_lamports.checked_sub(proceeds).ok_or(PredictError::MathOverflow)?;
+
+    require!(
+        market.reserve_lamports >= market.q_true.max(market.q_false),
+        PredictError::LmsrInsolvent
+    );
+
+    let ev = &mut ctx.accounts.event;
+    let outstanding = outstanding_for_side_mut(ev, side)?;
+    *outstanding = outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+    ev.total_collateral_lamports = ev
+        .total_collateral_lamports
+        .checked_sub(proceeds)
+        .ok_or(PredictError::MathOverflow)?;
+
+    let [true_units, false_units] = side_units(side, amount)?;
+    let event_key = ctx.accounts.event.key();
+    record_redemption(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.user_position,
+        &ctx.accounts.user.key(),
+        &event_key,
+        [ctx.bumps.user_stats, ctx.bumps.user_position],
+        proceeds,
+        true_units,
+        false_units,
+        0,
+        now,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitLmsrMarket<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 8 * 5 + 1,
+        seeds = [SEED_LMSR_MARKET, event.key().as_ref()],
+        bump
+    )]
+    pub lmsr_market: Account<'info, LmsrMarket>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump,
+        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TradeLmsrOutcome<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_LMSR_MARKET, event.key().as_ref()],
+        bump = lmsr_market.bump
+    )]
+    pub lmsr_market: Account<'info, LmsrMarket>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump,
+        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    // ---- trading stats ----
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    /// CHECK: PDA mint authority signer
+    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    // TRUE or FALSE mint, checked against `side`
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
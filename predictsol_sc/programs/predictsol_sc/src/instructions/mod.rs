@@ -0,0 +1,39 @@
+pub mod event;
+pub mod single_side;
+pub mod lmsr;
+pub mod dispute;
+pub mod revote;
+pub mod void;
+pub mod emergency_exit;
+pub mod pool;
+pub mod order_book;
+pub mod categorical;
+pub mod scalar;
+pub mod conditional;
+pub mod token_collateral;
+pub mod token_2022;
+pub mod config;
+pub mod treasury;
+pub mod pause;
+pub mod creator;
+pub mod referral;
+
+pub use event::*;
+pub use single_side::*;
+pub use lmsr::*;
+pub use dispute::*;
+pub use revote::*;
+pub use void::*;
+pub use emergency_exit::*;
+pub use pool::*;
+pub use order_book::*;
+pub use categorical::*;
+pub use scalar::*;
+pub use conditional::*;
+pub use token_collateral::*;
+pub use token_2022::*;
+pub use config::*;
+pub use treasury::*;
+pub use pause::*;
+pub use creator::*;
+pub use referral::*;
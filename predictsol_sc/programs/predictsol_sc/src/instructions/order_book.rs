@@ -0,0 +1,560 @@
+use crate::*;
+
+pub(crate) fn init_order_book(ctx: Context<InitOrderBook>) -> Result<()> {
+    // lamport escrow for bids (system-owned, 0 space)
+    let event_key = ctx.accounts.event.key();
+    let escrow_bump = ctx.bumps.order_escrow;
+    let escrow_seeds: [&[u8]; 3] = [
+        SEED_ORDER_ESCROW,
+        event_key.as_ref(),
+        &[escrow_bump],
+    ];
+    create_system_pda_0space(
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.order_escrow.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &escrow_seeds,
+    )?;
+
+    let book = &mut ctx.accounts.order_book;
+    book.event = event_key;
+    book.next_order_id = 0;
+    book.true_escrow = ctx.accounts.true_escrow.key();
+    book.false_escrow = ctx.accounts.false_escrow.key();
+    book.bump = ctx.bumps.order_book;
+    book.escrow_bump = escrow_bump;
+    book.orders = Vec::new();
+    book.fills = Vec::new();
+
+    Ok(())
+}
+
+pub(crate) fn init_open_orders(ctx: Context<InitOpenOrders>) -> Result<()> {
+    let oo = &mut ctx.accounts.open_orders;
+    oo.event = ctx.accounts.event.key();
+    oo.owner = ctx.accounts.user.key();
+    oo.free_lamports = 0;
+    oo.free_true = 0;
+    oo.free_false = 0;
+    oo.bump = ctx.bumps.open_orders;
+    Ok(())
+}
+
+pub(crate) fn place_order(
+    ctx: Context<PlaceOrder>,
+    token_side: u8,
+    is_bid: bool,
+    price: u64,
+    quantity: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_BUY)?;
+    require_trading_open(&ctx.accounts.event)?;
+    require!((1..=LAMPORTS_PER_TOKEN).contains(&price), PredictError::InvalidPrice);
+    require!(
+        mul_div_floor(quantity, price, LAMPORTS_PER_TOKEN)? >= ORDER_MIN_NOTIONAL_LAMPORTS,
+        PredictError::OrderTooSmall
+    );
+
+    let expected_escrow = match token_side {
+        1 => ctx.accounts.order_book.true_escrow,
+        2 => ctx.accounts.order_book.false_escrow,
+        _ => return err!(PredictError::InvalidSide),
+    };
+    require_keys_eq!(ctx.accounts.token_escrow.key(), expected_escrow, PredictError::InvalidVault);
+
+    // bids lock lamports, asks lock tokens
+    let locked_lamports = if is_bid {
+        let lamports = mul_div_ceil(quantity, price, LAMPORTS_PER_TOKEN)?;
+        transfer_in(&ctx.accounts.user, &ctx.accounts.order_escrow, &ctx.accounts.system_program, lamports)?;
+        lamports
+    } else {
+        require!(ctx.accounts.user_token_ata.amount >= quantity, PredictError::InsufficientTrueBalance);
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_ata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_escrow.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            quantity,
+            ctx.accounts.mint.decimals,
+        )?;
+        0
+    };
+
+    let book = &mut ctx.accounts.order_book;
+
+    // full queue: only a better price gets in, the worst order is refunded to its owner
+    let queued = book
+        .orders
+        .iter()
+        .filter(|o| o.token_side == token_side && o.is_bid == is_bid)
+        .count();
+    if queued >= ORDER_BOOK_MAX_PER_QUEUE {
+        let wi = worst_order_index(&book.orders, token_side, is_bid).ok_or(PredictError::OrderBookFull)?;
+        let worst_price = book.orders[wi].price;
+        let better = if is_bid { price > worst_price } else { price < worst_price };
+        require!(better, PredictError::OrderBookFull);
+        require!(book.fills.len() < ORDER_BOOK_MAX_FILLS, PredictError::OrderBookFull);
+
+        let evicted = book.orders.swap_remove(wi);
+        book.fills.push(Fill {
+            bidder: evicted.owner,
+            asker: evicted.owner,
+            token_side,
+            quantity: if evicted.is_bid { 0 } else { evicted.remaining },
+            lamports: 0,
+            bid_refund: if evicted.is_bid { evicted.locked_lamports } else { 0 },
+        });
+    }
+
+    let id = book.next_order_id;
+    book.next_order_id = id.checked_add(1).ok_or(PredictError::MathOverflow)?;
+    book.orders.push(Order {
+        id,
+        owner: ctx.accounts.user.key(),
+        token_side,
+        is_bid,
+        price,
+        remaining: quantity,
+        locked_lamports,
+    });
+
+    Ok(())
+}
+
+// Always allowed, so orders never get stuck after trading halts.
+// Escrow goes back to the owner's free balance, withdrawn via settle_funds.
+pub(crate) fn cancel_order(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
+    let book = &mut ctx.accounts.order_book;
+    let idx = book
+        .orders
+        .iter()
+        .position(|o| o.id == order_id)
+        .ok_or(PredictError::OrderNotFound)?;
+    require_keys_eq!(book.orders[idx].owner, ctx.accounts.user.key(), PredictError::Unauthorized);
+
+    let order = book.orders.swap_remove(idx);
+    let oo = &mut ctx.accounts.open_orders;
+
+    if order.is_bid {
+        oo.free_lamports = oo.free_lamports.checked_add(order.locked_lamports).ok_or(PredictError::MathOverflow)?;
+    } else {
+        credit_open_orders_tokens(oo, order.token_side, order.remaining)?;
+    }
+
+    Ok(())
+}
+
+// Permissionless crank: crosses the best bid and ask for one token side
+// and pushes the fills to the event queue. Fills don't touch UserStats /
+// UserPosition: neither trader signs the crank to pay for those PDAs, and
+// the book charges no protocol fee for a tier discount to apply to.
+pub(crate) fn match_orders(ctx: Context<MatchOrders>, token_side: u8, limit: u8) -> Result<()> {
+    require!(token_side == 1 || token_side == 2, PredictError::InvalidSide);
+    require_trading_open(&ctx.accounts.event)?;
+
+    let book = &mut ctx.accounts.order_book;
+    let mut matched: u8 = 0;
+
+    while matched < limit && book.fills.len() < ORDER_BOOK_MAX_FILLS {
+        let (bi, ai) = match (
+            best_order_index(&book.orders, token_side, true),
+            best_order_index(&book.orders, token_side, false),
+        ) {
+            (Some(b), Some(a)) => (b, a),
+            _ => break,
+        };
+
+        let (bid_price, bid_id) = (book.orders[bi].price, book.orders[bi].id);
+        let (ask_price, ask_id) = (book.orders[ai].price, book.orders[ai].id);
+        if bid_price < ask_price {
+            break;
+        }
+
+        // resting (older) order sets the price
+        let exec_price = if bid_id < ask_id { bid_price } else { ask_price };
+        let quantity = book.orders[bi].remaining.min(book.orders[ai].remaining);
+        let lamports = mul_div_floor(quantity, exec_price, LAMPORTS_PER_TOKEN)?;
+
+        let bid = &mut book.orders[bi];
+        bid.remaining = bid.remaining.checked_sub(quantity).ok_or(PredictError::MathOverflow)?;
+        bid.locked_lamports = bid.locked_lamports.checked_sub(lamports).ok_or(PredictError::MathOverflow)?;
+        let bid_done = bid.remaining == 0;
+        // price improvement left in escrow goes back to the bidder
+        let bid_refund = if bid_done { bid.locked_lamports } else { 0 };
+        let bidder = bid.owner;
+
+        let ask = &mut book.orders[ai];
+        ask.remaining = ask.remaining.checked_sub(quantity).ok_or(PredictError::MathOverflow)?;
+        let ask_done = ask.remaining == 0;
+        let asker = ask.owner;
+
+        book.fills.push(Fill {
+            bidder,
+            asker,
+            token_side,
+            quantity,
+            lamports,
+            bid_refund,
+        });
+
+        // remove the higher index first so the lower one stays valid
+        let (hi, lo) = if bi > ai { (bi, ai) } else { (ai, bi) };
+        let (hi_done, lo_done) = if bi > ai { (bid_done, ask_done) } else { (ask_done, bid_done) };
+        if hi_done {
+            book.orders.swap_remove(hi);
+        }
+        if lo_done {
+            book.orders.swap_remove(lo);
+        }
+
+        matched += 1;
+    }
+
+    require!(matched > 0, PredictError::NothingToMatch);
+    Ok(())
+}
+
+// Permissionless crank: applies queued fills to the owners' OpenOrders
+// accounts, passed as remaining accounts. Stops at the first fill whose
+// accounts are missing.
+pub(crate) fn consume_events<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ConsumeEvents<'info>>,
+    limit: u8,
+) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+
+    let mut open_orders: Vec<Account<'info, OpenOrders>> = Vec::with_capacity(ctx.remaining_accounts.len());
+    for ai in ctx.remaining_accounts.iter() {
+        require!(
+            !open_orders.iter().any(|o| o.key() == ai.key()),
+            PredictError::InvalidOpenOrders
+        );
+        let oo: Account<'info, OpenOrders> = Account::try_from(ai)?;
+        require_keys_eq!(oo.event, event_key, PredictError::InvalidOpenOrders);
+        open_orders.push(oo);
+    }
+
+    let book = &mut ctx.accounts.order_book;
+    let mut consumed: u8 = 0;
+
+    while consumed < limit && !book.fills.is_empty() {
+        let fill = book.fills[0].clone();
+
+        let bi = open_orders.iter().position(|o| o.owner == fill.bidder);
+        let ai = open_orders.iter().position(|o| o.owner == fill.asker);
+        let (bi, ai) = match (bi, ai) {
+            (Some(b), Some(a)) => (b, a),
+            _ => break,
+        };
+
+        let bidder = &mut open_orders[bi];
+        credit_open_orders_tokens(bidder, fill.token_side, fill.quantity)?;
+        bidder.free_lamports = bidder.free_lamports.checked_add(fill.bid_refund).ok_or(PredictError::MathOverflow)?;
+
+        let asker = &mut open_orders[ai];
+        asker.free_lamports = asker.free_lamports.checked_add(fill.lamports).ok_or(PredictError::MathOverflow)?;
+
+        book.fills.remove(0);
+        consumed += 1;
+    }
+
+    require!(consumed > 0, PredictError::NothingToConsume);
+
+    for oo in open_orders.iter() {
+        oo.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
+
+// Withdraws everything credited to the caller's OpenOrders account.
+pub(crate) fn settle_funds(ctx: Context<SettleFunds>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_PAIR_REDEEM)?;
+    let oo = &ctx.accounts.open_orders;
+    let (lamports, free_true, free_false) = (oo.free_lamports, oo.free_true, oo.free_false);
+    require!(lamports > 0 || free_true > 0 || free_false > 0, PredictError::NothingToClaim);
+
+    let event_key = ctx.accounts.event.key();
+    let book = &ctx.accounts.order_book;
+
+    if lamports > 0 {
+        // rent safety
+        let keep = vault_keep_lamports()?;
+        let escrow_lamports = ctx.accounts.order_escrow.to_account_info().lamports();
+        require!(
+            escrow_lamports >= keep.saturating_add(lamports),
+            PredictError::VaultInsufficientFunds
+        );
+
+        let seeds: [&[u8]; 3] = [SEED_ORDER_ESCROW, event_key.as_ref(), &[book.escrow_bump]];
+        invoke_signed(
+            &system_instruction::transfer(&ctx.accounts.order_escrow.key(), &ctx.accounts.user.key(), lamports),
+            &[
+                ctx.accounts.order_escrow.to_account_info(),
+                ctx.accounts.user.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&seeds],
+        )?;
+    }
+
+    transfer_from_order_token_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.true_escrow.to_account_info(),
+
+        &ctx.accounts.true_mint.to_account_info(),
+        &ctx.accounts.user_true_ata.to_account_info(),
+        &ctx.accounts.order_book.to_account_info(),
+        &event_key,
+        book.bump,
+
+        ctx.accounts.true_mint.decimals,
+        free_true,
+    )?;
+
+    transfer_from_order_token_escrow(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.false_escrow.to_account_info(),
+
+        &ctx.accounts.false_mint.to_account_info(),
+        &ctx.accounts.user_false_ata.to_account_info(),
+        &ctx.accounts.order_book.to_account_info(),
+        &event_key,
+        book.bump,
+
+        ctx.accounts.false_mint.decimals,
+        free_false,
+    )?;
+
+    let oo = &mut ctx.accounts.open_orders;
+    oo.free_lamports = 0;
+    oo.free_true = 0;
+    oo.free_false = 0;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitOrderBook<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + 32 + 8 + 32 + 32 + 1 + 1
+            + 4 + ORDER_BOOK_MAX_ORDERS * (8 + 32 + 1 + 1 + 8 + 8 + 8)
+            + 4 + ORDER_BOOK_MAX_FILLS * (32 + 32 + 1 + 8 + 8 + 8),
+        seeds = [SEED_ORDER_BOOK, event.key().as_ref()],
+        bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    /// CHECK: PDA system account holding bid escrow
+    #[account(mut, seeds = [SEED_ORDER_ESCROW, event.key().as_ref()], bump)]
+    pub order_escrow: UncheckedAccount<'info>,
+
+    #[account(address = event.true_mint @ PredictError::InvalidMint)]
+    pub true_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = event.false_mint @ PredictError::InvalidMint)]
+    pub false_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_ORDER_TOKEN_ESCROW, event.key().as_ref(), &[1u8]],
+        bump,
+        token::mint = true_mint,
+        token::authority = order_book,
+        token::token_program = token_program
+    )]
+    pub true_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        seeds = [SEED_ORDER_TOKEN_ESCROW, event.key().as_ref(), &[2u8]],
+        bump,
+        token::mint = false_mint,
+        token::authority = order_book,
+        token::token_program = token_program
+    )]
+    pub false_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitOpenOrders<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 3 + 1,
+        seeds = [SEED_OPEN_ORDERS, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_ORDER_BOOK, event.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    // fills can only be credited to owners that have one
+    #[account(
+        seeds = [SEED_OPEN_ORDERS, event.key().as_ref(), user.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    #[account(mut, seeds = [SEED_ORDER_ESCROW, event.key().as_ref()], bump = order_book.escrow_bump)]
+    pub order_escrow: SystemAccount<'info>,
+
+    // TRUE or FALSE escrow, checked against `token_side`
+    #[account(mut)]
+    pub token_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_escrow.mint @ PredictError::InvalidMint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_token_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_token_ata.mint == token_escrow.mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    pub user: Signer<'info>,
+
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_ORDER_BOOK, event.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_OPEN_ORDERS, event.key().as_ref(), user.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+}
+
+#[derive(Accounts)]
+pub struct MatchOrders<'info> {
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_ORDER_BOOK, event.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+}
+
+#[derive(Accounts)]
+pub struct ConsumeEvents<'info> {
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_ORDER_BOOK, event.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+}
+
+#[derive(Accounts)]
+pub struct SettleFunds<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        seeds = [SEED_ORDER_BOOK, event.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_OPEN_ORDERS, event.key().as_ref(), user.key().as_ref()],
+        bump = open_orders.bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+
+    #[account(mut, seeds = [SEED_ORDER_ESCROW, event.key().as_ref()], bump = order_book.escrow_bump)]
+    pub order_escrow: SystemAccount<'info>,
+
+    #[account(mut, address = order_book.true_escrow @ PredictError::InvalidVault)]
+    pub true_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = order_book.false_escrow @ PredictError::InvalidVault)]
+    pub false_escrow: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = event.true_mint @ PredictError::InvalidMint)]
+    pub true_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = event.false_mint @ PredictError::InvalidMint)]
+    pub false_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_true_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_true_ata.mint == true_escrow.mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_true_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_false_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_false_ata.mint == false_escrow.mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_false_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,51 @@
+use crate::*;
+
+// Emergency pause of the PAUSE_* classes across all events; flags = 0 lifts it.
+pub(crate) fn set_protocol_pause(
+    ctx: Context<UpdateConfig>,
+    flags: u8,
+    reason: String,
+    duration_secs: i64,
+) -> Result<()> {
+    let admin = ctx.accounts.admin.key();
+    let config = &mut ctx.accounts.config;
+    config.pause = new_pause_state(&config.pause, flags, admin, reason, duration_secs)?;
+    Ok(())
+}
+
+// Same for a single event; the admin or the event creator can halt it.
+pub(crate) fn set_event_pause(
+    ctx: Context<SetEventPause>,
+    flags: u8,
+    reason: String,
+    duration_secs: i64,
+) -> Result<()> {
+    let authority = ctx.accounts.authority.key();
+    let ev = &mut ctx.accounts.event;
+    ev.pause = event_pause_update(&ctx.accounts.config, &ev.pause, ev.creator, authority, flags, reason, duration_secs)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump = config.bump,
+        has_one = admin @ PredictError::Unauthorized
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+}
+
+#[derive(Accounts)]
+pub struct SetEventPause<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+}
@@ -0,0 +1,445 @@
+use crate::*;
+
+pub(crate) fn init_pool(ctx: Context<InitPool>, side: u8, fee_bps: u16) -> Result<()> {
+    require!(fee_bps <= POOL_MAX_FEE_BPS, PredictError::InvalidFeeBps);
+    require_trading_open(&ctx.accounts.event)?;
+
+    let expected_mint = mint_for_side(&ctx.accounts.event, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+
+    // SOL side of the pool (system-owned, 0 space)
+    let event_key = ctx.accounts.event.key();
+    let sol_vault_bump = ctx.bumps.sol_vault;
+    let sol_vault_seeds: [&[u8]; 4] = [
+        SEED_POOL_SOL_VAULT,
+        event_key.as_ref(),
+        &[side],
+        &[sol_vault_bump],
+    ];
+    create_system_pda_0space(
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &sol_vault_seeds,
+    )?;
+
+    // LP mint PDA, the pool signs LP mints
+    let lp_mint_bump = ctx.bumps.lp_mint;
+    let lp_mint_seeds: [&[u8]; 4] = [
+        SEED_POOL_LP_MINT,
+        event_key.as_ref(),
+        &[side],
+        &[lp_mint_bump],
+    ];
+    create_and_init_spl_mint_pda(
+        &ctx.accounts.creator.to_account_info(),
+        &ctx.accounts.lp_mint.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.rent.to_account_info(),
+        &ctx.accounts.pool.key(),
+        &lp_mint_seeds,
+        9,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.event = event_key;
+    pool.side = side;
+    pool.fee_bps = fee_bps;
+    pool.token_reserve = 0;
+    pool.sol_reserve = 0;
+    pool.lp_supply = 0;
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.token_vault = ctx.accounts.token_vault.key();
+    pool.sol_vault = ctx.accounts.sol_vault.key();
+    pool.bump = ctx.bumps.pool;
+    pool.sol_vault_bump = sol_vault_bump;
+
+    Ok(())
+}
+
+pub(crate) fn add_liquidity(
+    ctx: Context<PoolLiquidity>,
+    token_amount: u64,
+    max_lamports: u64,
+    min_lp_out: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_BUY)?;
+    require!(token_amount > 0, PredictError::InvalidAmount);
+    require_trading_open(&ctx.accounts.event)?;
+
+    let pool = &ctx.accounts.pool;
+
+    // First deposit sets the price, later ones must match the current ratio
+    let (lamports, lp_out) = if pool.lp_supply == 0 {
+        let lp = isqrt_u128((token_amount as u128) * (max_lamports as u128));
+        let lp = u64::try_from(lp).map_err(|_| error!(PredictError::MathOverflow))?;
+        require!(lp >= POOL_MIN_INITIAL_LP, PredictError::InsufficientInitialLiquidity);
+        (max_lamports, lp)
+    } else {
+        let lamports = mul_div_ceil(token_amount, pool.sol_reserve, pool.token_reserve)?;
+        let lp = mul_div_floor(token_amount, pool.lp_supply, pool.token_reserve)?;
+        (lamports, lp)
+    };
+
+    require!(lamports > 0 && lamports <= max_lamports, PredictError::SlippageExceeded);
+    require!(lp_out > 0 && lp_out >= min_lp_out, PredictError::SlippageExceeded);
+
+    // user tokens -> pool
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_ata.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        token_amount,
+        ctx.accounts.mint.decimals,
+    )?;
+
+    // user SOL -> pool
+    transfer_in(&ctx.accounts.user, &ctx.accounts.sol_vault, &ctx.accounts.system_program, lamports)?;
+
+    // mint LP to user, pool PDA is the LP mint authority
+    let event_key = ctx.accounts.event.key();
+    let side = pool.side;
+    let pool_seeds: [&[u8]; 4] = [SEED_POOL, event_key.as_ref(), &[side], &[pool.bump]];
+
+    token_interface::mint_to(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_ata.to_account_info(),
+                authority: ctx.accounts.pool.to_account_info(),
+            },
+        )
+        .with_signer(&[&pool_seeds]),
+        lp_out,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.token_reserve = pool.token_reserve.checked_add(token_amount).ok_or(PredictError::MathOverflow)?;
+    pool.sol_reserve = pool.sol_reserve.checked_add(lamports).ok_or(PredictError::MathOverflow)?;
+    pool.lp_supply = pool.lp_supply.checked_add(lp_out).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// Always allowed, also after resolution, so LPs can take their tokens
+// to the redeem instructions.
+pub(crate) fn remove_liquidity(
+    ctx: Context<PoolLiquidity>,
+    lp_amount: u64,
+    min_tokens_out: u64,
+    min_lamports_out: u64,
+) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_PAIR_REDEEM)?;
+    require!(lp_amount > 0, PredictError::InvalidAmount);
+    require!(ctx.accounts.user_lp_ata.amount >= lp_amount, PredictError::InsufficientLpBalance);
+
+    let pool = &ctx.accounts.pool;
+    require!(lp_amount <= pool.lp_supply, PredictError::InsufficientLpBalance);
+
+    let tokens_out = mul_div_floor(lp_amount, pool.token_reserve, pool.lp_supply)?;
+    let lamports_out = mul_div_floor(lp_amount, pool.sol_reserve, pool.lp_supply)?;
+    require!(tokens_out >= min_tokens_out, PredictError::SlippageExceeded);
+    require!(lamports_out >= min_lamports_out, PredictError::SlippageExceeded);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    let event_key = ctx.accounts.event.key();
+    transfer_from_pool_token_vault(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.token_vault.to_account_info(),
+        &ctx.accounts.mint.to_account_info(),
+        &ctx.accounts.user_token_ata.to_account_info(),
+        &ctx.accounts.pool.to_account_info(),
+        &event_key,
+        pool.side,
+        pool.bump,
+        ctx.accounts.mint.decimals,
+        tokens_out,
+    )?;
+
+    transfer_from_pool_sol_vault(
+        &ctx.accounts.sol_vault.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &event_key,
+        pool.side,
+        pool.sol_vault_bump,
+        lamports_out,
+    )?;
+
+    let pool = &mut ctx.accounts.pool;
+    pool.token_reserve = pool.token_reserve.checked_sub(tokens_out).ok_or(PredictError::MathOverflow)?;
+    pool.sol_reserve = pool.sol_reserve.checked_sub(lamports_out).ok_or(PredictError::MathOverflow)?;
+    pool.lp_supply = pool.lp_supply.checked_sub(lp_amount).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// sol_to_token = true buys position tokens with SOL, false sells them for SOL
+pub(crate) fn swap(ctx: Context<PoolSwap>, sol_to_token: bool, amount_in: u64, min_amount_out: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_BUY)?;
+    require!(amount_in > 0, PredictError::InvalidAmount);
+    require_trading_open(&ctx.accounts.event)?;
+
+    let pool = &ctx.accounts.pool;
+    let event_key = ctx.accounts.event.key();
+
+    let amount_out = if sol_to_token {
+        cp_amount_out(pool.sol_reserve, pool.token_reserve, amount_in, pool.fee_bps)?
+    } else {
+        cp_amount_out(pool.token_reserve, pool.sol_reserve, amount_in, pool.fee_bps)?
+    };
+    require!(amount_out > 0 && amount_out >= min_amount_out, PredictError::SlippageExceeded);
+
+    if sol_to_token {
+        transfer_in(&ctx.accounts.user, &ctx.accounts.sol_vault, &ctx.accounts.system_program, amount_in)?;
+
+        transfer_from_pool_token_vault(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.token_vault.to_account_info(),
+
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.user_token_ata.to_account_info(),
+            &ctx.accounts.pool.to_account_info(),
+            &event_key,
+            pool.side,
+            pool.bump,
+
+            ctx.accounts.mint.decimals,
+            amount_out,
+        )?;
+    } else {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_token_ata.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.token_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount_in,
+            ctx.accounts.mint.decimals,
+        )?;
+
+        transfer_from_pool_sol_vault(
+            &ctx.accounts.sol_vault.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &event_key,
+            pool.side,
+            pool.sol_vault_bump,
+            amount_out,
+        )?;
+    }
+
+    let pool = &mut ctx.accounts.pool;
+    if sol_to_token {
+        pool.sol_reserve = pool.sol_reserve.checked_add(amount_in).ok_or(PredictError::MathOverflow)?;
+        pool.token_reserve = pool.token_reserve.checked_sub(amount_out).ok_or(PredictError::MathOverflow)?;
+    } else {
+        pool.token_reserve = pool.token_reserve.checked_add(amount_in).ok_or(PredictError::MathOverflow)?;
+        pool.sol_reserve = pool.sol_reserve.checked_sub(amount_out).ok_or(PredictError::MathOverflow)?;
+    }
+
+    // the pool fee goes to the LPs rather than the protocol, so no tier discount applies
+    let side = pool.side;
+    let now = Clock::get()?.unix_timestamp;
+    let bumps = [ctx.bumps.user_stats, ctx.bumps.user_position];
+    if sol_to_token {
+        record_buy(
+            &mut ctx.accounts.user_stats,
+            &mut ctx.accounts.user_position,
+            &ctx.accounts.user.key(),
+            &event_key,
+            bumps,
+            amount_in,
+            amount_in,
+            side_units(side, amount_out)?,
+            now,
+        )?;
+    } else {
+        let [true_units, false_units] = side_units(side, amount_in)?;
+        record_redemption(
+            &mut ctx.accounts.user_stats,
+            &mut ctx.accounts.user_position,
+            &ctx.accounts.user.key(),
+            &event_key,
+            bumps,
+            amount_out,
+            true_units,
+            false_units,
+            0,
+            now,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(side: u8)]
+pub struct InitPool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(constraint = event.creator == creator.key() @ PredictError::Unauthorized)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 1 + 2 + 8 * 3 + 32 * 3 + 1 + 1,
+        seeds = [SEED_POOL, event.key().as_ref(), &[side]],
+        bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    // TRUE or FALSE mint, checked against `side`
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: PDA LP mint, created in the handler with the pool as authority
+    #[account(mut, seeds = [SEED_POOL_LP_MINT, event.key().as_ref(), &[side]], bump)]
+    pub lp_mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [SEED_POOL_TOKEN_VAULT, event.key().as_ref(), &[side]],
+        bump,
+        token::mint = mint,
+        token::authority = pool,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA system account holding the pool SOL
+    #[account(mut, seeds = [SEED_POOL_SOL_VAULT, event.key().as_ref(), &[side]], bump)]
+    pub sol_vault: UncheckedAccount<'info>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct PoolLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, event.key().as_ref(), &[pool.side]],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut, address = pool.lp_mint @ PredictError::InvalidMint)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool.token_vault @ PredictError::InvalidVault)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_vault.mint @ PredictError::InvalidMint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool.sol_vault @ PredictError::InvalidVault)]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_token_ata.mint == token_vault.mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_lp_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_lp_ata.mint == lp_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_lp_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PoolSwap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_POOL, event.key().as_ref(), &[pool.side]],
+        bump = pool.bump
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    #[account(mut, address = pool.token_vault @ PredictError::InvalidVault)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = token_vault.mint @ PredictError::InvalidMint)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut, address = pool.sol_vault @ PredictError::InvalidVault)]
+    pub sol_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = user_token_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_token_ata.mint == token_vault.mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_token_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // ---- trading stats ----
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,92 @@
+use crate::*;
+
+// Registers a referrer; buys that pass this account pay it part of the house cut.
+pub(crate) fn init_referral_account(ctx: Context<InitReferralAccount>) -> Result<()> {
+    let referrer_key = ctx.accounts.referrer.key();
+    let vault_bump = ctx.bumps.referral_vault;
+    let vault_seeds: [&[u8]; 3] = [SEED_REFERRAL_VAULT, referrer_key.as_ref(), &[vault_bump]];
+    create_system_pda_0space(
+        &ctx.accounts.referrer.to_account_info(),
+        &ctx.accounts.referral_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &vault_seeds,
+    )?;
+
+    let referral = &mut ctx.accounts.referral;
+    referral.referrer = referrer_key;
+    referral.pending_lamports = 0;
+    referral.total_earned_lamports = 0;
+    referral.total_claimed_lamports = 0;
+    referral.bump = ctx.bumps.referral;
+    referral.vault_bump = vault_bump;
+    Ok(())
+}
+
+pub(crate) fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+    let referral = &mut ctx.accounts.referral;
+    let amount = referral.pending_lamports;
+    require!(amount > 0, PredictError::NothingToClaim);
+
+    // rent safety
+    let keep = vault_keep_lamports()?;
+    require!(
+        ctx.accounts.referral_vault.lamports() >= keep.saturating_add(amount),
+        PredictError::VaultInsufficientFunds
+    );
+
+    let referrer_key = ctx.accounts.referrer.key();
+    let seeds: [&[u8]; 3] = [SEED_REFERRAL_VAULT, referrer_key.as_ref(), &[referral.vault_bump]];
+    invoke_signed(
+        &system_instruction::transfer(&ctx.accounts.referral_vault.key(), &referrer_key, amount),
+        &[
+            ctx.accounts.referral_vault.to_account_info(),
+            ctx.accounts.referrer.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&seeds],
+    )?;
+
+    referral.pending_lamports = 0;
+    referral.total_claimed_lamports = referral.total_claimed_lamports.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitReferralAccount<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = 8 + 32 + 8 * 3 + 1 + 1,
+        seeds = [SEED_REFERRAL, referrer.key().as_ref()],
+        bump
+    )]
+    pub referral: Account<'info, ReferralAccount>,
+
+    /// CHECK: PDA system account, holds referral rewards
+    #[account(mut, seeds = [SEED_REFERRAL_VAULT, referrer.key().as_ref()], bump)]
+    pub referral_vault: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimReferralRewards<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_REFERRAL, referrer.key().as_ref()],
+        bump = referral.bump,
+        has_one = referrer @ PredictError::Unauthorized
+    )]
+    pub referral: Account<'info, ReferralAccount>,
+
+    #[account(mut, seeds = [SEED_REFERRAL_VAULT, referrer.key().as_ref()], bump = referral.vault_bump)]
+    pub referral_vault: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
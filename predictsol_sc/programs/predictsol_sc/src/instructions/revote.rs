@@ -0,0 +1,136 @@
+use crate::*;
+
+// Instead of the 50/50 split, the creator can send a tie or below-threshold result to a
+// fresh Truth Network question (created beforehand with create_question). The round is
+// archived, revote_funding_bps of the pending creator commission is added to the new
+// question's reward and the event goes back to pending for fetch_and_store_winner.
+pub(crate) fn request_revote(ctx: Context<RequestRevote>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_RESOLVE)?;
+    require_native_collateral(&ctx.accounts.event)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    let ev = &mut ctx.accounts.event;
+    let q = &ctx.accounts.new_truth_question;
+
+    require!(ev.kind != EVENT_KIND_SCALAR, PredictError::InvalidEventKind);
+    require!(ev.revote_max_rounds > 0, PredictError::RevoteNotAllowed);
+    require!(
+        (ev.revote_round as usize) < (ev.revote_max_rounds as usize).min(MAX_REVOTE_ROUNDS),
+        PredictError::MaxRevoteRoundsReached
+    );
+    require!(revote_available(ev, now), PredictError::RevoteNotAllowed);
+
+    // the new question must be unused and still ahead of its commit phase end
+    require!(q.key() != ev.truth_question, PredictError::TruthQuestionMismatch);
+    require!(
+        !ev.revote_history.iter().any(|r| r.question == q.key()),
+        PredictError::DuplicateTruthQuestion
+    );
+    require!(!q.finalized, PredictError::RevoteNotAllowed);
+    require!(now < q.commit_end_time, PredictError::InvalidTimeOrder);
+
+    // same question asked again: the event title, with the options of the current question
+    let current = &ctx.accounts.current_truth_question;
+    require!(q.question_text == ev.title, PredictError::RevoteQuestionMismatch);
+    require!(
+        q.option_1 == current.option_1 && q.option_2 == current.option_2,
+        PredictError::RevoteQuestionMismatch
+    );
+    require!(q.commit_end_time < q.reveal_end_time, PredictError::InvalidTimeOrder);
+    require_keys_eq!(
+        ctx.accounts.truth_network_vault.key(),
+        q.vault_address,
+        PredictError::InvalidTruthVault
+    );
+
+    // a no-winner clawback is undone first; the next round decides again
+    reinstate_creator_commission(
+        ev,
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.commission_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.bumps.collateral_vault,
+    )?;
+
+    let funding = (ev.pending_creator_commission as u128)
+        .checked_mul(ev.revote_funding_bps as u128)
+        .ok_or(PredictError::MathOverflow)?
+        .checked_div(BPS_DENOM as u128)
+        .ok_or(PredictError::MathOverflow)? as u64;
+    if funding > 0 {
+        let event_key = ev.key();
+        transfer_from_commission_vault(
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.truth_network_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            &event_key,
+            ctx.bumps.commission_vault,
+            funding,
+        )?;
+        ev.pending_creator_commission = ev
+            .pending_creator_commission
+            .checked_sub(funding)
+            .ok_or(PredictError::MathOverflow)?;
+        ev.total_truth_commission_sent = ev
+            .total_truth_commission_sent
+            .checked_add(funding)
+            .ok_or(PredictError::MathOverflow)?;
+    }
+
+    // archive the round that just ended
+    let round = ev.revote_round as usize;
+    ev.revote_history[round] = RevoteRound {
+        question: ev.truth_question,
+        votes_option_1: ev.votes_option_1,
+        votes_option_2: ev.votes_option_2,
+        result_status: ev.result_status,
+        funding_lamports: funding,
+    };
+    ev.revote_round = ev.revote_round.checked_add(1).ok_or(PredictError::MathOverflow)?;
+
+    // back to pending on the new question
+    ev.truth_question = q.key();
+    ev.commit_end_time = q.commit_end_time;
+    ev.reveal_end_time = q.reveal_end_time;
+    ev.resolved = false;
+    ev.resolved_at = 0;
+    ev.result_status = RESULT_PENDING;
+    ev.winning_option = 0;
+    ev.winning_percent_bps = 0;
+    ev.votes_option_1 = 0;
+    ev.votes_option_2 = 0;
+    ev.dispute_deadline = 0;
+    ev.dispute_status = DISPUTE_NONE;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RequestRevote<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut, has_one = creator @ PredictError::Unauthorized)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(address = event.truth_question @ PredictError::TruthQuestionMismatch)]
+    pub current_truth_question: Box<Account<'info, Question>>,
+
+    pub new_truth_question: Box<Account<'info, Question>>,
+
+    /// CHECK: vault is system-owned PDA in Truth-Network (no data), but must be mutable
+    #[account(mut)]
+    pub truth_network_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Commission vault PDA
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()], bump)]
+    pub commission_vault: AccountInfo<'info>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(mut, seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
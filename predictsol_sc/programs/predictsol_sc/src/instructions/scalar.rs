@@ -0,0 +1,320 @@
+use crate::*;
+
+// Turns a freshly created event into a scalar (range) event, before its mints exist.
+pub(crate) fn init_scalar_market(ctx: Context<InitScalarMarket>, lower: i64, upper: i64) -> Result<()> {
+    require_native_collateral(&ctx.accounts.event)?;
+    require!(lower < upper, PredictError::InvalidScalarRange);
+    require!(ctx.accounts.event.true_mint == Pubkey::default(), PredictError::MintsAlreadyCreated);
+    require!(ctx.accounts.event.kind == EVENT_KIND_BINARY, PredictError::InvalidEventKind);
+
+    let market = &mut ctx.accounts.scalar_market;
+    market.event = ctx.accounts.event.key();
+    market.lower = lower;
+    market.upper = upper;
+    market.reported_value = 0;
+    market.value_reported = false;
+    market.long_payout_per_token = 0;
+    market.bump = ctx.bumps.scalar_market;
+
+    ctx.accounts.event.kind = EVENT_KIND_SCALAR;
+    Ok(())
+}
+
+// The creator reports the value once betting is over; Truth voters confirm it
+// (option 1) or reject it (option 2) during the commit/reveal window.
+pub(crate) fn report_scalar_value(ctx: Context<ReportScalarValue>, value: i64) -> Result<()> {
+    let ev = &ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now >= ev.bet_end_time, PredictError::BettingStillActive);
+    require!(now < ev.commit_end_time, PredictError::ScalarReportWindowClosed);
+    require!(!ctx.accounts.scalar_market.value_reported, PredictError::ScalarValueAlreadyReported);
+
+    let market = &mut ctx.accounts.scalar_market;
+    market.reported_value = value;
+    market.value_reported = true;
+
+    Ok(())
+}
+
+pub(crate) fn fetch_and_store_scalar_result(ctx: Context<FetchAndStoreScalarResult>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_RESOLVE)?;
+    let ev = &mut ctx.accounts.event;
+    let q = &mut ctx.accounts.truth_network_question;
+    let market = &mut ctx.accounts.scalar_market;
+
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now >= ev.bet_end_time, PredictError::BettingStillActive);
+    require!(!ev.resolved, PredictError::EventAlreadyResolved);
+    require!(!ev.emergency_exit_active, PredictError::EmergencyExitActive);
+    require!(ev.kind == EVENT_KIND_SCALAR, PredictError::InvalidEventKind);
+    require_keys_eq!(ev.truth_question, q.key(), PredictError::TruthQuestionMismatch);
+    require!(now >= q.reveal_end_time, PredictError::TruthVotingStillActive);
+
+    // CPI: finalize voting on Truth Network
+    let question_id = q.id;
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.truth_network_program.to_account_info(),
+        FinalizeVoting { question: q.to_account_info() },
+    );
+    finalize_voting(cpi_ctx, question_id)?;
+    q.reload()?;
+
+    let v1 = q.votes_option_1;
+    let v2 = q.votes_option_2;
+    let total_votes = v1.checked_add(v2).ok_or(PredictError::MathOverflow)?;
+
+    ev.votes_option_1 = v1;
+    ev.votes_option_2 = v2;
+    ev.resolved_at = now;
+    ev.resolved = true;
+
+    // sweep house commission once, regardless of outcome
+    let vault_bump = ctx.bumps.commission_vault;
+    sweep_house_commission(
+        ev,
+        &ctx.accounts.commission_vault.to_account_info(),
+        &ctx.accounts.house_treasury.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        vault_bump,
+    )?;
+
+    // Without a confirmed value both sides pay 0.5 through redeem_no_winner_after_final
+    ev.winning_option = 0;
+
+    if total_votes == 0 {
+        ev.winning_percent_bps = 0;
+        ev.result_status = RESULT_FINALIZED_NO_VOTES;
+        forfeit_creator_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+        return Ok(());
+    }
+
+    if q.winning_option == 0 {
+        ev.winning_percent_bps = 5000;
+        ev.result_status = RESULT_FINALIZED_TIE;
+        forfeit_creator_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+        return Ok(());
+    }
+
+    let winning_votes = if q.winning_option == 1 { v1 } else { v2 };
+    let wp_bps_u64 = winning_votes
+        .checked_mul(BPS_DENOM)
+        .ok_or(PredictError::MathOverflow)?
+        .checked_div(total_votes)
+        .ok_or(PredictError::MathOverflow)?;
+    let wp_bps: u16 = wp_bps_u64.min(10_000) as u16;
+    ev.winning_percent_bps = wp_bps;
+
+    // rejected, unreported or not enough consensus: no confirmed value
+    if q.winning_option != 1 || !market.value_reported || wp_bps < ev.consensus_threshold_bps {
+        ev.result_status = RESULT_FINALIZED_BELOW_THRESHOLD;
+        forfeit_creator_commission(
+            ev,
+            &ctx.accounts.commission_vault.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+            ctx.bumps.commission_vault,
+        )?;
+        return Ok(());
+    }
+
+    market.long_payout_per_token =
+        scalar_long_payout_per_token(market.lower, market.upper, market.reported_value)?;
+    ev.result_status = RESULT_RESOLVED_SCALAR;
+
+    Ok(())
+}
+
+// side 1 = LONG, 2 = SHORT
+pub(crate) fn redeem_scalar_after_final(ctx: Context<RedeemScalarAfterFinal>, side: u8, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_FINAL_REDEEM)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let ev = &mut ctx.accounts.event;
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require!(ev.result_status == RESULT_RESOLVED_SCALAR, PredictError::InvalidResultStatus);
+    require!(!ev.unclaimed_swept, PredictError::RedemptionExpired);
+
+    let expected_mint = mint_for_side(ev, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    let long_ppt = ctx.accounts.scalar_market.long_payout_per_token;
+    let ppt = if side == 1 { long_ppt } else { LAMPORTS_PER_TOKEN.saturating_sub(long_ppt) };
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let outstanding = outstanding_for_side_mut(ev, side)?;
+    *outstanding = outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    // worthless side (value clamped at the other end) just burns
+    let payout = payout_after_fee(mul_div_floor(amount, ppt, LAMPORTS_PER_TOKEN)?, ev.redeem_fee_bps)?;
+
+    // Rent safety; the last redeemer may be short by rounding dust only
+    let keep = vault_keep_lamports()?;
+    let available = ctx.accounts.collateral_vault.to_account_info().lamports().saturating_sub(keep);
+    require!(
+        available.saturating_add(VAULT_DUST_TOLERANCE_LAMPORTS) >= payout,
+        PredictError::VaultInsufficientFunds
+    );
+    let payout = payout.min(available);
+
+    transfer_from_collateral_vault(
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        &ev.key(),
+        ctx.bumps.collateral_vault,
+        payout,
+    )?;
+
+    ev.total_collateral_lamports = ev.total_collateral_lamports
+        .checked_sub(payout)
+        .ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitScalarMarket<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + 32 + 8 * 3 + 1 + 8 + 1,
+        seeds = [SEED_SCALAR_MARKET, event.key().as_ref()],
+        bump
+    )]
+    pub scalar_market: Account<'info, ScalarMarket>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReportScalarValue<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(constraint = event.creator == creator.key() @ PredictError::Unauthorized)]
+    pub event: Account<'info, Event>,
+
+    #[account(
+        mut,
+        seeds = [SEED_SCALAR_MARKET, event.key().as_ref()],
+        bump = scalar_market.bump
+    )]
+    pub scalar_market: Account<'info, ScalarMarket>,
+}
+
+#[derive(Accounts)]
+pub struct FetchAndStoreScalarResult<'info> {
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_SCALAR_MARKET, event.key().as_ref()],
+        bump = scalar_market.bump
+    )]
+    pub scalar_market: Account<'info, ScalarMarket>,
+
+    #[account(mut)]
+    pub truth_network_question: Box<Account<'info, Question>>,
+
+    pub truth_network_program: Program<'info, TruthNetwork>,
+
+    /// CHECK: Fixed house wallet
+    #[account(mut, address = event.house_wallet)]
+    pub house_treasury: AccountInfo<'info>,
+
+    /// CHECK: Commission vault PDA
+    #[account(
+        mut,
+        seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub commission_vault: AccountInfo<'info>,
+
+    /// CHECK: Collateral vault PDA, receives a forfeited creator commission
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemScalarAfterFinal<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        seeds = [SEED_SCALAR_MARKET, event.key().as_ref()],
+        bump = scalar_market.bump
+    )]
+    pub scalar_market: Account<'info, ScalarMarket>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump,
+        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    // LONG or SHORT mint, checked against `side`
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,188 @@
+use crate::*;
+
+pub(crate) fn set_single_side_pool(ctx: Context<SetSingleSidePool>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+
+    check_raydium_pool_mints(&ctx.accounts.pool_state.to_account_info(), &ctx.accounts.event)?;
+
+    ctx.accounts.event.raydium_pool = ctx.accounts.pool_state.key();
+    Ok(())
+}
+
+pub(crate) fn buy_single_side(ctx: Context<BuySingleSide>, side: u8, lamports: u64, min_tokens_out: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_BUY)?;
+    require!(side == 1 || side == 2, PredictError::InvalidSide);
+    require!(ctx.accounts.event.raydium_pool != Pubkey::default(), PredictError::InvalidRaydiumPool);
+    let now = Clock::get()?.unix_timestamp;
+
+    // same fee, deposit and mint as buy_positions_with_fee
+    let accs = &mut *ctx.accounts;
+    let net = buy_pair_core(
+        PairBuyAccounts {
+            user: &accs.user,
+            event: &mut accs.event,
+            config: &accs.config,
+            collateral_vault: &accs.collateral_vault,
+            commission_vault: &accs.commission_vault,
+            mint_authority: accs.mint_authority.to_account_info(),
+            true_mint: accs.true_mint.to_account_info(),
+            false_mint: accs.false_mint.to_account_info(),
+            user_true_ata: accs.user_true_ata.to_account_info(),
+            user_false_ata: accs.user_false_ata.to_account_info(),
+            truth_network_question: &accs.truth_network_question,
+            truth_network_vault: accs.truth_network_vault.to_account_info(),
+            user_stats: &mut accs.user_stats,
+            user_position: &mut accs.user_position,
+            referral: accs.referral.as_deref_mut(),
+            referral_vault: accs.referral_vault.as_ref(),
+            token_program: accs.token_program.to_account_info(),
+            system_program: &accs.system_program,
+        },
+        PairBuyBumps {
+            collateral_vault: ctx.bumps.collateral_vault,
+            mint_authority: ctx.bumps.mint_authority,
+            user_stats: ctx.bumps.user_stats,
+            user_position: ctx.bumps.user_position,
+        },
+        lamports,
+        now,
+    )?;
+
+    // 7) sell the unwanted side for more of the wanted side
+    let min_swap_out = min_tokens_out.saturating_sub(net);
+    let received = sell_unwanted_side(ctx.accounts, side, net, min_swap_out)?;
+    record_side_swap(&mut ctx.accounts.user_position, side, net, received)?;
+
+    let total_out = net.checked_add(received).ok_or(PredictError::MathOverflow)?;
+    require!(total_out >= min_tokens_out, PredictError::SlippageExceeded);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSingleSidePool<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Account<'info, Event>,
+
+    /// CHECK: Raydium CPMM pool state, owner and mints checked in the handler
+    pub pool_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct BuySingleSide<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub collateral_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()],
+        bump
+    )]
+    pub commission_vault: SystemAccount<'info>,
+
+    /// CHECK: PDA mint authority signer
+    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint)]
+    pub true_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint)]
+    pub false_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_true_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_true_ata.mint == true_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_true_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_false_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_false_ata.mint == false_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_false_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // ---- Truth network read + vault ----
+    #[account(mut)]
+    pub truth_network_question: Box<Account<'info, Question>>,
+
+    /// CHECK: vault is system-owned PDA in Truth-Network (no data), but must be mutable
+    #[account(mut)]
+    pub truth_network_vault: UncheckedAccount<'info>,
+
+    // ---- Raydium CPMM pool designated by the creator ----
+    /// CHECK: Raydium CPMM program
+    #[account(address = RAYDIUM_CPMM_PROGRAM_ID)]
+    pub raydium_program: UncheckedAccount<'info>,
+
+    /// CHECK: Raydium vault/LP authority PDA, checked by Raydium
+    pub raydium_authority: UncheckedAccount<'info>,
+
+    /// CHECK: checked against pool_state
+    pub amm_config: UncheckedAccount<'info>,
+
+    /// CHECK: must be the pool designated on the event
+    #[account(mut, address = event.raydium_pool @ PredictError::InvalidRaydiumPool)]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// CHECK: pool vault of the unwanted side, checked against pool_state
+    #[account(mut)]
+    pub input_vault: UncheckedAccount<'info>,
+
+    /// CHECK: pool vault of the wanted side, checked against pool_state
+    #[account(mut)]
+    pub output_vault: UncheckedAccount<'info>,
+
+    /// CHECK: checked against pool_state
+    #[account(mut)]
+    pub observation_state: UncheckedAccount<'info>,
+
+    // ---- trading stats ----
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    // ---- optional referrer ----
+    #[account(mut, seeds = [SEED_REFERRAL, referral.referrer.as_ref()], bump = referral.bump)]
+    pub referral: Option<Box<Account<'info, ReferralAccount>>>,
+
+    // checked against the referral's vault bump in the handler
+    #[account(mut)]
+    pub referral_vault: Option<SystemAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,134 @@
+use crate::*;
+
+// Same as create_event_mints, but the TRUE/FALSE mints are Token-2022 mints carrying their own
+// metadata (no Metaplex account) and a close authority, so delete_event can recover their rent.
+pub(crate) fn create_event_mints_2022(ctx: Context<CreateEventMints2022>) -> Result<()> {
+    let event_key = ctx.accounts.event.key();
+    let payer_ai = ctx.accounts.creator.to_account_info();
+    let system_ai = ctx.accounts.system_program.to_account_info();
+    let token_ai = ctx.accounts.token_program.to_account_info();
+    let auth_ai = ctx.accounts.mint_authority.to_account_info();
+    let decimals = ctx.accounts.event.collateral_decimals;
+
+    // 1) collateral vault PDA (system-owned, 0 space)
+    let vault_bump = ctx.bumps.collateral_vault;
+    let vault_seeds: [&[u8]; 3] = [
+        SEED_COLLATERAL_VAULT,
+        event_key.as_ref(),
+        &[vault_bump],
+    ];
+    create_system_pda_0space(
+        &payer_ai,
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &system_ai,
+        &vault_seeds,
+    )?;
+
+    // commission vault PDA (system-owned, 0 space), holds creator + house commissions
+    let commission_bump = ctx.bumps.commission_vault;
+    let commission_seeds: [&[u8]; 3] = [
+        SEED_COMMISSION_VAULT,
+        event_key.as_ref(),
+        &[commission_bump],
+    ];
+    create_system_pda_0space(
+        &payer_ai,
+        &ctx.accounts.commission_vault.to_account_info(),
+        &system_ai,
+        &commission_seeds,
+    )?;
+
+    let auth_bump = ctx.bumps.mint_authority;
+    let auth_seeds: [&[u8]; 3] = [
+        SEED_MINT_AUTH,
+        event_key.as_ref(),
+        &[auth_bump],
+    ];
+    let [(true_name, true_symbol, true_uri), (false_name, false_symbol, false_uri)] =
+        position_token_labels(&ctx.accounts.event, &ctx.accounts.config);
+
+    // 2) TRUE mint PDA
+    let true_bump = ctx.bumps.true_mint;
+    let true_seeds: [&[u8]; 3] = [
+        SEED_TRUE_MINT,
+        event_key.as_ref(),
+        &[true_bump],
+    ];
+    create_and_init_token_2022_mint_pda(
+        &payer_ai,
+        &ctx.accounts.true_mint.to_account_info(),
+        &token_ai,
+        &system_ai,
+        &auth_ai,
+        &true_seeds,
+        &auth_seeds,
+        decimals,
+        true_name,
+        true_symbol,
+        true_uri,
+    )?;
+
+    // 3) FALSE mint PDA
+    let false_bump = ctx.bumps.false_mint;
+    let false_seeds: [&[u8]; 3] = [
+        SEED_FALSE_MINT,
+        event_key.as_ref(),
+        &[false_bump],
+    ];
+    create_and_init_token_2022_mint_pda(
+        &payer_ai,
+        &ctx.accounts.false_mint.to_account_info(),
+        &token_ai,
+        &system_ai,
+        &auth_ai,
+        &false_seeds,
+        &auth_seeds,
+        decimals,
+        false_name,
+        false_symbol,
+        false_uri,
+    )?;
+
+    // 4) Save addresses to event
+    let ev = &mut ctx.accounts.event;
+    ev.collateral_vault = ctx.accounts.collateral_vault.key();
+    ev.true_mint = ctx.accounts.true_mint.key();
+    ev.false_mint = ctx.accounts.false_mint.key();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CreateEventMints2022<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    /// CHECK: PDA signer
+    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA true mint account
+    #[account(mut, seeds = [SEED_TRUE_MINT, event.key().as_ref()], bump)]
+    pub true_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA false mint
+    #[account(mut, seeds = [SEED_FALSE_MINT, event.key().as_ref()], bump)]
+    pub false_mint: UncheckedAccount<'info>,
+
+    /// CHECK: PDA system account
+    #[account(mut, seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA system account for commissions
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()], bump)]
+    pub commission_vault: UncheckedAccount<'info>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
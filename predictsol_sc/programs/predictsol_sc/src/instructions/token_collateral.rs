@@ -0,0 +1,782 @@
+use crate::*;
+
+// Switches a freshly created event to SPL token collateral (e.g. USDC, wSOL), before its mints exist.
+pub(crate) fn init_token_collateral(ctx: Context<InitTokenCollateral>) -> Result<()> {
+    let ev = &mut ctx.accounts.event;
+    require!(ev.true_mint == Pubkey::default(), PredictError::MintsAlreadyCreated);
+    require!(ev.kind == EVENT_KIND_BINARY, PredictError::InvalidEventKind);
+    require_native_collateral(ev)?;
+
+    ev.collateral_mint = ctx.accounts.collateral_mint.key();
+    ev.collateral_decimals = ctx.accounts.collateral_mint.decimals;
+
+    Ok(())
+}
+
+pub(crate) fn buy_positions_with_token(ctx: Context<BuyPositionsWithToken>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_BUY)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+    let now = Clock::get()?.unix_timestamp;
+
+    // 1) verification, same as buy_positions_with_fee
+    require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!ctx.accounts.event.resolved, PredictError::EventResolved);
+    require_token_collateral(&ctx.accounts.event)?;
+    require_keys_eq!(
+        ctx.accounts.event.truth_question,
+        ctx.accounts.truth_network_question.key(),
+        PredictError::TruthQuestionMismatch
+    );
+
+    // 2) fee split in collateral token units, discounted by the buyer's 30-day volume tier
+    let discount_bps = fee_tier_discount_bps(&ctx.accounts.config, rolling_volume_30d(&ctx.accounts.user_stats, now));
+    let (_fee, truth_cut, creator_cut, house_cut, net) =
+        event_fee_splits(&ctx.accounts.event, amount, now, discount_bps)?;
+
+    // 3) user -> token vault (net) and commission token vault (creator + house cut)
+    let commission = creator_cut.checked_add(house_cut).ok_or(PredictError::MathOverflow)?;
+    let to_vault = amount
+        .checked_sub(truth_cut)
+        .and_then(|v| v.checked_sub(commission))
+        .ok_or(PredictError::MathOverflow)?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_collateral_ata.to_account_info(),
+                mint: ctx.accounts.collateral_mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        to_vault,
+        ctx.accounts.collateral_mint.decimals,
+    )?;
+    if commission > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral_ata.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.commission_token_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            commission,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+    }
+
+    // 4) truth cut to the event's Truth cut vault; the Truth vault itself is a
+    // system account that could never spend collateral tokens
+    if truth_cut > 0 {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_collateral_ata.to_account_info(),
+                    mint: ctx.accounts.collateral_mint.to_account_info(),
+                    to: ctx.accounts.truth_token_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            truth_cut,
+            ctx.accounts.collateral_mint.decimals,
+        )?;
+    }
+
+    // 5) mint net TRUE + FALSE
+    let event_key = ctx.accounts.event.key();
+    let mint_auth_bump = ctx.bumps.mint_authority;
+    mint_position_tokens(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.true_mint.to_account_info(),
+        &ctx.accounts.user_true_ata.to_account_info(),
+        &ctx.accounts.mint_authority.to_account_info(),
+        &event_key,
+        mint_auth_bump,
+        net,
+    )?;
+    mint_position_tokens(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.false_mint.to_account_info(),
+        &ctx.accounts.user_false_ata.to_account_info(),
+        &ctx.accounts.mint_authority.to_account_info(),
+        &event_key,
+        mint_auth_bump,
+        net,
+    )?;
+
+    // 6) accounting (amounts in collateral token units); the truth cut counts as sent when claimed
+    apply_accounting(&mut ctx.accounts.event, amount, net, 0, creator_cut, house_cut)?;
+
+    // token amounts aren't lamports, so they build the position but not the tier volume
+    record_buy(
+        &mut ctx.accounts.user_stats,
+        &mut ctx.accounts.user_position,
+        &ctx.accounts.user.key(),
+        &event_key,
+        [ctx.bumps.user_stats, ctx.bumps.user_position],
+        0,
+        amount,
+        [net, net],
+        now,
+    )?;
+
+    Ok(())
+}
+
+pub(crate) fn redeem_pair_while_active_token(ctx: Context<RedeemPairWhileActiveToken>, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_PAIR_REDEEM)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    // as with SOL events, a voided event redeems pairs at full value until the sweep
+    let now = Clock::get()?.unix_timestamp;
+    let voided = ctx.accounts.event.result_status == RESULT_VOIDED;
+    if voided {
+        require!(!ctx.accounts.event.unclaimed_swept, PredictError::RedemptionExpired);
+    } else {
+        require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+        require!(!ctx.accounts.event.resolved, PredictError::EventAlreadyResolved);
+    }
+    require_token_collateral(&ctx.accounts.event)?;
+
+    require!(ctx.accounts.user_true_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+    require!(ctx.accounts.user_false_ata.amount >= amount, PredictError::InsufficientFalseBalance);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.true_mint.to_account_info(),
+                from: ctx.accounts.user_true_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.false_mint.to_account_info(),
+                from: ctx.accounts.user_false_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // voided: full value from the token vault, both tokens' share of the returned
+    // commission from the commission token vault
+    let (base, share) = if voided {
+        let units = amount.checked_mul(2).ok_or(PredictError::MathOverflow)?;
+        (amount, forfeited_commission_share(&ctx.accounts.event, units)?)
+    } else {
+        (payout_after_fee(amount, ctx.accounts.event.redeem_fee_bps)?, 0)
+    };
+    let payout = base.checked_add(share).ok_or(PredictError::MathOverflow)?;
+    require!(ctx.accounts.token_vault.amount >= base, PredictError::VaultInsufficientFunds);
+    require!(ctx.accounts.commission_token_vault.amount >= share, PredictError::VaultInsufficientFunds);
+
+    for (vault, part) in [(&ctx.accounts.token_vault, base), (&ctx.accounts.commission_token_vault, share)] {
+        if part == 0 {
+            continue;
+        }
+        transfer_from_token_vault(
+            &ctx.accounts.token_program.to_account_info(),
+            &vault.to_account_info(),
+
+            &ctx.accounts.collateral_mint.to_account_info(),
+            &ctx.accounts.user_collateral_ata.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ctx.accounts.event.key(),
+            ctx.bumps.collateral_vault,
+
+            ctx.accounts.collateral_mint.decimals,
+            part,
+        )?;
+    }
+
+    let ev = &mut ctx.accounts.event;
+    ev.total_collateral_lamports = ev.total_collateral_lamports.checked_sub(payout).ok_or(PredictError::MathOverflow)?;
+    ev.outstanding_true = ev.outstanding_true.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+    ev.outstanding_false = ev.outstanding_false.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// side is only checked for the no-winner case; a winner must redeem the winning side
+pub(crate) fn redeem_after_final_token(ctx: Context<RedeemAfterFinalToken>, side: u8, amount: u64) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_FINAL_REDEEM)?;
+    require_result_final(&ctx.accounts.event)?;
+    require!(amount > 0, PredictError::InvalidAmount);
+
+    let ev = &mut ctx.accounts.event;
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require!(!ev.unclaimed_swept, PredictError::RedemptionExpired);
+    require_token_collateral(ev)?;
+
+    let expected_mint = mint_for_side(ev, side)?;
+    require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
+    require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+
+    // winner pays 1 per token, every no-winner status pays 0.5 per token plus its share
+    // of the forfeited commission, which sits in the commission token vault
+    let (base, share) = if ev.result_status == RESULT_RESOLVED_WINNER {
+        require!(ev.winning_option == side, PredictError::NotWinningToken);
+        (payout_after_fee(amount, ev.redeem_fee_bps)?, 0)
+    } else {
+        let base = payout_after_fee(amount, effective_redeem_fee_bps(ev))?
+            .checked_div(2)
+            .ok_or(PredictError::MathOverflow)?;
+        (base, forfeited_commission_share(ev, amount)?)
+    };
+    let payout = base.checked_add(share).ok_or(PredictError::MathOverflow)?;
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.mint.to_account_info(),
+                from: ctx.accounts.user_ata.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let outstanding = outstanding_for_side_mut(ev, side)?;
+    *outstanding = outstanding.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
+
+    require!(ctx.accounts.token_vault.amount >= base, PredictError::VaultInsufficientFunds);
+    require!(ctx.accounts.commission_token_vault.amount >= share, PredictError::VaultInsufficientFunds);
+
+    for (vault, part) in [(&ctx.accounts.token_vault, base), (&ctx.accounts.commission_token_vault, share)] {
+        if part == 0 {
+            continue;
+        }
+        transfer_from_token_vault(
+            &ctx.accounts.token_program.to_account_info(),
+            &vault.to_account_info(),
+
+            &ctx.accounts.collateral_mint.to_account_info(),
+            &ctx.accounts.user_collateral_ata.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ev.key(),
+            ctx.bumps.collateral_vault,
+
+            ctx.accounts.collateral_mint.decimals,
+            part,
+        )?;
+    }
+
+    ev.total_collateral_lamports = ev.total_collateral_lamports
+        .checked_sub(payout)
+        .ok_or(PredictError::MathOverflow)?;
+
+    Ok(())
+}
+
+// remaining_accounts = co-creator collateral token accounts, in order
+pub(crate) fn claim_creator_commission_token<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ClaimCreatorCommissionToken<'info>>,
+) -> Result<()> {
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require_keys_eq!(ctx.accounts.creator.key(), ev.creator, PredictError::Unauthorized);
+    require_token_collateral(ev)?;
+
+    let amount = claimable_creator_commission(ev, now)?;
+    require!(amount > 0, PredictError::NothingToClaim);
+    require!(ctx.accounts.commission_token_vault.amount >= amount, PredictError::VaultInsufficientFunds);
+
+    let co_count = ev.co_creator_count as usize;
+    require!(ctx.remaining_accounts.len() == co_count, PredictError::InvalidRemainingAccounts);
+    let (payout_amount, shares) = creator_commission_splits(ev, amount)?;
+
+    let event_key = ev.key();
+    let token_program_ai = ctx.accounts.token_program.to_account_info();
+    let token_vault_ai = ctx.accounts.commission_token_vault.to_account_info();
+    let mint_ai = ctx.accounts.collateral_mint.to_account_info();
+    let authority_ai = ctx.accounts.collateral_vault.to_account_info();
+    let decimals = ctx.accounts.collateral_mint.decimals;
+
+    transfer_from_token_vault(
+        &token_program_ai,
+        &token_vault_ai,
+
+        &mint_ai,
+        &ctx.accounts.creator_collateral_ata.to_account_info(),
+        &authority_ai,
+        &event_key,
+        ctx.bumps.collateral_vault,
+
+        decimals,
+        payout_amount,
+    )?;
+
+    for (i, dest) in ctx.remaining_accounts.iter().enumerate() {
+        let ata: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(dest)?;
+        require_keys_eq!(ata.owner, ev.co_creators[i].wallet, PredictError::InvalidTokenAccountOwner);
+        require_keys_eq!(ata.mint, ev.collateral_mint, PredictError::InvalidTokenAccountMint);
+
+        transfer_from_token_vault(
+            &token_program_ai,
+            &token_vault_ai,
+
+            &mint_ai,
+            dest,
+            &authority_ai,
+            &event_key,
+            ctx.bumps.collateral_vault,
+
+            decimals,
+            shares[i],
+        )?;
+    }
+
+    ev.pending_creator_commission = ev
+        .pending_creator_commission
+        .checked_sub(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    ev.claimed_creator_commission = ev
+        .claimed_creator_commission
+        .checked_add(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+// Token-collateral counterpart of the house sweep done in fetch_and_store_winner.
+pub(crate) fn sweep_house_commission_token(ctx: Context<SweepHouseToken>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_SWEEP)?;
+    let ev = &mut ctx.accounts.event;
+
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require_token_collateral(ev)?;
+
+    let amount = ev.pending_house_commission;
+    require!(amount > 0, PredictError::NothingToSweep);
+    require!(ctx.accounts.commission_token_vault.amount >= amount, PredictError::VaultInsufficientFunds);
+
+    transfer_from_token_vault(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.commission_token_vault.to_account_info(),
+
+        &ctx.accounts.collateral_mint.to_account_info(),
+        &ctx.accounts.house_collateral_ata.to_account_info(),
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ev.key(),
+        ctx.bumps.collateral_vault,
+
+        ctx.accounts.collateral_mint.decimals,
+        amount,
+    )?;
+
+    ev.pending_house_commission = 0;
+    Ok(())
+}
+
+pub(crate) fn sweep_unclaimed_to_house_token(ctx: Context<SweepHouseToken>) -> Result<()> {
+    require_not_paused(&ctx.accounts.config, &ctx.accounts.event.pause, PAUSE_SWEEP)?;
+    require_result_final(&ctx.accounts.event)?;
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(ev.resolved, PredictError::EventNotResolved);
+    require!(!ev.unclaimed_swept, PredictError::AlreadySwept);
+    require_token_collateral(ev)?;
+
+    // only after X days
+    require!(
+        now >= ev.resolved_at.saturating_add(ev.unclaimed_sweep_delay_secs),
+        PredictError::SweepNotYetAvailable
+    );
+
+    // the collateral, the house commission and any unredeemed forfeited commission;
+    // the creator's pending commission stays claimable
+    let collateral = ctx.accounts.token_vault.amount;
+    let commission = ctx.accounts.commission_token_vault.amount.saturating_sub(ev.pending_creator_commission);
+    let amount = collateral.checked_add(commission).ok_or(PredictError::MathOverflow)?;
+    require!(amount > 0, PredictError::NothingToSweep);
+
+    for (vault, part) in [(&ctx.accounts.token_vault, collateral), (&ctx.accounts.commission_token_vault, commission)] {
+        if part == 0 {
+            continue;
+        }
+        transfer_from_token_vault(
+            &ctx.accounts.token_program.to_account_info(),
+            &vault.to_account_info(),
+
+            &ctx.accounts.collateral_mint.to_account_info(),
+            &ctx.accounts.house_collateral_ata.to_account_info(),
+            &ctx.accounts.collateral_vault.to_account_info(),
+            &ev.key(),
+            ctx.bumps.collateral_vault,
+
+            ctx.accounts.collateral_mint.decimals,
+            part,
+        )?;
+    }
+
+    ev.total_collateral_lamports = ev.total_collateral_lamports.saturating_sub(amount);
+    ev.pending_house_commission = 0;
+    ev.unclaimed_swept = true;
+    ev.swept_at = now;
+
+    Ok(())
+}
+
+// Permissionless: pays the Truth cut collected in collateral tokens to the Truth Network
+// wallet set in the config.
+pub(crate) fn claim_truth_commission_token(ctx: Context<ClaimTruthCommissionToken>) -> Result<()> {
+    require_token_collateral(&ctx.accounts.event)?;
+    require!(
+        ctx.accounts.config.truth_fee_recipient != Pubkey::default(),
+        PredictError::TruthRecipientNotConfigured
+    );
+
+    let amount = ctx.accounts.truth_token_vault.amount;
+    require!(amount > 0, PredictError::NothingToClaim);
+
+    transfer_from_token_vault(
+        &ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.truth_token_vault.to_account_info(),
+
+        &ctx.accounts.collateral_mint.to_account_info(),
+        &ctx.accounts.truth_collateral_ata.to_account_info(),
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.event.key(),
+        ctx.bumps.collateral_vault,
+
+        ctx.accounts.collateral_mint.decimals,
+        amount,
+    )?;
+
+    let ev = &mut ctx.accounts.event;
+    ev.total_truth_commission_sent = ev
+        .total_truth_commission_sent
+        .checked_add(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitTokenCollateral<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = event.creator == creator.key() @ PredictError::Unauthorized
+    )]
+    pub event: Box<Account<'info, Event>>,
+
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: collateral vault PDA, authority of the token vault
+    #[account(seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [SEED_TOKEN_COLLATERAL_VAULT, event.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = collateral_vault,
+        token::token_program = token_program
+    )]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Truth cut in collateral tokens, paid out by claim_truth_commission_token
+    #[account(
+        init,
+        payer = creator,
+        seeds = [SEED_TRUTH_TOKEN_VAULT, event.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = collateral_vault,
+        token::token_program = token_program
+    )]
+    pub truth_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // creator, house and forfeited commission, kept apart from the collateral
+    #[account(
+        init,
+        payer = creator,
+        seeds = [SEED_COMMISSION_TOKEN_VAULT, event.key().as_ref()],
+        bump,
+        token::mint = collateral_mint,
+        token::authority = collateral_vault,
+        token::token_program = token_program
+    )]
+    pub commission_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct BuyPositionsWithToken<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut, seeds = [SEED_TOKEN_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = event.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_collateral_ata.mint == event.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: PDA mint authority signer
+    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(mut, constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint)]
+    pub true_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint)]
+    pub false_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_true_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_true_ata.mint == true_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_true_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_false_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_false_ata.mint == false_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_false_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // ---- Truth network read + the event's Truth cut vault ----
+    pub truth_network_question: Box<Account<'info, Question>>,
+
+    #[account(mut, seeds = [SEED_TRUTH_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub truth_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub commission_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // ---- trading stats ----
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 8 * 4 + 4 + 8 * 2 + 1,
+        seeds = [SEED_USER_STATS, user.key().as_ref()],
+        bump
+    )]
+    pub user_stats: Box<Account<'info, UserStats>>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + 32 + 32 + 8 * 5 + 1,
+        seeds = [SEED_USER_POSITION, event.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_position: Box<Account<'info, UserPosition>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemPairWhileActiveToken<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: collateral vault PDA, authority of the token vault
+    #[account(seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [SEED_TOKEN_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub commission_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = event.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_collateral_ata.mint == event.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint)]
+    pub true_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(mut, constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint)]
+    pub false_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_true_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_true_ata.mint == true_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_true_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        constraint = user_false_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_false_ata.mint == false_mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_false_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RedeemAfterFinalToken<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: collateral vault PDA, authority of the token vault
+    #[account(seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [SEED_TOKEN_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub commission_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = event.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_collateral_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_collateral_ata.mint == event.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // TRUE or FALSE mint, checked against `side`
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub user_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorCommissionToken<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    /// CHECK: collateral vault PDA, authority of the commission token vault
+    #[account(seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub commission_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = event.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = creator_collateral_ata.owner == event.creator_payout @ PredictError::InvalidTokenAccountOwner,
+        constraint = creator_collateral_ata.mint == event.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub creator_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SweepHouseToken<'info> {
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: collateral vault PDA, authority of the token vault
+    #[account(seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [SEED_TOKEN_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, seeds = [SEED_COMMISSION_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub commission_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = event.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = house_collateral_ata.owner == event.house_wallet @ PredictError::InvalidTokenAccountOwner,
+        constraint = house_collateral_ata.mint == event.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub house_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTruthCommissionToken<'info> {
+    #[account(mut)]
+    pub event: Box<Account<'info, Event>>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    /// CHECK: collateral vault PDA, authority of the Truth cut vault
+    #[account(seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: UncheckedAccount<'info>,
+
+    #[account(mut, seeds = [SEED_TRUTH_TOKEN_VAULT, event.key().as_ref()], bump)]
+    pub truth_token_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = event.collateral_mint @ PredictError::InvalidMint)]
+    pub collateral_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = truth_collateral_ata.owner == config.truth_fee_recipient @ PredictError::InvalidTruthVault,
+        constraint = truth_collateral_ata.mint == event.collateral_mint @ PredictError::InvalidTokenAccountMint
+    )]
+    pub truth_collateral_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
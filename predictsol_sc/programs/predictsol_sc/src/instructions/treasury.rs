@@ -0,0 +1,155 @@
+use crate::*;
+
+// Creates the HouseTreasury PDA; point the config house wallet at it through a config change.
+pub(crate) fn init_house_treasury(ctx: Context<InitHouseTreasury>) -> Result<()> {
+    let treasury = &mut ctx.accounts.house_treasury;
+    treasury.total_received = 0;
+    treasury.total_distributed = 0;
+    treasury.inflow_count = 0;
+    treasury.last_distributed_at = 0;
+    treasury.bump = ctx.bumps.house_treasury;
+    Ok(())
+}
+
+// Permissionless: pays everything above rent to the config beneficiaries by weight.
+// remaining_accounts = wallets of the beneficiaries with a non-zero weight, in config order.
+pub(crate) fn distribute_treasury<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeTreasury<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let beneficiaries: Vec<TreasuryBeneficiary> = ctx
+        .accounts
+        .config
+        .treasury_beneficiaries
+        .iter()
+        .filter(|b| b.weight_bps > 0)
+        .copied()
+        .collect();
+    require!(!beneficiaries.is_empty(), PredictError::TreasuryNotConfigured);
+    require!(ctx.remaining_accounts.len() == beneficiaries.len(), PredictError::InvalidRemainingAccounts);
+
+    let treasury_ai = ctx.accounts.house_treasury.to_account_info();
+    let rent_min = Rent::get()?.minimum_balance(treasury_ai.data_len());
+    let available = treasury_ai.lamports().saturating_sub(rent_min);
+    require!(available > 0, PredictError::NothingToClaim);
+
+    let mut paid: u64 = 0;
+    for (b, dest) in beneficiaries.iter().zip(ctx.remaining_accounts.iter()) {
+        require_keys_eq!(dest.key(), b.wallet, PredictError::InvalidRemainingAccounts);
+        let share = mul_div_floor(available, b.weight_bps as u64, BPS_DENOM)?;
+        ctx.accounts.house_treasury.sub_lamports(share)?;
+        dest.add_lamports(share)?;
+        paid = paid.checked_add(share).ok_or(PredictError::MathOverflow)?;
+    }
+
+    let treasury = &mut ctx.accounts.house_treasury;
+    treasury.total_distributed = treasury.total_distributed.checked_add(paid).ok_or(PredictError::MathOverflow)?;
+    treasury.last_distributed_at = now;
+    Ok(())
+}
+
+// Same for collateral tokens swept into a treasury-owned token account.
+// remaining_accounts = token accounts of the beneficiaries with a non-zero weight, in config order.
+pub(crate) fn distribute_treasury_token<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DistributeTreasuryToken<'info>>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let beneficiaries: Vec<TreasuryBeneficiary> = ctx
+        .accounts
+        .config
+        .treasury_beneficiaries
+        .iter()
+        .filter(|b| b.weight_bps > 0)
+        .copied()
+        .collect();
+    require!(!beneficiaries.is_empty(), PredictError::TreasuryNotConfigured);
+    require!(ctx.remaining_accounts.len() == beneficiaries.len(), PredictError::InvalidRemainingAccounts);
+
+    let available = ctx.accounts.treasury_token_account.amount;
+    require!(available > 0, PredictError::NothingToClaim);
+
+    let bump = ctx.accounts.house_treasury.bump;
+    let seeds: [&[u8]; 2] = [SEED_HOUSE_TREASURY, &[bump]];
+    let mint = &ctx.accounts.mint;
+
+    for (b, dest) in beneficiaries.iter().zip(ctx.remaining_accounts.iter()) {
+        let ata: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(dest)?;
+        require_keys_eq!(ata.owner, b.wallet, PredictError::InvalidTokenAccountOwner);
+        require_keys_eq!(ata.mint, mint.key(), PredictError::InvalidTokenAccountMint);
+
+        let share = mul_div_floor(available, b.weight_bps as u64, BPS_DENOM)?;
+        if share == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.treasury_token_account.to_account_info(),
+                    mint: mint.to_account_info(),
+                    to: dest.clone(),
+                    authority: ctx.accounts.house_treasury.to_account_info(),
+                },
+            )
+            .with_signer(&[&seeds]),
+            share,
+            mint.decimals,
+        )?;
+    }
+
+    ctx.accounts.house_treasury.last_distributed_at = now;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitHouseTreasury<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [SEED_PROTOCOL_CONFIG],
+        bump = config.bump,
+        has_one = admin @ PredictError::Unauthorized
+    )]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + 8 * 4 + 1,
+        seeds = [SEED_HOUSE_TREASURY],
+        bump
+    )]
+    pub house_treasury: Account<'info, HouseTreasury>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeTreasury<'info> {
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut, seeds = [SEED_HOUSE_TREASURY], bump = house_treasury.bump)]
+    pub house_treasury: Account<'info, HouseTreasury>,
+}
+
+#[derive(Accounts)]
+pub struct DistributeTreasuryToken<'info> {
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut, seeds = [SEED_HOUSE_TREASURY], bump = house_treasury.bump)]
+    pub house_treasury: Account<'info, HouseTreasury>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == house_treasury.key() @ PredictError::InvalidTokenAccountOwner,
+        constraint = treasury_token_account.mint == mint.key() @ PredictError::InvalidTokenAccountMint
+    )]
+    pub treasury_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,86 @@
+use crate::*;
+
+// Governance invalidates an event (ambiguous wording, cancelled match, creator fraud).
+// Pending creator and house commission go back to the holders, who then redeem each
+// side at 0.5 or a pair at full value, fee free. The Truth cut already left for the
+// Truth Network and referral cuts were paid to referrers, so those cannot be refunded.
+// A provisional result (dispute window, open dispute, revote window) can still be
+// voided; an open dispute is closed with the bond returned to the challenger.
+pub(crate) fn void_event(ctx: Context<VoidEvent>) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let void_authority = if config.void_authority == Pubkey::default() { config.admin } else { config.void_authority };
+    require_keys_eq!(ctx.accounts.authority.key(), void_authority, PredictError::Unauthorized);
+
+    let ev = &mut ctx.accounts.event;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(!ev.emergency_exit_active, PredictError::EmergencyExitActive);
+    if ev.resolved {
+        require!(ev.result_status != RESULT_VOIDED, PredictError::EventAlreadyResolved);
+        let provisional = ev.dispute_status == DISPUTE_OPEN || now < ev.dispute_deadline || revote_available(ev, now);
+        require!(provisional, PredictError::EventAlreadyResolved);
+    }
+
+    if ev.dispute_status == DISPUTE_OPEN {
+        let dispute = ctx.accounts.dispute.as_ref().ok_or(PredictError::DisputeAccountRequired)?;
+        let challenger = ctx.accounts.challenger.as_ref().ok_or(PredictError::DisputeAccountRequired)?;
+        require_keys_eq!(dispute.challenger, challenger.key(), PredictError::Unauthorized);
+        // bond and rent back to the challenger
+        dispute.close(challenger.to_account_info())?;
+    }
+    ev.dispute_status = DISPUTE_NONE;
+    ev.dispute_deadline = 0;
+
+    let refund = ev
+        .pending_creator_commission
+        .checked_add(ev.pending_house_commission)
+        .ok_or(PredictError::MathOverflow)?;
+    return_commission_to_holders(
+        ev,
+        refund,
+        &ctx.accounts.commission_vault.to_account_info(),
+        &ctx.accounts.collateral_vault.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        ctx.bumps.commission_vault,
+    )?;
+    ev.pending_creator_commission = 0;
+    ev.pending_house_commission = 0;
+
+    ev.resolved = true;
+    ev.resolved_at = now;
+    ev.winning_option = 0;
+    ev.winning_percent_bps = 0;
+    ev.result_status = RESULT_VOIDED;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct VoidEvent<'info> {
+    // config.void_authority (the admin while unset), checked in the handler
+    pub authority: Signer<'info>,
+
+    #[account(seeds = [SEED_PROTOCOL_CONFIG], bump = config.bump)]
+    pub config: Box<Account<'info, ProtocolConfig>>,
+
+    #[account(mut)]
+    pub event: Account<'info, Event>,
+
+    // required while a dispute is open; closed to the challenger with the bond
+    #[account(mut, seeds = [SEED_DISPUTE, event.key().as_ref()], bump = dispute.bump)]
+    pub dispute: Option<Account<'info, Dispute>>,
+
+    /// CHECK: challenger wallet recorded on the dispute
+    #[account(mut)]
+    pub challenger: Option<AccountInfo<'info>>,
+
+    /// CHECK: Commission vault PDA
+    #[account(mut, seeds = [SEED_COMMISSION_VAULT, event.key().as_ref()], bump)]
+    pub commission_vault: AccountInfo<'info>,
+
+    /// CHECK: Collateral vault PDA
+    #[account(mut, seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
+    pub collateral_vault: AccountInfo<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -2,12 +2,16 @@ use anchor_lang::prelude::*;
 use anchor_lang::prelude::pubkey;
 use anchor_lang::solana_program::{ instruction::{AccountMeta, Instruction}, program::invoke_signed, program_pack::Pack, system_instruction};
 
-use anchor_spl::token::{self, Burn, InitializeMint, Mint, MintTo, Token, TokenAccount};
+use anchor_spl::token_interface::{self, Burn, InitializeMint, Mint, Token2022, TokenAccount, TokenInterface, TransferChecked};
 use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022::{self, extension::{metadata_pointer, ExtensionType}};
 
 // for metadata
 pub const METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
 
+// for single-side buys (sell the unwanted side into a TRUE/FALSE pool)
+pub const RAYDIUM_CPMM_PROGRAM_ID: Pubkey = pubkey!("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+
 // Import the truth_network program
 declare_program!(truth_network);
 use truth_network::{ 
@@ -21,8 +25,14 @@ use truth_network::{
 // Import the Truth-Network program
 use truth_network::accounts::Question;
 
-pub const HOUSE_WALLET: Pubkey = pubkey!("CQaZgx5jqQrz7c8shCG3vJLiiPGPrawSGhvkgXtGyxL");
 
+mod math;
+pub mod instructions;
+pub mod state;
+
+use math::*;
+pub use instructions::*;
+pub use state::*;
 
 declare_id!("Fhud5X7RHZT6159Mr964dhZA6SUDj5Dt8Zk54K4x6Twf");
 
@@ -35,28 +45,167 @@ pub const SEED_TRUE_MINT: &[u8] = b"true_mint";
 pub const SEED_FALSE_MINT: &[u8] = b"false_mint";
 pub const SEED_MINT_AUTH: &[u8] = b"mint_authority";
 pub const SEED_COLLATERAL_VAULT: &[u8] = b"collateral_vault";
+pub const SEED_COMMISSION_VAULT: &[u8] = b"commission_vault";
+pub const SEED_LMSR_MARKET: &[u8] = b"lmsr_market";
+pub const SEED_POOL: &[u8] = b"pool";
+pub const SEED_POOL_LP_MINT: &[u8] = b"pool_lp_mint";
+pub const SEED_POOL_TOKEN_VAULT: &[u8] = b"pool_token_vault";
+pub const SEED_POOL_SOL_VAULT: &[u8] = b"pool_sol_vault";
+pub const SEED_ORDER_BOOK: &[u8] = b"order_book";
+pub const SEED_ORDER_ESCROW: &[u8] = b"order_escrow";
+pub const SEED_ORDER_TOKEN_ESCROW: &[u8] = b"order_token_escrow";
+pub const SEED_OPEN_ORDERS: &[u8] = b"open_orders";
+pub const SEED_CATEGORICAL_EVENT: &[u8] = b"categorical_event";
+pub const SEED_CATEGORICAL_MINT: &[u8] = b"categorical_mint";
+pub const SEED_SCALAR_MARKET: &[u8] = b"scalar_market";
+pub const SEED_CONDITIONAL_MARKET: &[u8] = b"conditional_market";
+pub const SEED_CONDITIONAL_VAULT: &[u8] = b"conditional_vault";
+pub const SEED_TOKEN_COLLATERAL_VAULT: &[u8] = b"token_collateral_vault";
+pub const SEED_TRUTH_TOKEN_VAULT: &[u8] = b"truth_token_vault";
+pub const SEED_COMMISSION_TOKEN_VAULT: &[u8] = b"commission_token_vault";
+pub const SEED_PROTOCOL_CONFIG: &[u8] = b"protocol_config";
+pub const SEED_REFERRAL: &[u8] = b"referral";
+pub const SEED_REFERRAL_VAULT: &[u8] = b"referral_vault";
+pub const SEED_USER_STATS: &[u8] = b"user_stats";
+pub const SEED_USER_POSITION: &[u8] = b"user_position";
+pub const SEED_PENDING_CHANGE: &[u8] = b"pending_change";
+pub const SEED_HOUSE_TREASURY: &[u8] = b"house_treasury";
+pub const SEED_DISPUTE: &[u8] = b"dispute";
 
-pub const DEFAULT_CONSENSUS_THRESHOLD_BPS: u16 = 8000; // 80.00%
 pub const BPS_DENOM: u64 = 10_000;
 
+// ProtocolConfig bounds (house wallet, fees, threshold, sweep delay and URIs live in the config PDA)
+pub const MIN_CONSENSUS_THRESHOLD_BPS: u16 = 5_001; // strict majority
+pub const MAX_BUY_FEE_BPS: u16 = 500; // 5.00%
+pub const MAX_REDEEM_FEE_BPS: u16 = 500; // 5.00%
+#[cfg(not(feature = "test-delays"))]
+pub const MIN_UNCLAIMED_SWEEP_DELAY_SECS: i64 = 24 * 60 * 60; // 1 day
+// localnet test builds (`--features test-delays`) may sweep within seconds
+#[cfg(feature = "test-delays")]
+pub const MIN_UNCLAIMED_SWEEP_DELAY_SECS: i64 = 1;
+pub const MAX_UNCLAIMED_SWEEP_DELAY_SECS: i64 = 365 * 24 * 60 * 60; // 1 year
+// emergency_exit opens this long after reveal_end_time if the event is still unresolved
+#[cfg(not(feature = "test-delays"))]
+pub const MIN_EMERGENCY_EXIT_DELAY_SECS: i64 = 30 * 24 * 60 * 60; // 30 days
+#[cfg(feature = "test-delays")]
+pub const MIN_EMERGENCY_EXIT_DELAY_SECS: i64 = 1;
+pub const MAX_EMERGENCY_EXIT_DELAY_SECS: i64 = 365 * 24 * 60 * 60; // 1 year
+// optional dispute window after fetch_and_store_winner, 0 = results are final at once
+pub const MAX_DISPUTE_WINDOW_SECS: i64 = 7 * 24 * 60 * 60; // 7 days
+// an open dispute the admin has not decided within this time can be settled by anyone
+pub const DISPUTE_RESOLUTION_TIMEOUT_SECS: i64 = 14 * 24 * 60 * 60; // 14 days
+// tie / below-threshold results can be sent to a fresh Truth question this many times
+// before the 50/50 split applies; the creator has REVOTE_REQUEST_WINDOW_SECS to ask
+pub const MAX_REVOTE_ROUNDS: usize = 3;
+pub const REVOTE_REQUEST_WINDOW_SECS: i64 = 24 * 60 * 60; // 1 day
+pub const MAX_TOKEN_URI_LEN: usize = 200;
+
+// Event account layout. Events created before the config snapshot fields were added are
+// LEGACY_EVENT_SPACE long and must go through migrate_event before any other instruction
+pub const EVENT_LAYOUT_VERSION: u8 = 1;
+pub const LEGACY_EVENT_SPACE: usize = 8 + (296 + 1 + 154);
+pub const EVENT_SPACE: usize = 8 + (296 + 1 + 154 + 1 + 32 + 1 + 32 + 1 + 32 + 2 + 2 + 8 + 2 * 3 + 1 + 8 + 2 + 1 + 8 + 2 + 2 + 8 + 1 + 8 + 8 + 32 + 1
+    + (32 + 2) * MAX_CO_CREATORS + 1 + 1 + 2 + 8 + 8 + PAUSE_STATE_SPACE + 8 + 1 + 8 + 8 + 8 + 1 + 1 + 2 + 1
+    + (32 + 8 + 8 + 1 + 8) * MAX_REVOTE_ROUNDS + 1 + 1);
+
+// Config changes are queued in the PendingChange PDA and can only be executed after this delay
+#[cfg(not(feature = "test-delays"))]
+pub const CONFIG_TIMELOCK_SECS: i64 = 2 * 24 * 60 * 60; // 2 days
+#[cfg(feature = "test-delays")]
+pub const CONFIG_TIMELOCK_SECS: i64 = 5;
+pub const CONFIG_PARAMS_SPACE: usize = 32 + 32 + 2 * 3 + 8 + 2 * (4 + MAX_TOKEN_URI_LEN) + 2 * 5 + 2
+    + (8 + 2) * MAX_FEE_TIERS + 1 + (32 + 2) * MAX_TREASURY_BENEFICIARIES + 8 + 8 + 8 + 1 + 2 + 32;
+
+// Emergency pauses block instruction classes; every pause expires after at most MAX_PAUSE_SECS
+pub const PAUSE_BUY: u8 = 1 << 0;
+pub const PAUSE_PAIR_REDEEM: u8 = 1 << 1;
+pub const PAUSE_FINAL_REDEEM: u8 = 1 << 2;
+pub const PAUSE_SWEEP: u8 = 1 << 3;
+pub const PAUSE_RESOLVE: u8 = 1 << 4;
+pub const PAUSE_ALL: u8 = PAUSE_BUY | PAUSE_PAIR_REDEEM | PAUSE_FINAL_REDEEM | PAUSE_SWEEP | PAUSE_RESOLVE;
+pub const MAX_PAUSE_SECS: i64 = 7 * 24 * 60 * 60; // 7 days
+pub const MAX_PAUSE_REASON_LEN: usize = 64;
+pub const PAUSE_STATE_SPACE: usize = 1 + 32 + (4 + MAX_PAUSE_REASON_LEN) + 8 + 8;
+pub const PAUSE_COOLDOWN_SECS: i64 = 7 * 24 * 60 * 60; // between the end of one pause and the next
+
+// HouseTreasury payouts, e.g. operations, insurance and stakers; weights sum to 100%
+pub const MAX_TREASURY_BENEFICIARIES: usize = 4;
+
+// Buy fee split, in bps of the fee. House takes whatever truth and creator don't.
+pub const DEFAULT_TRUTH_SHARE_BPS: u16 = 3_333;
+pub const DEFAULT_CREATOR_SHARE_BPS: u16 = 3_333;
+
+// Optional buy fee curves: the fee ramps from buy_fee_bps up to fee_curve_max_bps
+// between fee_curve_start and bet_end_time.
+pub const FEE_CURVE_FLAT: u8 = 0;
+pub const FEE_CURVE_LINEAR: u8 = 1;
+pub const FEE_CURVE_STEP: u8 = 2;
+pub const FEE_CURVE_MAX_STEPS: u8 = 24;
+
+// Volume tiers: buy fee discount by rolling 30-day volume (lamports)
+pub const MAX_FEE_TIERS: usize = 4;
+pub const VOLUME_WINDOW_SECS: i64 = 30 * 24 * 60 * 60;
+
+// Co-creators sharing the creator commission, besides the payout address
+pub const MAX_CO_CREATORS: usize = 5;
+
+// Creator commission vesting during the betting window
+pub const VESTING_NONE: u8 = 0;      // claimable after bet_end_time (or resolution with clawback)
+pub const VESTING_LINEAR: u8 = 1;    // vests linearly from created_at to bet_end_time
+pub const VESTING_TRANCHES: u8 = 2;  // vests in equal tranches over the same window
+pub const VESTING_MAX_TRANCHES: u8 = 12;
+pub const MIN_VESTING_HOLDBACK_BPS: u16 = 2000;  // always locked until the event settles
+
 pub const RESULT_PENDING: u8 = 0;
 pub const RESULT_RESOLVED_WINNER: u8 = 1;
 pub const RESULT_FINALIZED_NO_VOTES: u8 = 2;
 pub const RESULT_FINALIZED_TIE: u8 = 3;
 pub const RESULT_FINALIZED_BELOW_THRESHOLD: u8 = 4;
+pub const RESULT_RESOLVED_SCALAR: u8 = 5;
+pub const RESULT_EMERGENCY_EXIT: u8 = 6;          // never resolved, holders exit pro rata
+pub const RESULT_VOIDED: u8 = 7;                  // invalidated by governance, full refunds
+
+pub const DISPUTE_NONE: u8 = 0;
+pub const DISPUTE_OPEN: u8 = 1;         // challenged, waiting for the admin decision
+pub const DISPUTE_SETTLED: u8 = 2;
+
+// Event kinds. Scalar events use true_mint as LONG and false_mint as SHORT.
+pub const EVENT_KIND_BINARY: u8 = 0;
+pub const EVENT_KIND_SCALAR: u8 = 1;
+// Conditional events are collateralised by a parent event's TRUE or FALSE token
+pub const EVENT_KIND_CONDITIONAL: u8 = 2;
+
+// Categorical events: one Truth question (yes/no) per outcome
+pub const MIN_CATEGORICAL_OUTCOMES: u8 = 3;
+pub const MAX_CATEGORICAL_OUTCOMES: u8 = 16;
+
+pub const OUTCOME_PENDING: u8 = 0;
+pub const OUTCOME_YES: u8 = 1;
+pub const OUTCOME_NO: u8 = 2;
+pub const OUTCOME_NO_VOTES: u8 = 3;
 
-pub const REDEEM_FEE_BPS: u64 = 0; // no more fee on redeem
 pub const VAULT_DUST_TOLERANCE_LAMPORTS: u64 = 10;
 
-pub const UNCLAIMED_SWEEP_DELAY_SECS: i64 = 30 * 24 * 60 * 60; // 30 days for test 5 * 60;
+// LMSR liquidity parameter bounds (b, in lamports). Creator subsidy = b * ln(2).
+pub const LMSR_MIN_B: u64 = 100_000_000; // 0.1 SOL
+pub const LMSR_MAX_B: u64 = 10_000_000_000_000; // 10,000 SOL
+
+// Constant-product pools (position token / SOL)
+pub const POOL_MAX_FEE_BPS: u16 = 1000; // 10.00%
+pub const POOL_MIN_INITIAL_LP: u64 = 1_000_000;
 
-// uri token metadata
-pub const TRUE_TOKEN_URI: &str = "https://black-generous-emu-9.mypinata.cloud/ipfs/bafkreihwrwcaodgkzekrb5zjbeh43mnrnryjvtpqaclccxzxv3jnrl2nnq";
-pub const FALSE_TOKEN_URI: &str = "https://black-generous-emu-9.mypinata.cloud/ipfs/bafkreieywt5nxjcrcibmdedwssrpkygln57h3ddq656azeuexp7dbxl2fa";
+// Order book: prices are lamports per whole token (9 decimals, same as the position mints)
+pub const LAMPORTS_PER_TOKEN: u64 = 1_000_000_000;
+pub const ORDER_BOOK_MAX_ORDERS: usize = 64;
+// each (token side, bid/ask) queue gets a quarter of the book; when a queue is full a
+// better-priced order evicts its worst order, whose escrow is refunded through the fill queue
+pub const ORDER_BOOK_MAX_PER_QUEUE: usize = ORDER_BOOK_MAX_ORDERS / 4;
+pub const ORDER_BOOK_MAX_FILLS: usize = 32;
+pub const ORDER_MIN_NOTIONAL_LAMPORTS: u64 = 10_000_000; // 0.01 SOL
 
 #[inline(always)]
 fn vault_keep_lamports() -> Result<u64> {
-    Ok(Rent::get()?.minimum_balance(0) as u64)
+    Ok(Rent::get()?.minimum_balance(0))
 }
 
 #[inline(never)]
@@ -66,7 +215,7 @@ fn create_system_pda_0space<'info>(
     system_program: &AccountInfo<'info>,
     signer_seeds: &[&[u8]],
 ) -> Result<()> {
-    let required_lamports = Rent::get()?.minimum_balance(0) as u64;
+    let required_lamports = Rent::get()?.minimum_balance(0);
     let current = pda.lamports();
 
     // Already funded enough: nothing to do.
@@ -111,6 +260,7 @@ fn create_system_pda_0space<'info>(
 
 
 #[inline(never)]
+#[allow(clippy::too_many_arguments)]
 fn create_and_init_spl_mint_pda<'info>(
     payer: &AccountInfo<'info>,
     mint: &AccountInfo<'info>,
@@ -149,19 +299,11 @@ fn create_and_init_spl_mint_pda<'info>(
         },
     );
 
-    token::initialize_mint(cpi, decimals, mint_authority, None)?;
+    token_interface::initialize_mint(cpi, decimals, mint_authority, None)?;
 
     Ok(())
 }
 
-#[inline(always)]
-fn payout_after_fee(amount: u64) -> Result<u64> {
-    let fee = amount
-        .checked_mul(REDEEM_FEE_BPS).ok_or_else(|| error!(PredictError::MathOverflow))?
-        .checked_div(BPS_DENOM).ok_or_else(|| error!(PredictError::MathOverflow))?;
-    amount.checked_sub(fee).ok_or_else(|| error!(PredictError::MathOverflow))
-}
-
 // metadata helper - title prefix
 fn short_prefix(title: &str) -> String {
     title
@@ -273,18 +415,12 @@ pub enum Category {
 // ============================================================
 // Create event, create mint, buy token and mint token helpers
 // ============================================================
-fn compute_fee_splits(lamports: u64) -> Result<(u64, u64, u64, u64, u64)> {
-    let fee = lamports / 100; // 1%
-    let net = lamports.checked_sub(fee).ok_or(PredictError::MathOverflow)?;
-
-    let third = fee / 3;
-    let truth_cut = third;
-    let creator_cut = third;
-    let house_cut = fee
-        .checked_sub(truth_cut.checked_add(creator_cut).ok_or(PredictError::MathOverflow)?)
-        .ok_or(PredictError::MathOverflow)?;
 
-    Ok((fee, truth_cut, creator_cut, house_cut, net))
+// fee schedule stored on the event at creation, evaluated at `now`, less any volume tier discount
+fn event_fee_splits(ev: &Event, lamports: u64, now: i64, discount_bps: u16) -> Result<(u64, u64, u64, u64, u64)> {
+    let fee_bps = current_buy_fee_bps(ev, now)? as u64;
+    let fee_bps = fee_bps.saturating_sub(mul_div_floor(fee_bps, discount_bps as u64, BPS_DENOM)?);
+    compute_fee_splits(lamports, fee_bps as u16, ev.truth_share_bps, ev.creator_share_bps)
 }
 
 fn transfer_in<'info>(
@@ -375,10 +511,42 @@ fn transfer_from_collateral_vault<'info>(
 }
 
 
+#[inline(never)]
+fn transfer_from_commission_vault<'info>(
+    commission_vault: &AccountInfo<'info>,
+    dest: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    event_key: &Pubkey,
+    vault_bump: u8,
+    lamports: u64,
+) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
+
+    let seeds: [&[u8]; 3] = [
+        SEED_COMMISSION_VAULT,
+        event_key.as_ref(),
+        &[vault_bump],
+    ];
+
+    invoke_signed(
+        &system_instruction::transfer(commission_vault.key, dest.key, lamports),
+        &[
+            commission_vault.clone(),
+            dest.clone(),
+            system_program.clone(),
+        ],
+        &[&seeds],
+    )?;
+
+    Ok(())
+}
+
 #[inline(never)]
 fn sweep_house_commission<'info>(
     ev: &mut Account<'info, Event>,
-    collateral_vault: &AccountInfo<'info>,
+    commission_vault: &AccountInfo<'info>,
     house_treasury: &AccountInfo<'info>,
     system_program: &AccountInfo<'info>,
     vault_bump: u8,
@@ -390,7 +558,7 @@ fn sweep_house_commission<'info>(
 
     // rent safety
     let keep = vault_keep_lamports()?;
-    let vault_lamports = collateral_vault.lamports();
+    let vault_lamports = commission_vault.lamports();
     require!(
         vault_lamports >= keep.saturating_add(amount),
         PredictError::VaultInsufficientFunds
@@ -398,60 +566,166 @@ fn sweep_house_commission<'info>(
 
     let event_key = ev.key();
 
-    transfer_from_collateral_vault(
-        collateral_vault,
+    transfer_from_commission_vault(
+        commission_vault,
         house_treasury,
         system_program,
         &event_key,
         vault_bump,
         amount,
     )?;
+    record_treasury_inflow(house_treasury, &event_key, amount)?;
 
     ev.pending_house_commission = 0;
+    ev.house_lamports_paid = ev.house_lamports_paid.checked_add(amount).ok_or(PredictError::MathOverflow)?;
     Ok(())
 }
 
 
-fn mint_net_positions<'info>(
-    accs: &BuyPositionsWithFee<'info>,
-    net: u64,
-    mint_auth_bump: u8,
-) -> Result<()> {
-    let event_key = accs.event.key();
-    let seeds = &[
-        SEED_MINT_AUTH,
-        event_key.as_ref(),
-        &[mint_auth_bump],
-    ];
-    let signer = &[&seeds[..]];
+// Accounts shared by the SOL buy paths (pair buy and single-side buy)
+struct PairBuyAccounts<'a, 'info> {
+    user: &'a Signer<'info>,
+    event: &'a mut Account<'info, Event>,
+    config: &'a ProtocolConfig,
+    collateral_vault: &'a SystemAccount<'info>,
+    commission_vault: &'a SystemAccount<'info>,
+    mint_authority: AccountInfo<'info>,
+    true_mint: AccountInfo<'info>,
+    false_mint: AccountInfo<'info>,
+    user_true_ata: AccountInfo<'info>,
+    user_false_ata: AccountInfo<'info>,
+    truth_network_question: &'a Account<'info, Question>,
+    truth_network_vault: AccountInfo<'info>,
+    user_stats: &'a mut UserStats,
+    user_position: &'a mut UserPosition,
+    referral: Option<&'a mut Account<'info, ReferralAccount>>,
+    referral_vault: Option<&'a SystemAccount<'info>>,
+    token_program: AccountInfo<'info>,
+    system_program: &'a Program<'info, System>,
+}
 
-    token::mint_to(
-        CpiContext::new(
-            accs.token_program.to_account_info(),
-            token::MintTo {
-                mint: accs.true_mint.to_account_info(),
-                to: accs.user_true_ata.to_account_info(),
-                authority: accs.mint_authority.to_account_info(),
-            },
+struct PairBuyBumps {
+    collateral_vault: u8,
+    mint_authority: u8,
+    user_stats: u8,
+    user_position: u8,
+}
+
+// Fee split, deposit, truth / referral cuts, TRUE + FALSE mint and stats for a SOL buy.
+// Returns the net amount minted on each side.
+#[inline(never)]
+fn buy_pair_core<'info>(a: PairBuyAccounts<'_, 'info>, bumps: PairBuyBumps, lamports: u64, now: i64) -> Result<u64> {
+    require_native_collateral(a.event)?;
+    require!(lamports > 0, PredictError::InvalidAmount);
+
+    // 1) verification
+    // Block buys after betting window ends
+    require!(now < a.event.bet_end_time, PredictError::BettingPeriodEnded);
+    // No one can buy positions after the event has been resolved
+    require!(!a.event.resolved, PredictError::EventResolved);
+    // Conditional events take parent tokens, not SOL
+    require!(a.event.kind != EVENT_KIND_CONDITIONAL, PredictError::InvalidEventKind);
+
+    // The Truth question account passed is the SAME one that this event was originally linked to
+    require_keys_eq!(
+        a.event.truth_question,
+        a.truth_network_question.key(),
+        PredictError::TruthQuestionMismatch
+    );
+
+    // verify truth vault matches the truth question
+    require_keys_eq!(
+        a.truth_network_vault.key(),
+        a.truth_network_question.vault_address,
+        PredictError::InvalidTruthVault
+    );
+
+    // 2) fee split, discounted by the buyer's 30-day volume tier
+    let discount_bps = fee_tier_discount_bps(a.config, rolling_volume_30d(a.user_stats, now));
+    let (_fee, truth_cut, creator_cut, house_cut, net) = event_fee_splits(a.event, lamports, now, discount_bps)?;
+
+    // referrer is paid out of the house cut
+    let referral_cut = match a.referral.as_ref() {
+        Some(referral) => {
+            require_keys_neq!(referral.referrer, a.user.key(), PredictError::SelfReferral);
+            mul_div_floor(house_cut, a.event.referral_share_bps as u64, BPS_DENOM)?
+        }
+        None => 0,
+    };
+    let house_cut = house_cut.checked_sub(referral_cut).ok_or(PredictError::MathOverflow)?;
+
+    // 3) referral cut straight to the referrer's vault, claimable later
+    let event_key = a.event.key();
+    if referral_cut > 0 {
+        let referral_vault = a.referral_vault.ok_or(PredictError::ReferralVaultRequired)?;
+        let referral = a.referral.ok_or(PredictError::ReferralVaultRequired)?;
+        let expected_vault = Pubkey::create_program_address(
+            &[SEED_REFERRAL_VAULT, referral.referrer.as_ref(), &[referral.vault_bump]],
+            &crate::ID,
         )
-        .with_signer(signer),
-        net,
+        .map_err(|_| error!(PredictError::InvalidVault))?;
+        require_keys_eq!(referral_vault.key(), expected_vault, PredictError::InvalidVault);
+
+        transfer_in(a.user, referral_vault, a.system_program, referral_cut)?;
+
+        referral.pending_lamports = referral.pending_lamports.checked_add(referral_cut).ok_or(PredictError::MathOverflow)?;
+        referral.total_earned_lamports = referral.total_earned_lamports.checked_add(referral_cut).ok_or(PredictError::MathOverflow)?;
+        a.event.total_referral_commission = a
+            .event
+            .total_referral_commission
+            .checked_add(referral_cut)
+            .ok_or(PredictError::MathOverflow)?;
+    }
+
+    // 4) transfer user -> commission vault (creator + house), the rest -> collateral vault
+    let commission = creator_cut.checked_add(house_cut).ok_or(PredictError::MathOverflow)?;
+    let deposited = lamports
+        .checked_sub(commission)
+        .and_then(|v| v.checked_sub(referral_cut))
+        .ok_or(PredictError::MathOverflow)?;
+    transfer_in(a.user, a.collateral_vault, a.system_program, deposited)?;
+    transfer_in(a.user, a.commission_vault, a.system_program, commission)?;
+
+    // 4b) move only truth cut to truth vault
+    transfer_from_vault_to_truth(
+        &a.collateral_vault.to_account_info(),
+        &a.truth_network_vault,
+        &a.system_program.to_account_info(),
+        &event_key,
+        bumps.collateral_vault,
+        truth_cut,
     )?;
 
-    token::mint_to(
-        CpiContext::new(
-            accs.token_program.to_account_info(),
-            token::MintTo {
-                mint: accs.false_mint.to_account_info(),
-                to: accs.user_false_ata.to_account_info(),
-                authority: accs.mint_authority.to_account_info(),
-            },
-        )
-        .with_signer(signer),
-        net,
+    // 5) mint net TRUE + FALSE
+    for (mint, ata) in [(&a.true_mint, &a.user_true_ata), (&a.false_mint, &a.user_false_ata)] {
+        mint_position_tokens(
+            &a.token_program,
+            mint,
+            ata,
+            &a.mint_authority,
+            &event_key,
+            bumps.mint_authority,
+            net,
+        )?;
+    }
+
+    // 6) accounting
+    apply_accounting(a.event, deposited, net, truth_cut, creator_cut, house_cut)?;
+
+    // 7) trading stats
+    record_buy(
+        a.user_stats,
+        a.user_position,
+        &a.user.key(),
+        &event_key,
+        [bumps.user_stats, bumps.user_position],
+        lamports,
+        lamports,
+        [net, net],
+        now,
     )?;
 
-    Ok(())
+    Ok(net)
 }
 
 fn apply_accounting(
@@ -481,6 +755,7 @@ fn apply_accounting(
         .total_issued_per_side
         .checked_add(net)
         .ok_or(PredictError::MathOverflow)?;
+    ev.has_buys = true;
 
     ev.total_truth_commission_sent = ev
         .total_truth_commission_sent
@@ -514,593 +789,1527 @@ fn no_outstanding_tokens(ev: &Event) -> bool {
         };
     }
 
-    // No-winner cases: allow delete only if both sides redeemed
+    // No-winner and voided cases: allow delete only if both sides redeemed
     ev.outstanding_true == 0 && ev.outstanding_false == 0
 }
 
 
+// ============================================================
+// LMSR market maker helpers
+// ============================================================
 
-// ======================================================
-// PROGRAM
-// ======================================================
-#[program]
-pub mod predictol_sc {
-    use super::*;
-
-    pub fn initialize_event_counter(ctx: Context<InitializeEventCounter>) -> Result<()> {
-        ctx.accounts.counter.creator = ctx.accounts.creator.key();
-        ctx.accounts.counter.count = 0;
-        Ok(())
+// Lamports left in the vault that belong to the creator once the maker's liability is paid.
+fn lmsr_creator_surplus<'info>(
+    ev: &Event,
+    market: Option<&Account<'info, LmsrMarket>>,
+) -> Result<u64> {
+    if !ev.has_lmsr {
+        return Ok(0);
     }
+    let market = market.ok_or(PredictError::LmsrMarketRequired)?;
+    require!(ev.resolved, PredictError::EventNotResolved);
 
-    pub fn create_event_core(
-        ctx: Context<CreateEventCore>,
-        title: String,
-        category: u8,
-        bet_end_time: i64,
-        commit_end_time: i64,
-        reveal_end_time: i64,
-        truth_question: Option<Pubkey>,
-    ) -> Result<()> {
-        let now = Clock::get()?.unix_timestamp;
+    Ok(market.reserve_lamports.saturating_sub(lmsr_liability(ev, market)?))
+}
 
-        require!((10..=150).contains(&title.len()), PredictError::InvalidTitleLength);
-        require!(bet_end_time > now, PredictError::InvalidBetEndTime);
-        require!(bet_end_time < commit_end_time, PredictError::InvalidTimeOrder);
-        require!(commit_end_time < reveal_end_time, PredictError::InvalidTimeOrder);
-        require!(category <= 3, PredictError::InvalidCategory);
-
-        let counter = &mut ctx.accounts.counter;
-        let event_id = counter.count;
-
-        let ev = &mut ctx.accounts.event;
-        ev.creator = ctx.accounts.creator.key();
-        ev.event_id = event_id;
-        ev.title = title;
-        ev.category = category;
-        ev.bet_end_time = bet_end_time;
-        ev.commit_end_time = commit_end_time;
-        ev.reveal_end_time = reveal_end_time;
-        ev.created_at = now;
-        ev.truth_question = truth_question.unwrap_or_default();
-        ev.total_collateral_lamports = 0;
-        ev.total_issued_per_side = 0;
-        ev.outstanding_true = 0;
-        ev.outstanding_false = 0;
-        ev.resolved = false;
-        ev.winning_option = 0;
-        ev.winning_percent_bps = 0;
-        ev.votes_option_1 = 0;
-        ev.votes_option_2 = 0;
-        ev.consensus_threshold_bps = DEFAULT_CONSENSUS_THRESHOLD_BPS;
-        ev.resolved_at = 0;
-        ev.result_status = RESULT_PENDING;
-        ev.total_truth_commission_sent = 0;
-        ev.pending_creator_commission = 0;
-        ev.pending_house_commission = 0;
-        ev.unclaimed_swept = false;
-        ev.swept_at = 0;
-
-        counter.count = counter.count.checked_add(1).ok_or(PredictError::MathOverflow)?;
-
-        Ok(())
-    }
+#[inline(never)]
+fn mint_position_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    event_key: &Pubkey,
+    mint_auth_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    let seeds = &[
+        SEED_MINT_AUTH,
+        event_key.as_ref(),
+        &[mint_auth_bump],
+    ];
+    let signer = &[&seeds[..]];
 
-    pub fn create_event_mints(ctx: Context<CreateEventMints>) -> Result<()> {
+    token_interface::mint_to(
+        CpiContext::new(
+            token_program.clone(),
+            token_interface::MintTo {
+                mint: mint.clone(),
+                to: to.clone(),
+                authority: mint_authority.clone(),
+            },
+        )
+        .with_signer(signer),
+        amount,
+    )
+}
 
-        let event_key = ctx.accounts.event.key();
-        let payer_ai = ctx.accounts.creator.to_account_info();
-        let system_ai = ctx.accounts.system_program.to_account_info();
-        let token_ai = ctx.accounts.token_program.to_account_info();
-        let rent_ai = ctx.accounts.rent.to_account_info();
-
-        // 1) collateral vault PDA (system-owned, 0 space)
-        let vault_bump = ctx.bumps.collateral_vault;
-        let vault_seeds: [&[u8]; 3] = [
-            SEED_COLLATERAL_VAULT,
-            event_key.as_ref(),
-            &[vault_bump],
-        ];
-        create_system_pda_0space(
-            &payer_ai,
-            &ctx.accounts.collateral_vault.to_account_info(),
-            &system_ai,
-            &vault_seeds,
-        )?;
+fn outstanding_for_side_mut(ev: &mut Event, side: u8) -> Result<&mut u64> {
+    match side {
+        1 => Ok(&mut ev.outstanding_true),
+        2 => Ok(&mut ev.outstanding_false),
+        _ => err!(PredictError::InvalidSide),
+    }
+}
 
-        // 2) TRUE mint PDA
-        let true_bump = ctx.bumps.true_mint;
-        let true_seeds: [&[u8]; 3] = [
-            SEED_TRUE_MINT,
-            event_key.as_ref(),
-            &[true_bump],
-        ];
-        create_and_init_spl_mint_pda(
-            &payer_ai,
-            &ctx.accounts.true_mint.to_account_info(),
-            &token_ai,
-            &system_ai,
-            &rent_ai,
-            &ctx.accounts.mint_authority.key(),
-            &true_seeds,
-            9,
-        )?;
+fn mint_for_side(ev: &Event, side: u8) -> Result<Pubkey> {
+    match side {
+        1 => Ok(ev.true_mint),
+        2 => Ok(ev.false_mint),
+        _ => err!(PredictError::InvalidSide),
+    }
+}
 
-        // 3) FALSE mint PDA
-        let false_bump = ctx.bumps.false_mint;
-        let false_seeds: [&[u8]; 3] = [
-            SEED_FALSE_MINT,
-            event_key.as_ref(),
-            &[false_bump],
-        ];
-        create_and_init_spl_mint_pda(
-            &payer_ai,
-            &ctx.accounts.false_mint.to_account_info(),
-            &token_ai,
-            &system_ai,
-            &rent_ai,
-            &ctx.accounts.mint_authority.key(),
-            &false_seeds,
-            9,
-        )?;
+// ============================================================
+// Constant-product pool helpers
+// ============================================================
 
-        // ---------- Create Metadata for TRUE ----------
-        let prefix = short_prefix(&ctx.accounts.event.title);
-        let true_name = format!("PS-{}-TRUE", prefix);
-        let false_name = format!("PS-{}-FALSE", prefix);
-
-        let true_symbol = "TRUE".to_string();
-        let false_symbol = "FALSE".to_string();
-
-        let true_uri = TRUE_TOKEN_URI.to_string();
-        let false_uri = FALSE_TOKEN_URI.to_string();
-
-        let true_metadata = ctx.accounts.true_metadata.key();
-        let false_metadata = ctx.accounts.false_metadata.key();
-
-
-        // signer seeds for mint authority
-        let auth_bump = ctx.bumps.mint_authority;
-        let auth_seeds = &[
-            SEED_MINT_AUTH,
-            event_key.as_ref(),
-            &[auth_bump],
-        ];
-        let signer = &[&auth_seeds[..]];
-
-        // Build metadata struct
-        let true_data = DataV2 {
-            name: true_name,
-            symbol: true_symbol,
-            uri: true_uri,
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        };
+// Pools and the order book trade only while betting is open and the event is not resolved
+fn require_trading_open(ev: &Event) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(now < ev.bet_end_time, PredictError::BettingPeriodEnded);
+    require!(!ev.resolved, PredictError::EventResolved);
+    Ok(())
+}
 
-        let false_data = DataV2 {
-            name: false_name,
-            symbol: false_symbol,
-            uri: false_uri,
-            seller_fee_basis_points: 0,
-            creators: None,
-            collection: None,
-            uses: None,
-        };
+#[inline(never)]
+fn transfer_from_pool_sol_vault<'info>(
+    sol_vault: &AccountInfo<'info>,
+    dest: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    event_key: &Pubkey,
+    side: u8,
+    sol_vault_bump: u8,
+    lamports: u64,
+) -> Result<()> {
+    if lamports == 0 {
+        return Ok(());
+    }
 
-        // TRUE metadata CPI
-        let ix = ix_create_metadata_account_v3(
-            true_metadata,
-            ctx.accounts.true_mint.key(),
-            ctx.accounts.mint_authority.key(),
-            ctx.accounts.creator.key(),
-            ctx.accounts.mint_authority.key(), // update authority = same PDA
-            true_data,
-            false, // not_mutable
-        )?;
+    let seeds: [&[u8]; 4] = [
+        SEED_POOL_SOL_VAULT,
+        event_key.as_ref(),
+        &[side],
+        &[sol_vault_bump],
+    ];
 
-        invoke_signed(
-            &ix,
-            &[
-                //ctx.accounts.metadata_program.to_account_info(),
-                ctx.accounts.true_metadata.to_account_info(),
-                ctx.accounts.true_mint.to_account_info(),
-                ctx.accounts.mint_authority.to_account_info(),
-                ctx.accounts.creator.to_account_info(),
-                ctx.accounts.mint_authority.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-                ctx.accounts.rent.to_account_info(),
-            ],
-            signer,
-        )?;
+    invoke_signed(
+        &system_instruction::transfer(sol_vault.key, dest.key, lamports),
+        &[
+            sol_vault.clone(),
+            dest.clone(),
+            system_program.clone(),
+        ],
+        &[&seeds],
+    )?;
 
+    Ok(())
+}
 
-        // FALSE metadata CPI
-        let ix = ix_create_metadata_account_v3(
-            false_metadata,
-            ctx.accounts.false_mint.key(),
-            ctx.accounts.mint_authority.key(),
-            ctx.accounts.creator.key(),
-            ctx.accounts.mint_authority.key(),
-            false_data,
-            false, // not_mutable
-        )?;
+#[inline(never)]
+#[allow(clippy::too_many_arguments)]
+fn transfer_from_pool_token_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    token_vault: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    dest: &AccountInfo<'info>,
+    pool: &AccountInfo<'info>,
+    event_key: &Pubkey,
+    side: u8,
+    pool_bump: u8,
+    decimals: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
 
-        invoke_signed(
-            &ix,
-            &[
-                //ctx.accounts.metadata_program.to_account_info(),
-                ctx.accounts.false_metadata.to_account_info(),
-                ctx.accounts.false_mint.to_account_info(),
-                ctx.accounts.mint_authority.to_account_info(),
-                ctx.accounts.creator.to_account_info(),
-                ctx.accounts.mint_authority.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-                ctx.accounts.rent.to_account_info(),
-            ],
-            signer,
-        )?;
+    let seeds: [&[u8]; 4] = [
+        SEED_POOL,
+        event_key.as_ref(),
+        &[side],
+        &[pool_bump],
+    ];
 
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: token_vault.clone(),
+                mint: mint.clone(),
+                to: dest.clone(),
+                authority: pool.clone(),
+            },
+        )
+        .with_signer(&[&seeds]),
+        amount,
+        decimals,
+    )
+}
 
-        // 4) Save addresses to event
-        let ev = &mut ctx.accounts.event;
-        ev.collateral_vault = ctx.accounts.collateral_vault.key();
-        ev.true_mint = ctx.accounts.true_mint.key();
-        ev.false_mint = ctx.accounts.false_mint.key();
+// ============================================================
+// Order book helpers
+// ============================================================
+// Best bid = highest price, best ask = lowest price; ties go to the older order.
+fn best_order_index(orders: &[Order], token_side: u8, is_bid: bool) -> Option<usize> {
+    let mut best: Option<usize> = None;
 
-        Ok(())
+    for (i, o) in orders.iter().enumerate() {
+        if o.token_side != token_side || o.is_bid != is_bid {
+            continue;
+        }
+        let better = match best {
+            None => true,
+            Some(b) => {
+                let cur = &orders[b];
+                if is_bid {
+                    o.price > cur.price || (o.price == cur.price && o.id < cur.id)
+                } else {
+                    o.price < cur.price || (o.price == cur.price && o.id < cur.id)
+                }
+            }
+        };
+        if better {
+            best = Some(i);
+        }
     }
 
-    pub fn buy_positions_with_fee(ctx: Context<BuyPositionsWithFee>, lamports: u64) -> Result<()> {
-        require!(lamports > 0, PredictError::InvalidAmount);
-        let now = Clock::get()?.unix_timestamp;
+    best
+}
 
-        // 1) verification
-        // Block buys after betting window ends
-        require!(
-            now < ctx.accounts.event.bet_end_time,
-            PredictError::BettingPeriodEnded
-        );
-        // No one can buy positions after the event has been resolved
-        require!(
-            !ctx.accounts.event.resolved,
-            PredictError::EventResolved
-        );
+// Worst bid = lowest price, worst ask = highest price; ties go to the newer order.
+fn worst_order_index(orders: &[Order], token_side: u8, is_bid: bool) -> Option<usize> {
+    let mut worst: Option<usize> = None;
 
-        // The Truth question account passed is the SAME one that this event was originally linked to
-        require_keys_eq!(
-            ctx.accounts.event.truth_question,
-            ctx.accounts.truth_network_question.key(),
-            PredictError::TruthQuestionMismatch
-        );
+    for (i, o) in orders.iter().enumerate() {
+        if o.token_side != token_side || o.is_bid != is_bid {
+            continue;
+        }
+        let worse = match worst {
+            None => true,
+            Some(w) => {
+                let cur = &orders[w];
+                if is_bid {
+                    o.price < cur.price || (o.price == cur.price && o.id > cur.id)
+                } else {
+                    o.price > cur.price || (o.price == cur.price && o.id > cur.id)
+                }
+            }
+        };
+        if worse {
+            worst = Some(i);
+        }
+    }
 
-        // verify truth vault matches the truth question
-        require_keys_eq!(
-            ctx.accounts.truth_network_vault.key(),
-            ctx.accounts.truth_network_question.vault_address,
-            PredictError::InvalidTruthVault
-        );
+    worst
+}
 
-        // 2) fee split
-        let (fee, truth_cut, creator_cut, house_cut, net) = compute_fee_splits(lamports)?;
-
-        // 3) transfer user -> collateral vault (full lamports)
-        transfer_in(&ctx.accounts.user, &ctx.accounts.collateral_vault, &ctx.accounts.system_program, lamports)?;
-
-        // 4) move only truth cut to truth vault
-        let vault_bump = ctx.bumps.collateral_vault;
-        transfer_from_vault_to_truth(
-            &ctx.accounts.collateral_vault.to_account_info(),
-            &ctx.accounts.truth_network_vault.to_account_info(),
-            &ctx.accounts.system_program.to_account_info(),
-            &ctx.accounts.event.key(),
-            vault_bump,
-            truth_cut,
-        )?;
+fn credit_open_orders_tokens(oo: &mut OpenOrders, token_side: u8, amount: u64) -> Result<()> {
+    let free = match token_side {
+        1 => &mut oo.free_true,
+        2 => &mut oo.free_false,
+        _ => return err!(PredictError::InvalidSide),
+    };
+    *free = free.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
 
-        // 5) mint net TRUE + FALSE
-        let mint_auth_bump = ctx.bumps.mint_authority;
-        mint_net_positions(&ctx.accounts, net, mint_auth_bump)?;
+#[inline(never)]
+#[allow(clippy::too_many_arguments)]
+fn transfer_from_order_token_escrow<'info>(
+    token_program: &AccountInfo<'info>,
+    escrow: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    dest: &AccountInfo<'info>,
+    order_book: &AccountInfo<'info>,
+    event_key: &Pubkey,
+    book_bump: u8,
+    decimals: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
 
+    let seeds: [&[u8]; 3] = [
+        SEED_ORDER_BOOK,
+        event_key.as_ref(),
+        &[book_bump],
+    ];
 
-        // 6) accounting
-        apply_accounting(&mut ctx.accounts.event, lamports, net, truth_cut, creator_cut, house_cut)?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: escrow.clone(),
+                mint: mint.clone(),
+                to: dest.clone(),
+                authority: order_book.clone(),
+            },
+        )
+        .with_signer(&[&seeds]),
+        amount,
+        decimals,
+    )
+}
 
-        Ok(())
-    }
+// ============================================================
+// Raydium CPMM helpers (single-side buy)
+// ============================================================
+const IX_RAYDIUM_SWAP_BASE_INPUT: [u8; 8] = [143, 190, 90, 218, 196, 30, 51, 222];
+
+// Raydium CPMM PoolState offsets (after the 8 byte discriminator)
+const RAYDIUM_POOL_AMM_CONFIG_OFFSET: usize = 8;
+const RAYDIUM_POOL_TOKEN_0_VAULT_OFFSET: usize = 72;
+const RAYDIUM_POOL_TOKEN_1_VAULT_OFFSET: usize = 104;
+const RAYDIUM_POOL_TOKEN_0_MINT_OFFSET: usize = 168;
+const RAYDIUM_POOL_TOKEN_1_MINT_OFFSET: usize = 200;
+const RAYDIUM_POOL_OBSERVATION_OFFSET: usize = 296;
+
+fn read_pubkey_at(data: &[u8], offset: usize) -> Result<Pubkey> {
+    let bytes: [u8; 32] = data
+        .get(offset..offset + 32)
+        .ok_or(PredictError::InvalidRaydiumPool)?
+        .try_into()
+        .map_err(|_| error!(PredictError::InvalidRaydiumPool))?;
+    Ok(Pubkey::new_from_array(bytes))
+}
 
+// Pool must be a Raydium CPMM pool trading exactly this event's TRUE against FALSE.
+fn check_raydium_pool_mints(pool_state: &AccountInfo, ev: &Event) -> Result<()> {
+    require_keys_eq!(*pool_state.owner, RAYDIUM_CPMM_PROGRAM_ID, PredictError::InvalidRaydiumPool);
 
+    let data = pool_state.try_borrow_data()?;
+    let mint_0 = read_pubkey_at(&data, RAYDIUM_POOL_TOKEN_0_MINT_OFFSET)?;
+    let mint_1 = read_pubkey_at(&data, RAYDIUM_POOL_TOKEN_1_MINT_OFFSET)?;
 
-    pub fn redeem_pair_while_active(ctx: Context<RedeemPairWhileActive>, amount: u64) -> Result<()> {
-        require!(amount > 0, PredictError::InvalidAmount);
+    require!(
+        (mint_0 == ev.true_mint && mint_1 == ev.false_mint)
+            || (mint_0 == ev.false_mint && mint_1 == ev.true_mint),
+        PredictError::InvalidRaydiumPool
+    );
 
-        // Must be during betting period ("pair redeem")
-        let now = Clock::get()?.unix_timestamp;
-        require!(now < ctx.accounts.event.bet_end_time, PredictError::BettingPeriodEnded);
+    Ok(())
+}
 
-        // the event should not be resolved
-        require!(!ctx.accounts.event.resolved, PredictError::EventAlreadyResolved);
+fn ix_raydium_swap_base_input(
+    accounts: &SingleSideSwapAccounts,
+    amount_in: u64,
+    minimum_amount_out: u64,
+) -> Instruction {
+    let mut data = IX_RAYDIUM_SWAP_BASE_INPUT.to_vec();
+    data.extend_from_slice(&amount_in.to_le_bytes());
+    data.extend_from_slice(&minimum_amount_out.to_le_bytes());
+
+    Instruction {
+        program_id: RAYDIUM_CPMM_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(accounts.payer, true),
+            AccountMeta::new_readonly(accounts.authority, false),
+            AccountMeta::new_readonly(accounts.amm_config, false),
+            AccountMeta::new(accounts.pool_state, false),
+            AccountMeta::new(accounts.input_token_account, false),
+            AccountMeta::new(accounts.output_token_account, false),
+            AccountMeta::new(accounts.input_vault, false),
+            AccountMeta::new(accounts.output_vault, false),
+            AccountMeta::new_readonly(accounts.token_program, false),
+            AccountMeta::new_readonly(accounts.token_program, false),
+            AccountMeta::new_readonly(accounts.input_mint, false),
+            AccountMeta::new_readonly(accounts.output_mint, false),
+            AccountMeta::new(accounts.observation_state, false),
+        ],
+        data,
+    }
+}
 
-        // User wallet must have enough TRUE and FALSE to burn
-        require!(ctx.accounts.user_true_ata.amount >= amount, PredictError::InsufficientTrueBalance);
-        require!(ctx.accounts.user_false_ata.amount >= amount, PredictError::InsufficientFalseBalance);
+struct SingleSideSwapAccounts {
+    payer: Pubkey,
+    authority: Pubkey,
+    amm_config: Pubkey,
+    pool_state: Pubkey,
+    input_token_account: Pubkey,
+    output_token_account: Pubkey,
+    input_vault: Pubkey,
+    output_vault: Pubkey,
+    token_program: Pubkey,
+    input_mint: Pubkey,
+    output_mint: Pubkey,
+    observation_state: Pubkey,
+}
 
-        // Burn TRUE tokens
-        token::burn(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.true_mint.to_account_info(),
-                    from: ctx.accounts.user_true_ata.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
-                },
-            ),
-            amount,
-        )?;
+// Sells `amount_in` of the unwanted side into the designated pool and
+// returns how many tokens of the wanted side the user received.
+#[inline(never)]
+fn sell_unwanted_side<'info>(accs: &mut BuySingleSide<'info>, side: u8, amount_in: u64, minimum_amount_out: u64) -> Result<u64> {
+    // the pair minted earlier in this instruction is not in the deserialized balance yet
+    match side {
+        1 => accs.user_true_ata.reload()?,
+        2 => accs.user_false_ata.reload()?,
+        _ => return err!(PredictError::InvalidSide),
+    }
 
-        // Burn FALSE tokens
-        token::burn(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
+    let (input_ata, output_ata, input_mint, output_mint) = match side {
+        1 => (&accs.user_false_ata, &accs.user_true_ata, &accs.false_mint, &accs.true_mint),
+        2 => (&accs.user_true_ata, &accs.user_false_ata, &accs.true_mint, &accs.false_mint),
+        _ => return err!(PredictError::InvalidSide),
+    };
+
+    // pool accounts must match the pool state
+    {
+        let data = accs.pool_state.try_borrow_data()?;
+        require_keys_eq!(
+            read_pubkey_at(&data, RAYDIUM_POOL_AMM_CONFIG_OFFSET)?,
+            accs.amm_config.key(),
+            PredictError::InvalidRaydiumPool
+        );
+        require_keys_eq!(
+            read_pubkey_at(&data, RAYDIUM_POOL_OBSERVATION_OFFSET)?,
+            accs.observation_state.key(),
+            PredictError::InvalidRaydiumPool
+        );
+
+        let mint_0 = read_pubkey_at(&data, RAYDIUM_POOL_TOKEN_0_MINT_OFFSET)?;
+        let vault_0 = read_pubkey_at(&data, RAYDIUM_POOL_TOKEN_0_VAULT_OFFSET)?;
+        let vault_1 = read_pubkey_at(&data, RAYDIUM_POOL_TOKEN_1_VAULT_OFFSET)?;
+        let (expected_in, expected_out) = if mint_0 == input_mint.key() { (vault_0, vault_1) } else { (vault_1, vault_0) };
+        require_keys_eq!(accs.input_vault.key(), expected_in, PredictError::InvalidRaydiumPool);
+        require_keys_eq!(accs.output_vault.key(), expected_out, PredictError::InvalidRaydiumPool);
+    }
+
+    let before = output_ata.amount;
+
+    let ix = ix_raydium_swap_base_input(
+        &SingleSideSwapAccounts {
+            payer: accs.user.key(),
+            authority: accs.raydium_authority.key(),
+            amm_config: accs.amm_config.key(),
+            pool_state: accs.pool_state.key(),
+            input_token_account: input_ata.key(),
+            output_token_account: output_ata.key(),
+            input_vault: accs.input_vault.key(),
+            output_vault: accs.output_vault.key(),
+            token_program: accs.token_program.key(),
+            input_mint: input_mint.key(),
+            output_mint: output_mint.key(),
+            observation_state: accs.observation_state.key(),
+        },
+        amount_in,
+        minimum_amount_out,
+    );
+
+    anchor_lang::solana_program::program::invoke(
+        &ix,
+        &[
+            accs.user.to_account_info(),
+            accs.raydium_authority.to_account_info(),
+            accs.amm_config.to_account_info(),
+            accs.pool_state.to_account_info(),
+            input_ata.to_account_info(),
+            output_ata.to_account_info(),
+            accs.input_vault.to_account_info(),
+            accs.output_vault.to_account_info(),
+            accs.token_program.to_account_info(),
+            input_mint.to_account_info(),
+            output_mint.to_account_info(),
+            accs.observation_state.to_account_info(),
+            accs.raydium_program.to_account_info(),
+        ],
+    )?;
+
+    let output_ata = match side {
+        1 => &mut accs.user_true_ata,
+        _ => &mut accs.user_false_ata,
+    };
+    output_ata.reload()?;
+
+    output_ata.amount.checked_sub(before).ok_or_else(|| error!(PredictError::MathOverflow))
+}
+
+// ============================================================
+// Categorical event helpers
+// ============================================================
+// remaining_accounts = [mint_0, ata_0, mint_1, ata_1, ...] in outcome order
+fn categorical_outcome_accounts<'info>(
+    cev: &CategoricalEvent,
+    remaining: &'info [AccountInfo<'info>],
+    owner: &Pubkey,
+) -> Result<Vec<(&'info AccountInfo<'info>, InterfaceAccount<'info, TokenAccount>)>> {
+    let n = cev.outcome_count as usize;
+    require!(remaining.len() == n * 2, PredictError::InvalidRemainingAccounts);
+
+    let mut out = Vec::with_capacity(n);
+    for i in 0..n {
+        let mint = &remaining[i * 2];
+        require_keys_eq!(mint.key(), cev.outcome_mints[i], PredictError::InvalidMint);
+
+        let ata: InterfaceAccount<'info, TokenAccount> = InterfaceAccount::try_from(&remaining[i * 2 + 1])?;
+        require_keys_eq!(ata.owner, *owner, PredictError::InvalidTokenAccountOwner);
+        require_keys_eq!(ata.mint, mint.key(), PredictError::InvalidTokenAccountMint);
+
+        out.push((mint, ata));
+    }
+
+    Ok(out)
+}
+
+fn no_outstanding_categorical(cev: &CategoricalEvent) -> bool {
+    if cev.resolved && cev.result_status == RESULT_RESOLVED_WINNER {
+        return cev
+            .outstanding
+            .get(cev.winning_outcome as usize)
+            .is_some_and(|o| *o == 0);
+    }
+    cev.outstanding.iter().all(|o| *o == 0)
+}
+
+// Pays the pending house commission out of the commission vault once the event settles.
+fn sweep_categorical_house_commission<'info>(
+    cev: &mut Account<'info, CategoricalEvent>,
+    commission_vault: &AccountInfo<'info>,
+    house_treasury: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    vault_bump: u8,
+) -> Result<()> {
+    let amount = cev.pending_house_commission;
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let keep = vault_keep_lamports()?;
+    require!(
+        commission_vault.lamports() >= keep.saturating_add(amount),
+        PredictError::VaultInsufficientFunds
+    );
+
+    let cev_key = cev.key();
+    transfer_from_commission_vault(commission_vault, house_treasury, system_program, &cev_key, vault_bump, amount)?;
+    record_treasury_inflow(house_treasury, &cev_key, amount)?;
+    cev.pending_house_commission = 0;
+    cev.house_lamports_paid = cev.house_lamports_paid.checked_add(amount).ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+// No winner: with clawback on, the creator commission moves to the collateral vault and is
+// paid pro rata over every outcome token still outstanding, as for binary events.
+fn forfeit_categorical_creator_commission<'info>(
+    cev: &mut Account<'info, CategoricalEvent>,
+    commission_vault: &AccountInfo<'info>,
+    collateral_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    vault_bump: u8,
+) -> Result<()> {
+    let forfeited = cev.pending_creator_commission;
+    if !cev.creator_clawback || forfeited == 0 {
+        return Ok(());
+    }
+
+    let keep = vault_keep_lamports()?;
+    require!(
+        commission_vault.lamports() >= keep.saturating_add(forfeited),
+        PredictError::VaultInsufficientFunds
+    );
+    let cev_key = cev.key();
+    transfer_from_commission_vault(commission_vault, collateral_vault, system_program, &cev_key, vault_bump, forfeited)?;
+
+    let base = cev
+        .outstanding
+        .iter()
+        .try_fold(0u64, |acc, o| acc.checked_add(*o))
+        .ok_or(PredictError::MathOverflow)?;
+    cev.forfeited_commission = forfeited;
+    cev.forfeit_base_units = base;
+    cev.total_collateral_lamports = cev.total_collateral_lamports.checked_add(forfeited).ok_or(PredictError::MathOverflow)?;
+    cev.pending_creator_commission = 0;
+    Ok(())
+}
+
+// ============================================================
+// Conditional market helpers
+// ============================================================
+#[inline(never)]
+#[allow(clippy::too_many_arguments)]
+fn transfer_from_conditional_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    token_vault: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    dest: &AccountInfo<'info>,
+    conditional_market: &AccountInfo<'info>,
+    event_key: &Pubkey,
+    market_bump: u8,
+    decimals: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let seeds: [&[u8]; 3] = [
+        SEED_CONDITIONAL_MARKET,
+        event_key.as_ref(),
+        &[market_bump],
+    ];
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: token_vault.clone(),
+                mint: mint.clone(),
+                to: dest.clone(),
+                authority: conditional_market.clone(),
+            },
+        )
+        .with_signer(&[&seeds]),
+        amount,
+        decimals,
+    )
+}
+
+// The condition holds only when the parent resolved with the chosen side as winner.
+fn conditional_parent_met(parent: &Event, parent_side: u8) -> bool {
+    parent.resolved
+        && parent.result_status == RESULT_RESOLVED_WINNER
+        && parent.winning_option == parent_side
+}
+
+// ============================================================
+// SPL token collateral helpers
+// ============================================================
+// Token-collateral events move collateral through the token vault; the
+// lamport instructions are for native SOL events only.
+fn require_native_collateral(ev: &Event) -> Result<()> {
+    require!(ev.collateral_mint == Pubkey::default(), PredictError::TokenCollateralEvent);
+    Ok(())
+}
+
+fn require_token_collateral(ev: &Event) -> Result<()> {
+    require!(ev.collateral_mint != Pubkey::default(), PredictError::NativeCollateralEvent);
+    Ok(())
+}
+
+// Burns rounding dust left in a collateral token vault, then closes it to `destination`.
+fn close_token_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: Option<&AccountInfo<'info>>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    require!(vault.amount <= VAULT_DUST_TOLERANCE_LAMPORTS, PredictError::VaultNotEmpty);
+
+    if vault.amount > 0 {
+        let mint = mint.ok_or(PredictError::TokenVaultRequired)?;
+        token_interface::burn(
+            CpiContext::new(
+                token_program.clone(),
                 Burn {
-                    mint: ctx.accounts.false_mint.to_account_info(),
-                    from: ctx.accounts.user_false_ata.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
+                    mint: mint.clone(),
+                    from: vault.to_account_info(),
+                    authority: authority.clone(),
                 },
-            ),
-            amount,
+            )
+            .with_signer(&[seeds]),
+            vault.amount,
         )?;
+    }
 
-        // fee = 1% (100 bps)
-        let fee = amount
-            .checked_mul(REDEEM_FEE_BPS).ok_or(PredictError::MathOverflow)?
-            .checked_div(BPS_DENOM).ok_or(PredictError::MathOverflow)?;
+    token_interface::close_account(
+        CpiContext::new(
+            token_program.clone(),
+            token_interface::CloseAccount {
+                account: vault.to_account_info(),
+                destination: destination.clone(),
+                authority: authority.clone(),
+            },
+        )
+        .with_signer(&[seeds]),
+    )
+}
+
+// The token vault is owned by the collateral vault PDA, which signs every transfer out.
+#[inline(never)]
+#[allow(clippy::too_many_arguments)]
+fn transfer_from_token_vault<'info>(
+    token_program: &AccountInfo<'info>,
+    token_vault: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    dest: &AccountInfo<'info>,
+    collateral_vault: &AccountInfo<'info>,
+    event_key: &Pubkey,
+    vault_bump: u8,
+    decimals: u8,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
 
-        let payout = amount.checked_sub(fee).ok_or(PredictError::MathOverflow)?;
+    let seeds: [&[u8]; 3] = [
+        SEED_COLLATERAL_VAULT,
+        event_key.as_ref(),
+        &[vault_bump],
+    ];
 
-        // Identify the "zero-line"— the amount of money that must stay 
-        // in the account so it isn't deleted by the network.
-        // Fetches the current rent configuration from the Solana network
-        let keep = vault_keep_lamports()?;
-        let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: token_vault.clone(),
+                mint: mint.clone(),
+                to: dest.clone(),
+                authority: collateral_vault.clone(),
+            },
+        )
+        .with_signer(&[&seeds]),
+        amount,
+        decimals,
+    )
+}
+
+// ============================================================
+// Token-2022 position mint helpers
+// ============================================================
+// (name, symbol, uri) for the TRUE/LONG and FALSE/SHORT mints
+fn position_token_labels(ev: &Event, config: &ProtocolConfig) -> [(String, String, String); 2] {
+    let prefix = short_prefix(&ev.title);
+    let (true_label, false_label, true_uri, false_uri) = if ev.kind == EVENT_KIND_SCALAR {
+        ("LONG", "SHORT", String::new(), String::new())
+    } else {
+        ("TRUE", "FALSE", config.true_token_uri.clone(), config.false_token_uri.clone())
+    };
+
+    [
+        (format!("PS-{}-{}", prefix, true_label), true_label.to_string(), true_uri),
+        (format!("PS-{}-{}", prefix, false_label), false_label.to_string(), false_uri),
+    ]
+}
+
+// spl-token-metadata-interface `Initialize` discriminator
+const IX_TOKEN_METADATA_INITIALIZE: [u8; 8] = [210, 225, 30, 162, 88, 184, 77, 141];
+
+fn ix_token_metadata_initialize(
+    token_program: Pubkey,
+    mint: Pubkey,
+    authority: Pubkey,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<Instruction> {
+    let mut ix_data = IX_TOKEN_METADATA_INITIALIZE.to_vec();
+    ix_data.extend_from_slice(&(name, symbol, uri).try_to_vec()?);
+
+    // metadata lives on the mint itself
+    let accounts = vec![
+        AccountMeta::new(mint, false),
+        AccountMeta::new_readonly(authority, false),
+        AccountMeta::new_readonly(mint, false),
+        AccountMeta::new_readonly(authority, true),
+    ];
+
+    Ok(Instruction {
+        program_id: token_program,
+        accounts,
+        data: ix_data,
+    })
+}
+
+// TLV header + update authority + mint + 3 borsh strings + empty additional_metadata vec
+fn token_metadata_tlv_len(name: &str, symbol: &str, uri: &str) -> usize {
+    4 + 32 + 32 + (4 + name.len()) + (4 + symbol.len()) + (4 + uri.len()) + 4
+}
+
+// Token-2022 mint with a close authority and on-mint metadata, both held by the mint authority PDA.
+// The metadata is written after the mint is initialised, so the account is funded for it up front.
+#[allow(clippy::too_many_arguments)]
+fn create_and_init_token_2022_mint_pda<'info>(
+    payer: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    mint_signer_seeds: &[&[u8]],
+    auth_signer_seeds: &[&[u8]],
+    decimals: u8,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    if mint.lamports() != 0 {
+        return Ok(());
+    }
+
+    let mint_len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::MintCloseAuthority,
+        ExtensionType::MetadataPointer,
+    ])?;
+    let metadata_len = token_metadata_tlv_len(&name, &symbol, &uri);
+    let lamports = Rent::get()?.minimum_balance(mint_len + metadata_len);
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            mint.key,
+            lamports,
+            mint_len as u64,
+            token_program.key,
+        ),
+        &[payer.clone(), mint.clone(), system_program.clone()],
+        &[mint_signer_seeds],
+    )?;
+
+    // extensions must be initialised before the mint itself
+    token_interface::initialize_mint_close_authority(
+        CpiContext::new(
+            token_program.clone(),
+            token_interface::InitializeMintCloseAuthority { mint: mint.clone() },
+        ),
+        Some(mint_authority.key),
+    )?;
+
+    invoke_signed(
+        &metadata_pointer::instruction::initialize(
+            token_program.key,
+            mint.key,
+            Some(*mint_authority.key),
+            Some(*mint.key),
+        )?,
+        std::slice::from_ref(mint),
+        &[],
+    )?;
+
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program.clone(),
+            token_interface::InitializeMint2 { mint: mint.clone() },
+        ),
+        decimals,
+        mint_authority.key,
+        None,
+    )?;
+
+    let ix = ix_token_metadata_initialize(
+        *token_program.key,
+        *mint.key,
+        *mint_authority.key,
+        name,
+        symbol,
+        uri,
+    )?;
+    invoke_signed(&ix, &[mint.clone(), mint_authority.clone()], &[auth_signer_seeds])?;
+
+    Ok(())
+}
+
+// Closes an empty Token-2022 position mint and returns its rent to `destination`.
+// Classic SPL mints cannot be closed and are left as they are.
+fn close_position_mint<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    destination: &AccountInfo<'info>,
+    mint_authority: &AccountInfo<'info>,
+    auth_signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let mint_ai = mint.to_account_info();
+    if *mint_ai.owner != spl_token_2022::ID || mint.supply != 0 {
+        return Ok(());
+    }
+
+    token_interface::close_account(
+        CpiContext::new(
+            token_program.clone(),
+            token_interface::CloseAccount {
+                account: mint_ai,
+                destination: destination.clone(),
+                authority: mint_authority.clone(),
+            },
+        )
+        .with_signer(&[auth_signer_seeds]),
+    )
+}
+
+// ============================================================
+// Protocol config helpers
+// ============================================================
+fn validate_config_params(params: &ConfigParams) -> Result<()> {
+    require!(params.house_wallet != Pubkey::default(), PredictError::InvalidConfig);
+    require!(
+        (MIN_CONSENSUS_THRESHOLD_BPS..=BPS_DENOM as u16).contains(&params.consensus_threshold_bps),
+        PredictError::InvalidConfig
+    );
+    require!(params.buy_fee_bps <= MAX_BUY_FEE_BPS, PredictError::InvalidConfig);
+    require!(
+        params.min_buy_fee_bps <= params.buy_fee_bps && params.buy_fee_bps <= params.max_buy_fee_bps,
+        PredictError::InvalidConfig
+    );
+    require!(params.max_buy_fee_bps <= MAX_BUY_FEE_BPS, PredictError::InvalidConfig);
+    require!(
+        (params.min_truth_share_bps as u64)
+            .saturating_add(params.min_house_share_bps as u64) <= BPS_DENOM,
+        PredictError::InvalidConfig
+    );
+    require!(params.max_creator_share_bps as u64 <= BPS_DENOM, PredictError::InvalidConfig);
+    require!(params.referral_share_bps as u64 <= BPS_DENOM, PredictError::InvalidConfig);
+    for (i, tier) in params.fee_tiers.iter().enumerate() {
+        require!(tier.discount_bps as u64 <= BPS_DENOM, PredictError::InvalidConfig);
+        if i > 0 {
+            let prev = &params.fee_tiers[i - 1];
+            require!(
+                tier.min_volume >= prev.min_volume && tier.discount_bps >= prev.discount_bps,
+                PredictError::InvalidConfig
+            );
+        }
+    }
+    let mut total_weight: u64 = 0;
+    for b in params.treasury_beneficiaries.iter() {
+        require!(b.weight_bps == 0 || b.wallet != Pubkey::default(), PredictError::InvalidConfig);
+        total_weight = total_weight.saturating_add(b.weight_bps as u64);
+    }
+    require!(total_weight == 0 || total_weight == BPS_DENOM, PredictError::InvalidConfig);
+    require!(params.redeem_fee_bps <= MAX_REDEEM_FEE_BPS, PredictError::InvalidConfig);
+    require!(
+        (MIN_UNCLAIMED_SWEEP_DELAY_SECS..=MAX_UNCLAIMED_SWEEP_DELAY_SECS).contains(&params.unclaimed_sweep_delay_secs),
+        PredictError::InvalidConfig
+    );
+    require!(
+        (MIN_EMERGENCY_EXIT_DELAY_SECS..=MAX_EMERGENCY_EXIT_DELAY_SECS).contains(&params.emergency_exit_delay_secs),
+        PredictError::InvalidConfig
+    );
+    require!(
+        (0..=MAX_DISPUTE_WINDOW_SECS).contains(&params.dispute_window_secs),
+        PredictError::InvalidConfig
+    );
+    require!(
+        params.dispute_window_secs == 0 || params.dispute_bond_lamports > 0,
+        PredictError::InvalidConfig
+    );
+    require!(params.revote_max_rounds as usize <= MAX_REVOTE_ROUNDS, PredictError::InvalidConfig);
+    require!(params.revote_funding_bps as u64 <= BPS_DENOM, PredictError::InvalidConfig);
+    require!(params.true_token_uri.len() <= MAX_TOKEN_URI_LEN, PredictError::InvalidConfig);
+    require!(params.false_token_uri.len() <= MAX_TOKEN_URI_LEN, PredictError::InvalidConfig);
+    Ok(())
+}
+
+// Creator-chosen fee schedule, bounded by the protocol config. House share is the remainder.
+fn validate_fee_schedule(config: &ProtocolConfig, schedule: &FeeSchedule) -> Result<()> {
+    require!(
+        (config.min_buy_fee_bps..=config.max_buy_fee_bps).contains(&schedule.fee_bps),
+        PredictError::InvalidFeeSchedule
+    );
+    require!(schedule.truth_share_bps >= config.min_truth_share_bps, PredictError::InvalidFeeSchedule);
+    require!(schedule.creator_share_bps <= config.max_creator_share_bps, PredictError::InvalidFeeSchedule);
+
+    let house_share = BPS_DENOM
+        .checked_sub(schedule.truth_share_bps as u64)
+        .and_then(|v| v.checked_sub(schedule.creator_share_bps as u64))
+        .ok_or(PredictError::InvalidFeeSchedule)?;
+    require!(house_share >= config.min_house_share_bps as u64, PredictError::InvalidFeeSchedule);
+    Ok(())
+}
+
+// buy fee schedule: creator's choice within the config bounds, else the protocol default
+fn fee_schedule_or_default(config: &ProtocolConfig, fee_schedule: Option<FeeSchedule>) -> Result<FeeSchedule> {
+    match fee_schedule {
+        Some(schedule) => {
+            validate_fee_schedule(config, &schedule)?;
+            Ok(schedule)
+        }
+        None => Ok(FeeSchedule {
+            fee_bps: config.buy_fee_bps,
+            truth_share_bps: DEFAULT_TRUTH_SHARE_BPS,
+            creator_share_bps: DEFAULT_CREATOR_SHARE_BPS,
+        }),
+    }
+}
+
+fn apply_config_params(config: &mut ProtocolConfig, params: ConfigParams) {
+    config.house_wallet = params.house_wallet;
+    config.truth_fee_recipient = params.truth_fee_recipient;
+    config.consensus_threshold_bps = params.consensus_threshold_bps;
+    config.buy_fee_bps = params.buy_fee_bps;
+    config.min_buy_fee_bps = params.min_buy_fee_bps;
+    config.max_buy_fee_bps = params.max_buy_fee_bps;
+    config.min_truth_share_bps = params.min_truth_share_bps;
+    config.min_house_share_bps = params.min_house_share_bps;
+    config.max_creator_share_bps = params.max_creator_share_bps;
+    config.referral_share_bps = params.referral_share_bps;
+    config.fee_tiers = params.fee_tiers;
+    config.creator_clawback = params.creator_clawback;
+    config.treasury_beneficiaries = params.treasury_beneficiaries;
+    config.emergency_exit_delay_secs = params.emergency_exit_delay_secs;
+    config.dispute_window_secs = params.dispute_window_secs;
+    config.dispute_bond_lamports = params.dispute_bond_lamports;
+    config.revote_max_rounds = params.revote_max_rounds;
+    config.revote_funding_bps = params.revote_funding_bps;
+    config.void_authority = params.void_authority;
+    config.redeem_fee_bps = params.redeem_fee_bps;
+    config.unclaimed_sweep_delay_secs = params.unclaimed_sweep_delay_secs;
+    config.true_token_uri = params.true_token_uri;
+    config.false_token_uri = params.false_token_uri;
+}
+
+// ============================================================
+// User trading stats helpers
+// ============================================================
+// Two fixed 30-day buckets; the previous one fades out linearly over the current window.
+fn roll_volume_window(stats: &mut UserStats, now: i64) {
+    let elapsed = now.saturating_sub(stats.window_start);
+    if elapsed < VOLUME_WINDOW_SECS {
+        return;
+    }
+    stats.previous_window_volume = if elapsed < 2 * VOLUME_WINDOW_SECS { stats.current_window_volume } else { 0 };
+    stats.current_window_volume = 0;
+    stats.window_start = now - elapsed % VOLUME_WINDOW_SECS;
+}
+
+fn rolling_volume_30d(stats: &UserStats, now: i64) -> u64 {
+    let elapsed = now.saturating_sub(stats.window_start);
+    if elapsed >= 2 * VOLUME_WINDOW_SECS {
+        return 0;
+    }
+    if elapsed >= VOLUME_WINDOW_SECS {
+        // the current bucket is about to become the previous one
+        let left = (2 * VOLUME_WINDOW_SECS - elapsed) as u128;
+        return (stats.current_window_volume as u128 * left / VOLUME_WINDOW_SECS as u128) as u64;
+    }
+    let left = (VOLUME_WINDOW_SECS - elapsed) as u128;
+    let faded = (stats.previous_window_volume as u128 * left / VOLUME_WINDOW_SECS as u128) as u64;
+    stats.current_window_volume.saturating_add(faded)
+}
+
+fn fee_tier_discount_bps(config: &ProtocolConfig, volume: u64) -> u16 {
+    config
+        .fee_tiers
+        .iter()
+        .filter(|t| t.discount_bps > 0 && volume >= t.min_volume)
+        .map(|t| t.discount_bps)
+        .max()
+        .unwrap_or(0)
+}
+
+// First touch of the user's stats / position PDAs (both init_if_needed)
+fn init_user_records(
+    stats: &mut UserStats,
+    position: &mut UserPosition,
+    user: &Pubkey,
+    event: &Pubkey,
+    bumps: [u8; 2],
+    now: i64,
+) -> Result<()> {
+    if stats.owner == Pubkey::default() {
+        stats.owner = *user;
+        stats.window_start = now;
+        stats.bump = bumps[0];
+    }
+    if position.owner == Pubkey::default() {
+        position.owner = *user;
+        position.event = *event;
+        position.bump = bumps[1];
+        stats.events_participated = stats.events_participated.checked_add(1).ok_or(PredictError::MathOverflow)?;
+    }
+    Ok(())
+}
+
+// `volume` is the lamport amount counted towards the fee tiers (0 for buys paid in SPL
+// tokens); `cost` is split across the bought [TRUE, FALSE] units pro rata, in the event's
+// collateral units.
+#[allow(clippy::too_many_arguments)]
+fn record_buy(
+    stats: &mut UserStats,
+    position: &mut UserPosition,
+    user: &Pubkey,
+    event: &Pubkey,
+    bumps: [u8; 2],
+    volume: u64,
+    cost: u64,
+    units: [u64; 2],
+    now: i64,
+) -> Result<()> {
+    init_user_records(stats, position, user, event, bumps, now)?;
+
+    roll_volume_window(stats, now);
+    stats.lifetime_volume = stats.lifetime_volume.checked_add(volume).ok_or(PredictError::MathOverflow)?;
+    stats.current_window_volume = stats.current_window_volume.checked_add(volume).ok_or(PredictError::MathOverflow)?;
+
+    let [true_units, false_units] = units;
+    let total_units = true_units.checked_add(false_units).ok_or(PredictError::MathOverflow)?;
+    if total_units == 0 {
+        return Ok(());
+    }
+    let true_cost = mul_div_floor(cost, true_units, total_units)?;
+    let false_cost = cost - true_cost;
+    position.cost_basis_true = position.cost_basis_true.checked_add(true_cost).ok_or(PredictError::MathOverflow)?;
+    position.cost_basis_false = position.cost_basis_false.checked_add(false_cost).ok_or(PredictError::MathOverflow)?;
+    position.units_true = position.units_true.checked_add(true_units).ok_or(PredictError::MathOverflow)?;
+    position.units_false = position.units_false.checked_add(false_units).ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+// [TRUE, FALSE] units for `amount` tokens of one side
+fn side_units(side: u8, amount: u64) -> Result<[u64; 2]> {
+    match side {
+        1 => Ok([amount, 0]),
+        2 => Ok([0, amount]),
+        _ => err!(PredictError::InvalidSide),
+    }
+}
+
+// Removes the basis of `units` tokens from one side, pro rata (tokens bought elsewhere have no basis).
+fn take_basis(basis: &mut u64, held: &mut u64, units: u64) -> Result<u64> {
+    if *held == 0 {
+        return Ok(0);
+    }
+    let used = units.min(*held);
+    let cost = mul_div_floor(*basis, used, *held)?;
+    *basis = basis.saturating_sub(cost);
+    *held = held.saturating_sub(used);
+    Ok(cost)
+}
 
+// Single-side buy: `sold` tokens of the unwanted side became `received` tokens of the wanted
+// side, so their basis moves with them.
+fn record_side_swap(position: &mut UserPosition, side: u8, sold: u64, received: u64) -> Result<()> {
+    let (from_basis, from_units, to_basis, to_units) = match side {
+        1 => (&mut position.cost_basis_false, &mut position.units_false, &mut position.cost_basis_true, &mut position.units_true),
+        2 => (&mut position.cost_basis_true, &mut position.units_true, &mut position.cost_basis_false, &mut position.units_false),
+        _ => return err!(PredictError::InvalidSide),
+    };
+    let moved = take_basis(from_basis, from_units, sold)?;
+    *to_basis = to_basis.checked_add(moved).ok_or(PredictError::MathOverflow)?;
+    *to_units = to_units.checked_add(received).ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+// Realised PnL = payout minus the basis of the TRUE / FALSE units redeemed. Once a winner is
+// final the losing side is worthless, so its whole remaining basis is realised (losing_side 0 = none).
+#[allow(clippy::too_many_arguments)]
+fn record_redemption(
+    stats: &mut UserStats,
+    position: &mut UserPosition,
+    user: &Pubkey,
+    event: &Pubkey,
+    bumps: [u8; 2],
+    payout: u64,
+    true_units: u64,
+    false_units: u64,
+    losing_side: u8,
+    now: i64,
+) -> Result<()> {
+    init_user_records(stats, position, user, event, bumps, now)?;
+
+    let true_units = if losing_side == 1 { position.units_true } else { true_units };
+    let false_units = if losing_side == 2 { position.units_false } else { false_units };
+    let cost = take_basis(&mut position.cost_basis_true, &mut position.units_true, true_units)?
+        .checked_add(take_basis(&mut position.cost_basis_false, &mut position.units_false, false_units)?)
+        .ok_or(PredictError::MathOverflow)?;
+    position.payouts = position.payouts.checked_add(payout).ok_or(PredictError::MathOverflow)?;
+
+    stats.total_payouts = stats.total_payouts.checked_add(payout).ok_or(PredictError::MathOverflow)?;
+    stats.realized_pnl = stats
+        .realized_pnl
+        .checked_add(payout as i64 - cost as i64)
+        .ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
+
+
+// ============================================================
+// Creator commission clawback
+// ============================================================
+
+// Hands pending commission over to the token holders. Native events move it into the
+// collateral vault; token events keep it in the commission token vault, and
+// redeem_after_final_token pays each holder's share from there.
+#[inline(never)]
+fn return_commission_to_holders<'info>(
+    ev: &mut Account<'info, Event>,
+    amount: u64,
+    commission_vault: &AccountInfo<'info>,
+    collateral_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    commission_bump: u8,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if ev.collateral_mint == Pubkey::default() {
+        let keep = vault_keep_lamports()?;
         require!(
-            vault_lamports >= keep.saturating_add(payout),
+            commission_vault.lamports() >= keep.saturating_add(amount),
             PredictError::VaultInsufficientFunds
         );
 
-        // Vault PDA signs the SOL transfer
-        let event_key = ctx.accounts.event.key();
-        let vault_bump = ctx.bumps.collateral_vault;
-        let vault_seeds: [&[u8]; 3] = [
-            SEED_COLLATERAL_VAULT,
-            event_key.as_ref(),
-            &[vault_bump],
-        ];
-
-        invoke_signed(
-            &system_instruction::transfer(
-                &ctx.accounts.collateral_vault.key(),
-                &ctx.accounts.user.key(),
-                payout,
-            ),
-            &[
-                ctx.accounts.collateral_vault.to_account_info(),
-                ctx.accounts.user.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&vault_seeds],
+        let event_key = ev.key();
+        transfer_from_commission_vault(
+            commission_vault,
+            collateral_vault,
+            system_program,
+            &event_key,
+            commission_bump,
+            amount,
         )?;
+    }
 
-        // Accounting updates
-        // Keeps event state updated for later redemption stages
-        ctx.accounts.event.total_collateral_lamports = ctx.accounts.event
-            .total_collateral_lamports
-            .checked_sub(payout)
-            .ok_or(PredictError::MathOverflow)?;
+    record_forfeited_commission(ev, amount)
+}
+
+// Bookkeeping for commission handed to the holders, paid pro rata over every token
+// outstanding at that point.
+fn record_forfeited_commission(ev: &mut Event, amount: u64) -> Result<()> {
+    ev.forfeited_commission = ev
+        .forfeited_commission
+        .checked_add(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    ev.forfeit_base_units = ev
+        .outstanding_true
+        .checked_add(ev.outstanding_false)
+        .ok_or(PredictError::MathOverflow)?;
+    ev.total_collateral_lamports = ev
+        .total_collateral_lamports
+        .checked_add(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    Ok(())
+}
 
-        // ctx.accounts.event.total_issued_per_side = ctx.accounts.event
-        //     .total_issued_per_side
-        //     .checked_sub(amount)
-        //     .ok_or(PredictError::MathOverflow)?;
+// On a no-winner result the creator's pending commission goes to the token holders.
+fn forfeit_creator_commission<'info>(
+    ev: &mut Account<'info, Event>,
+    commission_vault: &AccountInfo<'info>,
+    collateral_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    commission_bump: u8,
+) -> Result<()> {
+    if !ev.creator_clawback {
+        return Ok(());
+    }
 
-        ctx.accounts.event.outstanding_true = ctx.accounts.event
-            .outstanding_true
-            .checked_sub(amount)
-            .ok_or(PredictError::MathOverflow)?;
+    let amount = ev.pending_creator_commission;
+    return_commission_to_holders(ev, amount, commission_vault, collateral_vault, system_program, commission_bump)?;
+    ev.pending_creator_commission = 0;
+    Ok(())
+}
 
-        ctx.accounts.event.outstanding_false = ctx.accounts.event
-            .outstanding_false
-            .checked_sub(amount)
-            .ok_or(PredictError::MathOverflow)?;
+// Voided events refund the position at full value, so no redeem fee
+fn effective_redeem_fee_bps(ev: &Event) -> u16 {
+    if ev.result_status == RESULT_VOIDED {
+        0
+    } else {
+        ev.redeem_fee_bps
+    }
+}
 
-        Ok(())
+// Pro rata part of the forfeited commission for `amount` no-winner tokens
+fn forfeited_commission_share(ev: &Event, amount: u64) -> Result<u64> {
+    if ev.forfeited_commission == 0 || ev.forfeit_base_units == 0 {
+        return Ok(0);
     }
+    mul_div_floor(ev.forfeited_commission, amount, ev.forfeit_base_units)
+}
 
+// Shared by the emergency exits. The first exit checks the deadline and settles the event
+// as RESULT_EMERGENCY_EXIT (returns true); later ones only check it is still open.
+fn open_emergency_exit(ev: &mut Event, now: i64) -> Result<bool> {
+    if ev.resolved {
+        require!(ev.result_status == RESULT_EMERGENCY_EXIT, PredictError::EventAlreadyResolved);
+        require!(!ev.unclaimed_swept, PredictError::RedemptionExpired);
+        return Ok(false);
+    }
+    require!(
+        now >= ev.reveal_end_time.saturating_add(ev.emergency_exit_delay_secs),
+        PredictError::EmergencyExitNotAvailable
+    );
 
-    pub fn fetch_and_store_winner(ctx: Context<FetchAndStoreWinner>) -> Result<()> {
-        let ev = &mut ctx.accounts.event;
-        let q = &mut ctx.accounts.truth_network_question;
+    ev.resolved = true;
+    ev.resolved_at = now;
+    ev.result_status = RESULT_EMERGENCY_EXIT;
+    ev.winning_option = 0;
+    ev.winning_percent_bps = 0;
+    ev.dispute_deadline = 0;
+    ev.emergency_exit_active = true;
+    Ok(true)
+}
 
-        let now = Clock::get()?.unix_timestamp;
+// ============================================================
+// Creator commission recipients
+// ============================================================
+// Each co-creator gets its share, the payout address gets the rest (including rounding)
+fn creator_commission_splits(ev: &Event, amount: u64) -> Result<(u64, [u64; MAX_CO_CREATORS])> {
+    let mut shares = [0u64; MAX_CO_CREATORS];
+    let mut rest = amount;
+    for (i, co) in ev.co_creators.iter().take(ev.co_creator_count as usize).enumerate() {
+        shares[i] = mul_div_floor(amount, co.share_bps as u64, BPS_DENOM)?;
+        rest = rest.checked_sub(shares[i]).ok_or(PredictError::MathOverflow)?;
+    }
+    Ok((rest, shares))
+}
 
-        // Betting must be finished
+// Creator commission the creator may take now. Once the event settles (bet end, or
+// resolution when clawback is on) it is everything pending; before that only the vested
+// part of the commission earned so far, minus the holdback and what was already claimed.
+fn claimable_creator_commission(ev: &Event, now: i64) -> Result<u64> {
+    let settled = if ev.creator_clawback {
+        ev.resolved && ev.dispute_status != DISPUTE_OPEN && now >= ev.dispute_deadline
+    } else {
+        now >= ev.bet_end_time
+    };
+    if settled {
+        return Ok(ev.pending_creator_commission);
+    }
+    if ev.creator_vesting == VESTING_NONE {
         require!(now >= ev.bet_end_time, PredictError::BettingStillActive);
+        return err!(PredictError::EventNotResolved);
+    }
+
+    let earned = ev
+        .pending_creator_commission
+        .checked_add(ev.claimed_creator_commission)
+        .ok_or(PredictError::MathOverflow)?;
+    let vestable = mul_div_floor(
+        earned,
+        BPS_DENOM.saturating_sub(ev.vesting_holdback_bps as u64),
+        BPS_DENOM,
+    )?;
+
+    let duration = ev.bet_end_time.saturating_sub(ev.created_at).max(1) as u64;
+    let elapsed = (now.saturating_sub(ev.created_at).max(0) as u64).min(duration);
+    let vested = if ev.creator_vesting == VESTING_TRANCHES {
+        let tranches = ev.vesting_tranches.max(1) as u64;
+        let done = mul_div_floor(elapsed, tranches, duration)?;
+        mul_div_floor(vestable, done, tranches)?
+    } else {
+        mul_div_floor(vestable, elapsed, duration)?
+    };
+
+    Ok(vested
+        .saturating_sub(ev.claimed_creator_commission)
+        .min(ev.pending_creator_commission))
+}
+
+// ============================================================
+// House treasury helpers
+// ============================================================
+// House flows go to the event's house wallet snapshot. When that is the HouseTreasury PDA
+// the inflow is counted there as well; an external wallet just receives the lamports.
+fn record_treasury_inflow(house_treasury: &AccountInfo, event: &Pubkey, lamports: u64) -> Result<()> {
+    if lamports == 0 || house_treasury.owner != &crate::ID {
+        return Ok(());
+    }
+
+    let mut treasury = HouseTreasury::try_deserialize(&mut &house_treasury.try_borrow_data()?[..])?;
+    treasury.total_received = treasury
+        .total_received
+        .checked_add(lamports)
+        .ok_or(PredictError::MathOverflow)?;
+    treasury.inflow_count = treasury.inflow_count.saturating_add(1);
 
-        // Don't allow calling twice
-        require!(!ev.resolved, PredictError::EventAlreadyResolved);
+    emit!(TreasuryInflow {
+        event: *event,
+        lamports,
+        inflow_count: treasury.inflow_count,
+    });
 
-        require_keys_eq!(ev.truth_question, q.key(), PredictError::TruthQuestionMismatch);
+    let mut data = house_treasury.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    treasury.try_serialize(&mut writer)
+}
 
-        require!(now >= q.reveal_end_time, PredictError::TruthVotingStillActive);
+// ============================================================
+// Emergency pause helpers
+// ============================================================
+fn pause_blocks(pause: &PauseState, class: u8, now: i64) -> bool {
+    pause.flags & class != 0 && now < pause.expires_at
+}
 
-        // CPI: finalize voting on Truth Network
-        let question_id = q.id;
-        let cpi_ctx = CpiContext::new(
-            ctx.accounts.truth_network_program.to_account_info(),
-            FinalizeVoting { question: q.to_account_info() },
-        );
-        finalize_voting(cpi_ctx, question_id)?;
-
-        // Refresh the account after CPI
-        q.reload()?;
-
-        // Save votes
-        let v1 = q.votes_option_1;
-        let v2 = q.votes_option_2;
-        let total_votes = v1.checked_add(v2).ok_or(PredictError::MathOverflow)?;
-
-        ev.votes_option_1 = v1;
-        ev.votes_option_2 = v2;
-        ev.resolved_at = now;
-
-        // Mark event as resolved
-        ev.resolved = true;
-
-        // sweep house commission once, regardless of outcome
-        let vault_bump = ctx.bumps.collateral_vault;
-        sweep_house_commission(
-            ev,
-            &ctx.accounts.collateral_vault.to_account_info(),
-            &ctx.accounts.house_treasury.to_account_info(),
-            &ctx.accounts.system_program.to_account_info(),
-            vault_bump,
-        )?;
+// `pause` is the event's (binary or categorical) own pause state
+fn require_not_paused(config: &ProtocolConfig, pause: &PauseState, class: u8) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    require!(!pause_blocks(&config.pause, class, now), PredictError::ProtocolPaused);
+    require!(!pause_blocks(pause, class, now), PredictError::EventPaused);
+    Ok(())
+}
 
-        // no votes
-        if total_votes == 0 {
-            ev.winning_option = 0;
-            ev.winning_percent_bps = 0;
-            ev.result_status = RESULT_FINALIZED_NO_VOTES;
-            return Ok(());
-        }
+// flags = 0 lifts the current pause. A pause cannot be extended or renewed: the next one
+// needs PAUSE_COOLDOWN_SECS after the previous one ended (or was lifted).
+fn new_pause_state(current: &PauseState, flags: u8, paused_by: Pubkey, reason: String, duration_secs: i64) -> Result<PauseState> {
+    let now = Clock::get()?.unix_timestamp;
+    if flags == 0 {
+        return Ok(PauseState {
+            flags: 0,
+            paused_by: current.paused_by,
+            reason: String::new(),
+            paused_at: current.paused_at,
+            expires_at: current.expires_at.min(now),
+        });
+    }
+    require!(flags & !PAUSE_ALL == 0, PredictError::InvalidPause);
+    require!((1..=MAX_PAUSE_SECS).contains(&duration_secs), PredictError::InvalidPause);
+    require!(reason.len() <= MAX_PAUSE_REASON_LEN, PredictError::InvalidPause);
+    require!(
+        current.expires_at == 0 || now >= current.expires_at.saturating_add(PAUSE_COOLDOWN_SECS),
+        PredictError::PauseCooldownActive
+    );
 
-        // tie
-        if q.winning_option == 0 {
-            ev.winning_option = 0;
-            ev.winning_percent_bps = 5000;
-            ev.result_status = RESULT_FINALIZED_TIE;
-            return Ok(());
-        }
+    Ok(PauseState {
+        flags,
+        paused_by,
+        reason,
+        paused_at: now,
+        expires_at: now.checked_add(duration_secs).ok_or(PredictError::MathOverflow)?,
+    })
+}
 
-        // winner (1 or 2)
+// Admin or event creator. The creator cannot hold back final redemptions, and cannot lift
+// a pause the admin set.
+fn event_pause_update(
+    config: &ProtocolConfig,
+    current: &PauseState,
+    creator: Pubkey,
+    authority: Pubkey,
+    flags: u8,
+    reason: String,
+    duration_secs: i64,
+) -> Result<PauseState> {
+    let is_admin = authority == config.admin;
+    require!(is_admin || authority == creator, PredictError::Unauthorized);
+    if !is_admin {
+        require!(flags & PAUSE_FINAL_REDEEM == 0, PredictError::Unauthorized);
+        let now = Clock::get()?.unix_timestamp;
         require!(
-            q.winning_option == 1 || q.winning_option == 2,
-            PredictError::InvalidWinningOption
+            !(current.paused_by == config.admin && now < current.expires_at),
+            PredictError::Unauthorized
         );
+    }
+    new_pause_state(current, flags, authority, reason, duration_secs)
+}
 
-        let winning_votes = if q.winning_option == 1 {v1} else {v2};
-
-        // winning_percent_bps = winning_votes * 10000 / total_votes
-        let wp_bps_u64 = winning_votes
-            .checked_mul(BPS_DENOM)
-            .ok_or(PredictError::MathOverflow)?
-            .checked_div(total_votes)
-            .ok_or(PredictError::MathOverflow)?;
-
-        let wp_bps: u16 = wp_bps_u64.min(10_000) as u16;
-        ev.winning_percent_bps = wp_bps;
-
-        // if winner but below threshold => resolved but NO winner
-        if wp_bps < ev.consensus_threshold_bps {
-            ev.winning_option = 0;
-            ev.result_status = RESULT_FINALIZED_BELOW_THRESHOLD;
-            return Ok(());
-        }
+// ============================================================
+// Dispute helpers
+// ============================================================
+// A resolved result is only final once its dispute window passed and no dispute is open
+fn require_result_final(ev: &Event) -> Result<()> {
+    require!(ev.dispute_status != DISPUTE_OPEN, PredictError::DisputeOpen);
+    let now = Clock::get()?.unix_timestamp;
+    require!(now >= ev.dispute_deadline, PredictError::DisputeWindowActive);
+    require!(!revote_available(ev, now), PredictError::RevoteWindowActive);
+    Ok(())
+}
 
-        // Store result into PredictSol event
-        ev.winning_option = q.winning_option;
-        ev.result_status = RESULT_RESOLVED_WINNER;
-        ev.swept_at = 0;
+// A tie or below-threshold result stays provisional while the creator may still ask for a revote.
+// Token-collateral and scalar events can't request one, so their results are final right away.
+fn revote_available(ev: &Event, now: i64) -> bool {
+    ev.collateral_mint == Pubkey::default()
+        && ev.kind != EVENT_KIND_SCALAR
+        && ev.resolved
+        && (ev.result_status == RESULT_FINALIZED_TIE || ev.result_status == RESULT_FINALIZED_BELOW_THRESHOLD)
+        && ev.dispute_status == DISPUTE_NONE
+        && (ev.revote_round as usize) < (ev.revote_max_rounds as usize).min(MAX_REVOTE_ROUNDS)
+        && now < ev.resolved_at.saturating_add(REVOTE_REQUEST_WINDOW_SECS)
+}
 
-        Ok(())
+// Undoes a clawback when a dispute turns a no-winner result into a winner. Redemptions
+// were blocked meanwhile, so the forfeited commission is still in the collateral vault
+// (the commission token vault for token events).
+#[inline(never)]
+fn reinstate_creator_commission<'info>(
+    ev: &mut Account<'info, Event>,
+    collateral_vault: &AccountInfo<'info>,
+    commission_vault: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    collateral_bump: u8,
+) -> Result<()> {
+    let amount = ev.forfeited_commission;
+    if amount == 0 {
+        return Ok(());
     }
 
+    if ev.collateral_mint == Pubkey::default() {
+        let event_key = ev.key();
+        transfer_from_collateral_vault(
+            collateral_vault,
+            commission_vault,
+            system_program,
+            &event_key,
+            collateral_bump,
+            amount,
+        )?;
+    }
 
-    pub fn redeem_winner_after_final(ctx: Context<RedeemWinnerAfterFinal>, amount: u64) -> Result<()> {
-        require!(amount > 0, PredictError::InvalidAmount);
+    ev.pending_creator_commission = ev
+        .pending_creator_commission
+        .checked_add(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    ev.total_collateral_lamports = ev
+        .total_collateral_lamports
+        .checked_sub(amount)
+        .ok_or(PredictError::MathOverflow)?;
+    ev.forfeited_commission = 0;
+    ev.forfeit_base_units = 0;
+    Ok(())
+}
 
-        let ev = &mut ctx.accounts.event;
-        require!(ev.resolved, PredictError::EventNotResolved);
-        require!(ev.result_status == RESULT_RESOLVED_WINNER, PredictError::InvalidResultStatus);
-        require!(!ev.unclaimed_swept, PredictError::RedemptionExpired);
+// ======================================================
+// PROGRAM
+// ======================================================
+#[program]
+pub mod predictol_sc {
+    use super::*;
 
-        // Determine which token mint is winning
-        let winning_mint = if ev.winning_option == 1 {
-            ev.true_mint
-        } else if ev.winning_option == 2 {
-            ev.false_mint
-        } else {
-            return err!(PredictError::InvalidWinningOption);
-        };
+    pub fn initialize_event_counter(ctx: Context<InitializeEventCounter>) -> Result<()> {
+        instructions::event::initialize_event_counter(ctx)
+    }
 
-        // Ensure the provided mint/ata matches the winning side
-        require_keys_eq!(ctx.accounts.mint.key(), winning_mint, PredictError::NotWinningToken);
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_event_core(
+        ctx: Context<CreateEventCore>,
+        title: String,
+        category: u8,
+        bet_end_time: i64,
+        commit_end_time: i64,
+        reveal_end_time: i64,
+        truth_question: Option<Pubkey>,
+        fee_schedule: Option<FeeSchedule>,
+    ) -> Result<()> {
+        instructions::event::create_event_core(ctx, title, category, bet_end_time, commit_end_time, reveal_end_time, truth_question, fee_schedule)
+    }
 
-        // User must have enough winning tokens to burn
-        require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
+    pub fn create_event_mints(ctx: Context<CreateEventMints>) -> Result<()> {
+        instructions::event::create_event_mints(ctx)
+    }
 
-        // Burn winning token
-        token::burn(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    from: ctx.accounts.user_ata.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
-                },
-            ),
-            amount,
-        )?;
+    pub fn buy_positions_with_fee(ctx: Context<BuyPositionsWithFee>, lamports: u64) -> Result<()> {
+        instructions::event::buy_positions_with_fee(ctx, lamports)
+    }
 
-        match ev.winning_option {
-            1 => {
-                ev.outstanding_true = ev.outstanding_true.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
-            }
-            2 => {
-                ev.outstanding_false = ev.outstanding_false.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
-            }
-            _ => return err!(PredictError::InvalidWinningOption),
-        }
+    pub fn set_single_side_pool(ctx: Context<SetSingleSidePool>) -> Result<()> {
+        instructions::single_side::set_single_side_pool(ctx)
+    }
 
-        // ev.total_issued_per_side = ev
-        //     .total_issued_per_side
-        //     .checked_sub(amount)
-        //     .ok_or(PredictError::MathOverflow)?;
+    pub fn buy_single_side(ctx: Context<BuySingleSide>, side: u8, lamports: u64, min_tokens_out: u64) -> Result<()> {
+        instructions::single_side::buy_single_side(ctx, side, lamports, min_tokens_out)
+    }
 
-        // payout = amount minus fee (e.g. 1.0 -> 0.99)
-        let payout = payout_after_fee(amount)?;
+    pub fn redeem_pair_while_active(ctx: Context<RedeemPairWhileActive>, amount: u64) -> Result<()> {
+        instructions::event::redeem_pair_while_active(ctx, amount)
+    }
 
-        // Rent safety
-        let keep = vault_keep_lamports()?;
-        let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
-        require!(
-            vault_lamports >= keep.saturating_add(payout),
-            PredictError::VaultInsufficientFunds
-        );
+    pub fn init_lmsr_market(ctx: Context<InitLmsrMarket>, b: u64) -> Result<()> {
+        instructions::lmsr::init_lmsr_market(ctx, b)
+    }
 
-        // Vault PDA signs SOL transfer
-        let event_key = ev.key();
-        let vault_bump = ctx.bumps.collateral_vault;
-        let vault_seeds: [&[u8]; 3] = [SEED_COLLATERAL_VAULT, event_key.as_ref(), &[vault_bump]];
+    pub fn buy_outcome(ctx: Context<TradeLmsrOutcome>, side: u8, amount: u64, max_cost: u64) -> Result<()> {
+        instructions::lmsr::buy_outcome(ctx, side, amount, max_cost)
+    }
 
-        invoke_signed(
-            &system_instruction::transfer(&ctx.accounts.collateral_vault.key(), &ctx.accounts.user.key(), payout),
-            &[
-                ctx.accounts.collateral_vault.to_account_info(),
-                ctx.accounts.user.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&vault_seeds],
-        )?;
+    pub fn sell_outcome(ctx: Context<TradeLmsrOutcome>, side: u8, amount: u64, min_proceeds: u64) -> Result<()> {
+        instructions::lmsr::sell_outcome(ctx, side, amount, min_proceeds)
+    }
 
-        // Update the collateral vault
-        ev.total_collateral_lamports = ev.total_collateral_lamports
-            .checked_sub(payout)
-            .ok_or(PredictError::MathOverflow)?;
+    pub fn fetch_and_store_winner(ctx: Context<FetchAndStoreWinner>) -> Result<()> {
+        instructions::event::fetch_and_store_winner(ctx)
+    }
 
-        Ok(())
+    pub fn redeem_winner_after_final(ctx: Context<RedeemWinnerAfterFinal>, amount: u64) -> Result<()> {
+        instructions::event::redeem_winner_after_final(ctx, amount)
     }
 
     pub fn redeem_no_winner_after_final(
@@ -1108,579 +2317,359 @@ pub mod predictol_sc {
         side: u8,
         amount: u64,
     ) -> Result<()> {
-        require!(amount > 0, PredictError::InvalidAmount);
-
-        let ev = &mut ctx.accounts.event;
-        require!(ev.resolved, PredictError::EventNotResolved);
-        require!(!ev.unclaimed_swept, PredictError::RedemptionExpired);
-
-        // result status must be a no votes, tie or below threshold
-        // must not equal to RESULT_RESOLVED_WINNER 
-        require!(ev.result_status != RESULT_RESOLVED_WINNER, PredictError::InvalidResultStatus);
-
-        // side must match the mint provided
-        let expected_mint = match side {
-            1 => ev.true_mint,
-            2 => ev.false_mint,
-            _ => return err!(PredictError::InvalidWinningOption),
-        };
-        require_keys_eq!(ctx.accounts.mint.key(), expected_mint, PredictError::InvalidMint);
-
-        // burn the token the user is redeeming
-        require!(ctx.accounts.user_ata.amount >= amount, PredictError::InsufficientTrueBalance);
-
-        token::burn(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Burn {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    from: ctx.accounts.user_ata.to_account_info(),
-                    authority: ctx.accounts.user.to_account_info(),
-                },
-            ),
-            amount,
-        )?;
-
-        // ev.total_issued_per_side = ev
-        //     .total_issued_per_side
-        //     .checked_sub(amount)
-        //     .ok_or(PredictError::MathOverflow)?;
+        instructions::event::redeem_no_winner_after_final(ctx, side, amount)
+    }
 
-        match side {
-            1 => {
-                ev.outstanding_true = ev.outstanding_true.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
-            }
-            2 => {
-                ev.outstanding_false = ev.outstanding_false.checked_sub(amount).ok_or(PredictError::MathOverflow)?;
-            }
-            _ => return err!(PredictError::InvalidWinningOption),
-        }
+    pub fn challenge_result(ctx: Context<ChallengeResult>, proposed_option: u8) -> Result<()> {
+        instructions::dispute::challenge_result(ctx, proposed_option)
+    }
 
-        // here we pay half (since redeeming only one side. example 1 TRUE = 0.5 SOL)
-        let pair_payout = payout_after_fee(amount)?;
-        let payout = pair_payout.checked_div(2).ok_or(PredictError::MathOverflow)?;
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, final_option: u8) -> Result<()> {
+        instructions::dispute::resolve_dispute(ctx, final_option)
+    }
 
-        // rent safety
-        let keep = vault_keep_lamports()?;
-        let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
-        require!(
-            vault_lamports >= keep.saturating_add(payout),
-            PredictError::VaultInsufficientFunds
-        );
+    pub fn settle_expired_dispute(ctx: Context<SettleExpiredDispute>) -> Result<()> {
+        instructions::dispute::settle_expired_dispute(ctx)
+    }
 
-        // vault signs SOL transfer
-        let event_key = ev.key();
-        let vault_bump = ctx.bumps.collateral_vault;
-        let vault_seeds: [&[u8]; 3] = [SEED_COLLATERAL_VAULT, event_key.as_ref(), &[vault_bump]];
+    pub fn request_revote(ctx: Context<RequestRevote>) -> Result<()> {
+        instructions::revote::request_revote(ctx)
+    }
 
-        invoke_signed(
-            &system_instruction::transfer(&ctx.accounts.collateral_vault.key(), &ctx.accounts.user.key(), payout),
-            &[
-                ctx.accounts.collateral_vault.to_account_info(),
-                ctx.accounts.user.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[&vault_seeds],
-        )?;
+    pub fn void_event(ctx: Context<VoidEvent>) -> Result<()> {
+        instructions::void::void_event(ctx)
+    }
 
-        // update collateral vault 
-        ev.total_collateral_lamports = ev.total_collateral_lamports
-            .checked_sub(payout)
-            .ok_or(PredictError::MathOverflow)?;
+    pub fn emergency_exit(ctx: Context<EmergencyExit>, side: u8, amount: u64) -> Result<()> {
+        instructions::emergency_exit::emergency_exit(ctx, side, amount)
+    }
 
-        Ok(())
+    pub fn emergency_exit_token(ctx: Context<EmergencyExitToken>, side: u8, amount: u64) -> Result<()> {
+        instructions::emergency_exit::emergency_exit_token(ctx, side, amount)
     }
 
+    pub fn emergency_exit_conditional(ctx: Context<EmergencyExitConditional>, side: u8, amount: u64) -> Result<()> {
+        instructions::emergency_exit::emergency_exit_conditional(ctx, side, amount)
+    }
 
-    pub fn claim_creator_commission(ctx: Context<ClaimCreatorCommission>) -> Result<()> {
-        let ev = &mut ctx.accounts.event;
-        let now = Clock::get()?.unix_timestamp;
+    pub fn claim_creator_commission<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimCreatorCommission<'info>>,
+    ) -> Result<()> {
+        instructions::event::claim_creator_commission(ctx)
+    }
 
-        require!(now >= ev.bet_end_time, PredictError::BettingStillActive);
-        require_keys_eq!(ctx.accounts.creator.key(), ev.creator, PredictError::Unauthorized);
+    pub fn sweep_unclaimed_to_house(ctx: Context<SweepUnclaimedToHouse>) -> Result<()> {
+        instructions::event::sweep_unclaimed_to_house(ctx)
+    }
 
-        let amount = ev.pending_creator_commission;
-        require!(amount > 0, PredictError::NothingToClaim);
+    pub fn delete_event(ctx: Context<DeleteEvent>) -> Result<()> {
+        instructions::event::delete_event(ctx)
+    }
 
-        // rent safety
-        let keep = vault_keep_lamports()?;
-        let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
-        require!(vault_lamports >= keep.saturating_add(amount), PredictError::VaultInsufficientFunds);
+    pub fn init_pool(ctx: Context<InitPool>, side: u8, fee_bps: u16) -> Result<()> {
+        instructions::pool::init_pool(ctx, side, fee_bps)
+    }
 
-        // transfer
-        let event_key = ev.key();
-        let vault_bump = ctx.bumps.collateral_vault;
+    pub fn add_liquidity(
+        ctx: Context<PoolLiquidity>,
+        token_amount: u64,
+        max_lamports: u64,
+        min_lp_out: u64,
+    ) -> Result<()> {
+        instructions::pool::add_liquidity(ctx, token_amount, max_lamports, min_lp_out)
+    }
 
-        transfer_from_collateral_vault(
-            &ctx.accounts.collateral_vault.to_account_info(),
-            &ctx.accounts.creator.to_account_info(),
-            &ctx.accounts.system_program.to_account_info(),
-            &event_key,
-            vault_bump,
-            amount,
-        )?;
+    pub fn remove_liquidity(
+        ctx: Context<PoolLiquidity>,
+        lp_amount: u64,
+        min_tokens_out: u64,
+        min_lamports_out: u64,
+    ) -> Result<()> {
+        instructions::pool::remove_liquidity(ctx, lp_amount, min_tokens_out, min_lamports_out)
+    }
 
-        ev.pending_creator_commission = 0;
-        Ok(())
+    pub fn swap(ctx: Context<PoolSwap>, sol_to_token: bool, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        instructions::pool::swap(ctx, sol_to_token, amount_in, min_amount_out)
     }
 
-    pub fn sweep_unclaimed_to_house(ctx: Context<SweepUnclaimedToHouse>) -> Result<()> {
-        let ev = &mut ctx.accounts.event;
-        let now = Clock::get()?.unix_timestamp;
+    pub fn init_order_book(ctx: Context<InitOrderBook>) -> Result<()> {
+        instructions::order_book::init_order_book(ctx)
+    }
 
-        require!(ev.resolved, PredictError::EventNotResolved);
-        require!(!ev.unclaimed_swept, PredictError::AlreadySwept);
+    pub fn init_open_orders(ctx: Context<InitOpenOrders>) -> Result<()> {
+        instructions::order_book::init_open_orders(ctx)
+    }
 
-        // only after X days
-        require!(
-            now >= ev.resolved_at.saturating_add(UNCLAIMED_SWEEP_DELAY_SECS),
-            PredictError::SweepNotYetAvailable
-        );
+    pub fn place_order(
+        ctx: Context<PlaceOrder>,
+        token_side: u8,
+        is_bid: bool,
+        price: u64,
+        quantity: u64,
+    ) -> Result<()> {
+        instructions::order_book::place_order(ctx, token_side, is_bid, price, quantity)
+    }
 
-        let vault_ai = ctx.accounts.collateral_vault.to_account_info();
-        let keep = vault_keep_lamports()?;
+    pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
+        instructions::order_book::cancel_order(ctx, order_id)
+    }
 
-        let vault_lamports = vault_ai.lamports();
-        require!(vault_lamports > keep, PredictError::NothingToSweep);
+    pub fn match_orders(ctx: Context<MatchOrders>, token_side: u8, limit: u8) -> Result<()> {
+        instructions::order_book::match_orders(ctx, token_side, limit)
+    }
 
-        let amount = vault_lamports.saturating_sub(keep);
-        require!(amount > 0, PredictError::NothingToSweep);
-        
-        let vault_bump = ctx.bumps.collateral_vault;
+    pub fn consume_events<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ConsumeEvents<'info>>,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::order_book::consume_events(ctx, limit)
+    }
 
-        transfer_from_collateral_vault(
-            &vault_ai,
-            &ctx.accounts.house_treasury.to_account_info(),
-            &ctx.accounts.system_program.to_account_info(),
-            &ev.key(),
-            vault_bump,
-            amount,
-        )?;
+    pub fn settle_funds(ctx: Context<SettleFunds>) -> Result<()> {
+        instructions::order_book::settle_funds(ctx)
+    }
 
-        ev.total_collateral_lamports = ev.total_collateral_lamports.saturating_sub(amount);
-        ev.unclaimed_swept = true;
-        ev.swept_at = now;
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_categorical_event(
+        ctx: Context<CreateCategoricalEvent>,
+        title: String,
+        category: u8,
+        bet_end_time: i64,
+        commit_end_time: i64,
+        reveal_end_time: i64,
+        truth_questions: Vec<Pubkey>,
+        fee_schedule: Option<FeeSchedule>,
+    ) -> Result<()> {
+        instructions::categorical::create_categorical_event(ctx, title, category, bet_end_time, commit_end_time, reveal_end_time, truth_questions, fee_schedule)
+    }
 
-        Ok(())
+    pub fn create_categorical_mint(ctx: Context<CreateCategoricalMint>, index: u8) -> Result<()> {
+        instructions::categorical::create_categorical_mint(ctx, index)
     }
 
-    pub fn delete_event(ctx: Context<DeleteEvent>) -> Result<()> {
-        let ev = &ctx.accounts.event;
-        let now = Clock::get()?.unix_timestamp;
+    pub fn buy_categorical_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, BuyCategoricalSet<'info>>,
+        lamports: u64,
+    ) -> Result<()> {
+        instructions::categorical::buy_categorical_set(ctx, lamports)
+    }
 
-        require!(ev.resolved, PredictError::EventNotResolved);
-        require_keys_eq!(ctx.accounts.creator.key(), ev.creator, PredictError::Unauthorized);
+    pub fn redeem_categorical_set<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RedeemCategoricalSet<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::categorical::redeem_categorical_set(ctx, amount)
+    }
 
-        require!(ev.pending_creator_commission == 0, PredictError::CreatorCommissionNotClaimed);
-        require!(ev.pending_house_commission == 0, PredictError::HouseCommissionNotCleared);
+    pub fn finalize_categorical_outcome(ctx: Context<FinalizeCategoricalOutcome>, index: u8) -> Result<()> {
+        instructions::categorical::finalize_categorical_outcome(ctx, index)
+    }
 
-        // compute keep
-        let keep = vault_keep_lamports()?;
-        let vault_lamports = ctx.accounts.collateral_vault.to_account_info().lamports();
-
-        let resolved_at = ev.resolved_at;
-        require!(resolved_at > 0, PredictError::InvalidResolvedAt);
-
-        let after_window = now >= resolved_at.saturating_add(UNCLAIMED_SWEEP_DELAY_SECS);
-        let no_outstanding = no_outstanding_tokens(ev);
-        let vault_empty = vault_lamports <= keep.saturating_add(VAULT_DUST_TOLERANCE_LAMPORTS);
-
-        if !after_window {
-            // BEFORE window: strict, only deletable if no one ever bought / all burned if no winner / winning side - all burned
-            require!(no_outstanding, PredictError::OutstandingTokens);
-            require!(vault_empty, PredictError::VaultNotEmpty);
-        } else {
-            // AFTER window:
-            // - if sweep happened, ok (tokens may exist but is expired)
-            // - if sweep not happened, only allow when there is nothing to sweep (vault empty) and no outstanding tokens
-            if !ev.unclaimed_swept {
-                require!(no_outstanding, PredictError::OutstandingTokens);
-                require!(vault_empty, PredictError::VaultNotEmpty);
-            }
-        }
+    pub fn settle_categorical_event(ctx: Context<SettleCategoricalEvent>) -> Result<()> {
+        instructions::categorical::settle_categorical_event(ctx)
+    }
 
-        // Always require vault empty (only rent/keep allowed)
-        require!(vault_empty, PredictError::VaultNotEmpty);
-        
-
-        // drain last lamports (close the vault)
-        if vault_lamports > 0 {
-            let event_key = ctx.accounts.event.key();
-            let vault_bump = ctx.bumps.collateral_vault;
-            let seeds: [&[u8]; 3] = [SEED_COLLATERAL_VAULT, event_key.as_ref(), &[vault_bump]];
-
-            invoke_signed(
-                &system_instruction::transfer(
-                    ctx.accounts.collateral_vault.key,
-                    ctx.accounts.creator.key,
-                    vault_lamports,
-                ),
-                &[
-                    ctx.accounts.collateral_vault.to_account_info(),
-                    ctx.accounts.creator.to_account_info(),
-                    ctx.accounts.system_program.to_account_info(),
-                ],
-                &[&seeds],
-            )?;
-        }
+    pub fn redeem_categorical_after_final(ctx: Context<RedeemCategoricalAfterFinal>, index: u8, amount: u64) -> Result<()> {
+        instructions::categorical::redeem_categorical_after_final(ctx, index, amount)
+    }
 
-        Ok(())
+    pub fn emergency_exit_categorical(ctx: Context<EmergencyExitCategorical>, index: u8, amount: u64) -> Result<()> {
+        instructions::categorical::emergency_exit_categorical(ctx, index, amount)
     }
 
-}
+    pub fn claim_categorical_creator_commission(ctx: Context<ClaimCategoricalCreatorCommission>) -> Result<()> {
+        instructions::categorical::claim_categorical_creator_commission(ctx)
+    }
 
-// ======================================================
-// STATE
-// ======================================================
-#[account]
-pub struct EventCounter {
-    pub creator: Pubkey,
-    pub count: u64,
-}
-
-#[account]
-pub struct Event {
-    pub creator: Pubkey,
-    pub event_id: u64,
-    pub truth_question: Pubkey,
-    pub title: String,
-
-    pub bet_end_time: i64,
-    pub commit_end_time: i64,
-    pub reveal_end_time: i64,
-    pub created_at: i64,
-
-    pub total_collateral_lamports: u64,
-    pub total_issued_per_side: u64,
-    pub collateral_vault: Pubkey,
-    pub true_mint: Pubkey,
-    pub false_mint: Pubkey,
-
-    pub resolved: bool,
-    pub winning_option: u8,
-    pub winning_percent_bps: u16,    
-    pub votes_option_1: u64,         
-    pub votes_option_2: u64,         
-    pub consensus_threshold_bps: u16,
-    pub resolved_at: i64,            
-    pub result_status: u8,    
-
-    pub total_truth_commission_sent: u64,
-    pub pending_creator_commission: u64,
-    pub pending_house_commission: u64,
-
-    pub unclaimed_swept: bool,
-    pub swept_at: i64,
-
-    pub outstanding_true: u64,
-    pub outstanding_false: u64,
-    pub category: u8,
-}
+    pub fn sweep_categorical_unclaimed_to_house(ctx: Context<SweepCategoricalUnclaimedToHouse>) -> Result<()> {
+        instructions::categorical::sweep_categorical_unclaimed_to_house(ctx)
+    }
 
-// ======================================================
-// CONTEXTS
-// ======================================================
-#[derive(Accounts)]
-pub struct InitializeEventCounter<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    #[account(init, payer = creator, space = 8 + 32 + 8, seeds = [SEED_EVENT_COUNTER, creator.key().as_ref()], bump)]
-    pub counter: Account<'info, EventCounter>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn delete_categorical_event(ctx: Context<DeleteCategoricalEvent>) -> Result<()> {
+        instructions::categorical::delete_categorical_event(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct CreateEventCore<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    #[account(mut)]
-    pub counter: Account<'info, EventCounter>,
-    #[account(init, payer = creator, space = 8 + (296 + 1 + 154), seeds = [SEED_EVENT, creator.key().as_ref(), &counter.count.to_le_bytes()], bump)]
-    pub event: Account<'info, Event>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn init_scalar_market(ctx: Context<InitScalarMarket>, lower: i64, upper: i64) -> Result<()> {
+        instructions::scalar::init_scalar_market(ctx, lower, upper)
+    }
 
-#[derive(Accounts)]
-pub struct CreateEventMints<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+    pub fn report_scalar_value(ctx: Context<ReportScalarValue>, value: i64) -> Result<()> {
+        instructions::scalar::report_scalar_value(ctx, value)
+    }
 
-    #[account(mut)]
-    pub event: Account<'info, Event>,
+    pub fn fetch_and_store_scalar_result(ctx: Context<FetchAndStoreScalarResult>) -> Result<()> {
+        instructions::scalar::fetch_and_store_scalar_result(ctx)
+    }
 
-    /// CHECK: PDA signer
-    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
-    pub mint_authority: UncheckedAccount<'info>,
+    pub fn redeem_scalar_after_final(ctx: Context<RedeemScalarAfterFinal>, side: u8, amount: u64) -> Result<()> {
+        instructions::scalar::redeem_scalar_after_final(ctx, side, amount)
+    }
 
-    /// CHECK: PDA true mint account
-    #[account(mut, seeds = [SEED_TRUE_MINT, event.key().as_ref()], bump)]
-    pub true_mint: UncheckedAccount<'info>,
+    pub fn init_conditional_market(ctx: Context<InitConditionalMarket>, parent_side: u8) -> Result<()> {
+        instructions::conditional::init_conditional_market(ctx, parent_side)
+    }
 
-    /// CHECK: PDA false mint
-    #[account(mut, seeds = [SEED_FALSE_MINT, event.key().as_ref()], bump)]
-    pub false_mint: UncheckedAccount<'info>,
+    pub fn buy_conditional_positions(ctx: Context<BuyConditionalPositions>, amount: u64) -> Result<()> {
+        instructions::conditional::buy_conditional_positions(ctx, amount)
+    }
 
-    /// CHECK: PDA system account
-    #[account(mut, seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()], bump)]
-    pub collateral_vault: UncheckedAccount<'info>,
+    pub fn redeem_conditional_pair_while_active(ctx: Context<RedeemConditionalPair>, amount: u64) -> Result<()> {
+        instructions::conditional::redeem_conditional_pair_while_active(ctx, amount)
+    }
 
-    /// CHECK: Metaplex Toke Metadata program
-    #[account(address = METADATA_PROGRAM_ID)]
-    pub metadata_program: UncheckedAccount<'info>,
+    pub fn redeem_conditional_after_final(ctx: Context<RedeemConditionalAfterFinal>, side: u8, amount: u64) -> Result<()> {
+        instructions::conditional::redeem_conditional_after_final(ctx, side, amount)
+    }
 
-    /// CHECK: Metaplex metadata PDA for TRUE mint
-    #[account(mut, seeds = [b"metadata", METADATA_PROGRAM_ID.as_ref(), true_mint.key().as_ref()], bump, seeds::program = METADATA_PROGRAM_ID)]
-    pub true_metadata: UncheckedAccount<'info>,
+    pub fn sweep_conditional_unclaimed_to_house(ctx: Context<SweepConditionalUnclaimed>) -> Result<()> {
+        instructions::conditional::sweep_conditional_unclaimed_to_house(ctx)
+    }
 
-    /// CHECK: Metaplex metadata PDA for FALSE mint
-    #[account(mut, seeds = [b"metadata", METADATA_PROGRAM_ID.as_ref(), false_mint.key().as_ref()], bump, seeds::program = METADATA_PROGRAM_ID )]
-    pub false_metadata: UncheckedAccount<'info>,
+    pub fn init_token_collateral(ctx: Context<InitTokenCollateral>) -> Result<()> {
+        instructions::token_collateral::init_token_collateral(ctx)
+    }
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    pub fn buy_positions_with_token(ctx: Context<BuyPositionsWithToken>, amount: u64) -> Result<()> {
+        instructions::token_collateral::buy_positions_with_token(ctx, amount)
+    }
 
-#[derive(Accounts)]
-pub struct BuyPositionsWithFee<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+    pub fn redeem_pair_while_active_token(ctx: Context<RedeemPairWhileActiveToken>, amount: u64) -> Result<()> {
+        instructions::token_collateral::redeem_pair_while_active_token(ctx, amount)
+    }
 
-    #[account(mut)]
-    pub event: Account<'info, Event>,
-
-    #[account(
-        mut,
-        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
-        bump
-    )]
-    pub collateral_vault: SystemAccount<'info>,
+    pub fn redeem_after_final_token(ctx: Context<RedeemAfterFinalToken>, side: u8, amount: u64) -> Result<()> {
+        instructions::token_collateral::redeem_after_final_token(ctx, side, amount)
+    }
 
-    /// CHECK: PDA mint authority signer
-    #[account(seeds = [SEED_MINT_AUTH, event.key().as_ref()], bump)]
-    pub mint_authority: UncheckedAccount<'info>,
-
-    #[account(mut, constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint)]
-    pub true_mint: Account<'info, Mint>,
-    #[account(mut, constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint)]
-    pub false_mint: Account<'info, Mint>,
+    pub fn claim_creator_commission_token<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimCreatorCommissionToken<'info>>,
+    ) -> Result<()> {
+        instructions::token_collateral::claim_creator_commission_token(ctx)
+    }
 
-    #[account(mut)]
-    pub user_true_ata: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub user_false_ata: Account<'info, TokenAccount>,
-
-    // ---- Truth network read + vault ----
-    #[account(mut)]
-    pub truth_network_question: Account<'info, Question>,
-
-    /// CHECK: vault is system-owned PDA in Truth-Network (no data), but must be mutable
-    #[account(mut)]
-    pub truth_network_vault: UncheckedAccount<'info>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
-
-
-#[derive(Accounts)]
-pub struct FetchAndStoreWinner<'info> {
-    #[account(mut)]
-    pub event: Account<'info, Event>,
+    pub fn sweep_house_commission_token(ctx: Context<SweepHouseToken>) -> Result<()> {
+        instructions::token_collateral::sweep_house_commission_token(ctx)
+    }
 
-    #[account(mut)]
-    pub truth_network_question: Account<'info, Question>,
+    pub fn sweep_unclaimed_to_house_token(ctx: Context<SweepHouseToken>) -> Result<()> {
+        instructions::token_collateral::sweep_unclaimed_to_house_token(ctx)
+    }
 
-    pub truth_network_program: Program<'info, TruthNetwork>,
+    pub fn claim_truth_commission_token(ctx: Context<ClaimTruthCommissionToken>) -> Result<()> {
+        instructions::token_collateral::claim_truth_commission_token(ctx)
+    }
 
-    /// CHECK: Fixed house wallet
-    #[account(mut, address = HOUSE_WALLET)]
-    pub house_treasury: AccountInfo<'info>,
+    pub fn create_event_mints_2022(ctx: Context<CreateEventMints2022>) -> Result<()> {
+        instructions::token_2022::create_event_mints_2022(ctx)
+    }
 
-    /// CHECK: Collateral vault PDA
-    #[account(
-        mut,
-        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
-        bump
-    )]
-    pub collateral_vault: AccountInfo<'info>,
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        instructions::config::initialize_config(ctx, params)
+    }
 
-    pub system_program: Program<'info, System>,
-}
+    pub fn propose_config_change(ctx: Context<ProposeConfigChange>, params: ConfigParams) -> Result<()> {
+        instructions::config::propose_config_change(ctx, params)
+    }
 
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        instructions::config::execute_config_change(ctx)
+    }
 
+    pub fn cancel_config_change(_ctx: Context<CancelConfigChange>) -> Result<()> {
+        instructions::config::cancel_config_change(_ctx)
+    }
 
-#[derive(Accounts)]
-pub struct RedeemPairWhileActive<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    #[account(mut)]
-    pub event: Account<'info, Event>,
-
-    #[account(
-        mut,
-        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
-        bump,
-        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
-    )]
-    pub collateral_vault: SystemAccount<'info>,
+    pub fn init_house_treasury(ctx: Context<InitHouseTreasury>) -> Result<()> {
+        instructions::treasury::init_house_treasury(ctx)
+    }
 
-    #[account(
-        mut,
-        constraint = event.true_mint == true_mint.key() @ PredictError::InvalidMint
-    )]
-    pub true_mint: Account<'info, Mint>,
+    pub fn distribute_treasury<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeTreasury<'info>>,
+    ) -> Result<()> {
+        instructions::treasury::distribute_treasury(ctx)
+    }
 
-    #[account(
-        mut,
-        constraint = event.false_mint == false_mint.key() @ PredictError::InvalidMint
-    )]
-    pub false_mint: Account<'info, Mint>,
-
-    #[account(
-        mut,
-        constraint = user_true_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
-        constraint = user_true_ata.mint == true_mint.key() @ PredictError::InvalidTokenAccountMint
-    )]
-    pub user_true_ata: Account<'info, TokenAccount>,
+    pub fn distribute_treasury_token<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DistributeTreasuryToken<'info>>,
+    ) -> Result<()> {
+        instructions::treasury::distribute_treasury_token(ctx)
+    }
 
-    #[account(
-        mut,
-        constraint = user_false_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
-        constraint = user_false_ata.mint == false_mint.key() @ PredictError::InvalidTokenAccountMint
-    )]
-    pub user_false_ata: Account<'info, TokenAccount>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct RedeemWinnerAfterFinal<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+    pub fn set_protocol_pause(
+        ctx: Context<UpdateConfig>,
+        flags: u8,
+        reason: String,
+        duration_secs: i64,
+    ) -> Result<()> {
+        instructions::pause::set_protocol_pause(ctx, flags, reason, duration_secs)
+    }
 
-    #[account(mut)]
-    pub event: Account<'info, Event>,
+    pub fn set_event_pause(
+        ctx: Context<SetEventPause>,
+        flags: u8,
+        reason: String,
+        duration_secs: i64,
+    ) -> Result<()> {
+        instructions::pause::set_event_pause(ctx, flags, reason, duration_secs)
+    }
 
-    #[account(
-        mut,
-        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
-        bump,
-        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
-    )]
-    pub collateral_vault: SystemAccount<'info>,
+    pub fn set_categorical_pause(
+        ctx: Context<SetCategoricalPause>,
+        flags: u8,
+        reason: String,
+        duration_secs: i64,
+    ) -> Result<()> {
+        instructions::categorical::set_categorical_pause(ctx, flags, reason, duration_secs)
+    }
 
-    // Winning mint (TRUE or FALSE)
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-
-    // User ATA for winning mint
-    #[account(
-        mut,
-        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
-        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
-    )]
-    pub user_ata: Account<'info, TokenAccount>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
+    pub fn nominate_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        instructions::config::nominate_admin(ctx, new_admin)
+    }
 
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::config::accept_admin(ctx)
+    }
 
-#[derive(Accounts)]
-pub struct RedeemNoWinnerAfterFinal<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-
-    #[account(mut)]
-    pub event: Account<'info, Event>,
-
-    #[account(
-        mut,
-        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
-        bump,
-        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
-    )]
-    pub collateral_vault: SystemAccount<'info>,
-
-    // TRUE or FALSE mint
-    #[account(mut)]
-    pub mint: Account<'info, Mint>,
-
-    // User ATA for that mint
-    #[account(
-        mut,
-        constraint = user_ata.owner == user.key() @ PredictError::InvalidTokenAccountOwner,
-        constraint = user_ata.mint == mint.key() @ PredictError::InvalidTokenAccountMint
-    )]
-    pub user_ata: Account<'info, TokenAccount>,
-
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct ClaimCreatorCommission<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
-
-    #[account(mut)]
-    pub event: Account<'info, Event>,
-
-    #[account(
-        mut,
-        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
-        bump,
-        constraint = event.collateral_vault == collateral_vault.key() @ PredictError::InvalidVault
-    )]
-    pub collateral_vault: SystemAccount<'info>,
-
-    pub system_program: Program<'info, System>,
-}
-
-#[derive(Accounts)]
-pub struct SweepUnclaimedToHouse<'info> {
-    #[account(mut)]
-    pub event: Account<'info, Event>,
+    pub fn set_fee_curve(
+        ctx: Context<SetFeeCurve>,
+        curve: u8,
+        curve_start: i64,
+        max_fee_bps: u16,
+        steps: u8,
+        early_bird_end_time: i64,
+        early_bird_discount_bps: u16,
+    ) -> Result<()> {
+        instructions::creator::set_fee_curve(ctx, curve, curve_start, max_fee_bps, steps, early_bird_end_time, early_bird_discount_bps)
+    }
 
-    /// CHECK: Fixed house wallet
-    #[account(mut, address = HOUSE_WALLET)]
-    pub house_treasury: AccountInfo<'info>,
+    pub fn set_creator_payouts(
+        ctx: Context<SetCreatorPayouts>,
+        payout: Pubkey,
+        co_creators: Vec<CreatorShare>,
+    ) -> Result<()> {
+        instructions::creator::set_creator_payouts(ctx, payout, co_creators)
+    }
 
-    /// CHECK: Collateral vault PDA
-    #[account(
-        mut,
-        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
-        bump
-    )]
-    pub collateral_vault: AccountInfo<'info>,
-
-    pub system_program: Program<'info, System>,
-}
+    pub fn set_creator_vesting(
+        ctx: Context<SetCreatorVesting>,
+        vesting: u8,
+        tranches: u8,
+        holdback_bps: u16,
+    ) -> Result<()> {
+        instructions::creator::set_creator_vesting(ctx, vesting, tranches, holdback_bps)
+    }
 
-#[derive(Accounts)]
-pub struct DeleteEvent<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
-
-    #[account(
-        mut,
-        close = creator,
-        constraint = event.creator == creator.key() @ PredictError::Unauthorized
-    )]
-    pub event: Account<'info, Event>,
+    pub fn init_referral_account(ctx: Context<InitReferralAccount>) -> Result<()> {
+        instructions::referral::init_referral_account(ctx)
+    }
 
-    /// CHECK: Collateral vault PDA
-    #[account(
-        mut,
-        seeds = [SEED_COLLATERAL_VAULT, event.key().as_ref()],
-        bump
-    )]
-    pub collateral_vault: AccountInfo<'info>,
+    pub fn claim_referral_rewards(ctx: Context<ClaimReferralRewards>) -> Result<()> {
+        instructions::referral::claim_referral_rewards(ctx)
+    }
 
-    pub system_program: Program<'info, System>,
+    pub fn migrate_event(ctx: Context<MigrateEvent>) -> Result<()> {
+        instructions::event::migrate_event(ctx)
+    }
 }
 
 
@@ -1761,6 +2750,142 @@ pub enum PredictError {
     InvalidCategory,
     #[msg("Invalid resolved_at timestamp")]
     InvalidResolvedAt,
+    #[msg("Invalid side (1 = TRUE, 2 = FALSE)")]
+    InvalidSide,
+    #[msg("Invalid LMSR liquidity parameter")]
+    InvalidLiquidityParam,
+    #[msg("Slippage limit exceeded")]
+    SlippageExceeded,
+    #[msg("LMSR market does not hold enough of this side")]
+    LmsrInsufficientInventory,
+    #[msg("LMSR market would be insolvent")]
+    LmsrInsolvent,
+    #[msg("LMSR market account required for this event")]
+    LmsrMarketRequired,
+    #[msg("Invalid fee bps")]
+    InvalidFeeBps,
+    #[msg("Pool has no liquidity")]
+    PoolEmpty,
+    #[msg("Initial liquidity too small")]
+    InsufficientInitialLiquidity,
+    #[msg("Insufficient LP token balance")]
+    InsufficientLpBalance,
+    #[msg("Invalid order price")]
+    InvalidPrice,
+    #[msg("Order notional below the minimum")]
+    OrderTooSmall,
+    #[msg("Order book is full")]
+    OrderBookFull,
+    #[msg("Order not found")]
+    OrderNotFound,
+    #[msg("No crossing orders to match")]
+    NothingToMatch,
+    #[msg("No fills could be consumed")]
+    NothingToConsume,
+    #[msg("Invalid open orders account")]
+    InvalidOpenOrders,
+    #[msg("Invalid Raydium pool for this event")]
+    InvalidRaydiumPool,
+    #[msg("Invalid number of outcomes")]
+    InvalidOutcomeCount,
+    #[msg("Invalid outcome index")]
+    InvalidOutcomeIndex,
+    #[msg("Duplicate Truth question")]
+    DuplicateTruthQuestion,
+    #[msg("Outcome mints not created yet")]
+    OutcomeMintsNotCreated,
+    #[msg("Insufficient outcome token balance")]
+    InsufficientOutcomeBalance,
+    #[msg("Outcome already finalized")]
+    OutcomeAlreadyFinalized,
+    #[msg("Not every outcome is finalized yet")]
+    OutcomesNotFinalized,
+    #[msg("Invalid remaining accounts")]
+    InvalidRemainingAccounts,
+    #[msg("Invalid event kind for this action")]
+    InvalidEventKind,
+    #[msg("Invalid scalar range")]
+    InvalidScalarRange,
+    #[msg("Event mints already created")]
+    MintsAlreadyCreated,
+    #[msg("Scalar value report window is closed")]
+    ScalarReportWindowClosed,
+    #[msg("Scalar value already reported")]
+    ScalarValueAlreadyReported,
+    #[msg("Invalid parent event")]
+    InvalidParentEvent,
+    #[msg("Parent event is not resolved yet")]
+    ParentNotResolved,
+    #[msg("This event uses SPL token collateral")]
+    TokenCollateralEvent,
+    #[msg("This event uses native SOL collateral")]
+    NativeCollateralEvent,
+    #[msg("Token vault account required for this event")]
+    TokenVaultRequired,
+    #[msg("Truth fee recipient is not configured")]
+    TruthRecipientNotConfigured,
+    #[msg("Token program and mint authority required to close Token-2022 mints")]
+    TokenProgramRequired,
+    #[msg("Protocol config value out of bounds")]
+    InvalidConfig,
+    #[msg("Fee schedule outside protocol bounds")]
+    InvalidFeeSchedule,
+    #[msg("Invalid fee curve parameters")]
+    InvalidFeeCurve,
+    #[msg("Fee curve can only be set before the first buy")]
+    FeeCurveLocked,
+    #[msg("Referrer cannot refer themselves")]
+    SelfReferral,
+    #[msg("Referral vault required when a referrer is passed")]
+    ReferralVaultRequired,
+    #[msg("Invalid creator payout address or co-creator shares")]
+    InvalidCreatorShares,
+    #[msg("Creator payouts can only be changed before the first buy")]
+    CreatorPayoutsLocked,
+    #[msg("Invalid creator commission vesting parameters")]
+    InvalidVesting,
+    #[msg("Config change timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("No treasury beneficiaries configured")]
+    TreasuryNotConfigured,
+    #[msg("Protocol is paused for this action")]
+    ProtocolPaused,
+    #[msg("Event is halted for this action")]
+    EventPaused,
+    #[msg("Invalid pause flags, duration or reason")]
+    InvalidPause,
+    #[msg("Emergency exit is not available yet")]
+    EmergencyExitNotAvailable,
+    #[msg("Holders have started an emergency exit; the event can no longer resolve")]
+    EmergencyExitActive,
+    #[msg("Result is under dispute")]
+    DisputeOpen,
+    #[msg("Result is still inside its dispute window")]
+    DisputeWindowActive,
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+    #[msg("No open dispute for this event")]
+    NoOpenDispute,
+    #[msg("A new pause needs the cooldown after the previous one")]
+    PauseCooldownActive,
+    #[msg("Dispute and challenger accounts are required")]
+    DisputeAccountRequired,
+    #[msg("The dispute timed out, settle it with settle_expired_dispute")]
+    DisputeTimedOut,
+    #[msg("The dispute has not timed out yet")]
+    DisputeNotTimedOut,
+    #[msg("Revote question must repeat the event question and options")]
+    RevoteQuestionMismatch,
+    #[msg("Revote is not available for this event")]
+    RevoteNotAllowed,
+    #[msg("Maximum number of revote rounds reached")]
+    MaxRevoteRoundsReached,
+    #[msg("Result is still inside its revote window")]
+    RevoteWindowActive,
+    #[msg("Event already uses the current layout")]
+    EventAlreadyMigrated,
+    #[msg("Account is not a legacy event")]
+    InvalidEventLayout,
 }
 
 
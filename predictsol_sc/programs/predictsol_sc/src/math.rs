@@ -0,0 +1,302 @@
+use crate::*;
+
+// ============================================================
+// Fees
+// ============================================================
+#[inline(always)]
+pub(crate) fn payout_after_fee(amount: u64, redeem_fee_bps: u16) -> Result<u64> {
+    let fee = amount
+        .checked_mul(redeem_fee_bps as u64).ok_or_else(|| error!(PredictError::MathOverflow))?
+        .checked_div(BPS_DENOM).ok_or_else(|| error!(PredictError::MathOverflow))?;
+    amount.checked_sub(fee).ok_or_else(|| error!(PredictError::MathOverflow))
+}
+
+pub(crate) fn compute_fee_splits(
+    lamports: u64,
+    buy_fee_bps: u16,
+    truth_share_bps: u16,
+    creator_share_bps: u16,
+) -> Result<(u64, u64, u64, u64, u64)> {
+    let fee = mul_div_floor(lamports, buy_fee_bps as u64, BPS_DENOM)?;
+    let net = lamports.checked_sub(fee).ok_or(PredictError::MathOverflow)?;
+
+    let truth_cut = mul_div_floor(fee, truth_share_bps as u64, BPS_DENOM)?;
+    let creator_cut = mul_div_floor(fee, creator_share_bps as u64, BPS_DENOM)?;
+    let house_cut = fee
+        .checked_sub(truth_cut.checked_add(creator_cut).ok_or(PredictError::MathOverflow)?)
+        .ok_or(PredictError::MathOverflow)?;
+
+    Ok((fee, truth_cut, creator_cut, house_cut, net))
+}
+
+// Buy fee in force at `now`: early-bird discount first, then the curve toward bet_end_time.
+pub(crate) fn current_buy_fee_bps(ev: &Event, now: i64) -> Result<u16> {
+    let base = ev.buy_fee_bps as u64;
+
+    if now < ev.early_bird_end_time {
+        let discount = mul_div_floor(base, ev.early_bird_discount_bps as u64, BPS_DENOM)?;
+        return Ok(base.saturating_sub(discount) as u16);
+    }
+
+    if ev.fee_curve == FEE_CURVE_FLAT || now <= ev.fee_curve_start {
+        return Ok(ev.buy_fee_bps);
+    }
+
+    let span = ev.bet_end_time.saturating_sub(ev.fee_curve_start).max(1) as u64;
+    let elapsed = (now.saturating_sub(ev.fee_curve_start) as u64).min(span);
+    let rise = (ev.fee_curve_max_bps as u64).saturating_sub(base);
+
+    let extra = if ev.fee_curve == FEE_CURVE_LINEAR {
+        mul_div_floor(rise, elapsed, span)?
+    } else {
+        // stepwise: `steps` equal jumps, the last one reaching the max
+        let steps = ev.fee_curve_steps.max(1) as u64;
+        let step = mul_div_floor(elapsed, steps, span)?.saturating_add(1).min(steps);
+        mul_div_floor(rise, step, steps)?
+    };
+
+    Ok(base.saturating_add(extra) as u16)
+}
+
+// ============================================================
+// Integer helpers
+// ============================================================
+// floor(a * b / c) in u128
+pub(crate) fn mul_div_floor(a: u64, b: u64, c: u64) -> Result<u64> {
+    require!(c > 0, PredictError::MathOverflow);
+    let v = (a as u128).checked_mul(b as u128).ok_or(PredictError::MathOverflow)? / c as u128;
+    u64::try_from(v).map_err(|_| error!(PredictError::MathOverflow))
+}
+
+// ceil(a * b / c) in u128
+pub(crate) fn mul_div_ceil(a: u64, b: u64, c: u64) -> Result<u64> {
+    require!(c > 0, PredictError::MathOverflow);
+    let v = (a as u128).checked_mul(b as u128).ok_or(PredictError::MathOverflow)?.div_ceil(c as u128);
+    u64::try_from(v).map_err(|_| error!(PredictError::MathOverflow))
+}
+
+pub(crate) fn isqrt_u128(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method, starting above the root
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+// ============================================================
+// LMSR cost function
+// ============================================================
+// Fixed-point math for the cost function: unsigned Q64.64 in u128, no floats.
+pub(crate) const Q64_ONE: u128 = 1 << 64;
+
+pub(crate) const Q64_LN2: u128 = 0xB172_17F7_D1CF_79AB; // ln(2) * 2^64, rounded down
+
+// e^-x is below one ulp past this point
+pub(crate) const Q64_EXP_NEG_CUTOFF: u128 = 45;
+// Bound on the accumulated truncation error of exp/ln below, in ulps
+pub(crate) const Q64_MAX_ERROR_ULPS: u128 = 1024;
+
+// (a * b) >> 64 without overflowing u128, rounded down
+pub(crate) fn q64_mul(a: u128, b: u128) -> u128 {
+    let (a_hi, a_lo) = (a >> 64, a & (Q64_ONE - 1));
+    let (b_hi, b_lo) = (b >> 64, b & (Q64_ONE - 1));
+    ((a_hi * b_hi) << 64) + a_hi * b_lo + a_lo * b_hi + ((a_lo * b_lo) >> 64)
+}
+
+// e^-x for x >= 0 in Q64: x = k * ln2 + r, e^-r by its Taylor series, then >> k
+pub(crate) fn q64_exp_neg(x: u128) -> u128 {
+    if x >= Q64_EXP_NEG_CUTOFF * Q64_ONE {
+        return 0;
+    }
+    let k = x / Q64_LN2;
+    let r = x - k * Q64_LN2;
+
+    let (mut pos, mut neg) = (Q64_ONE, 0u128);
+    let mut term = Q64_ONE;
+    let mut n = 1u128;
+    loop {
+        term = q64_mul(term, r) / n;
+        if term == 0 {
+            break;
+        }
+        if n % 2 == 1 {
+            neg += term;
+        } else {
+            pos += term;
+        }
+        n += 1;
+    }
+    (pos - neg) >> k
+}
+
+// ln(1 + y) for 0 <= y <= 1 in Q64, as 2 * atanh(y / (2 + y))
+pub(crate) fn q64_ln1p(y: u128) -> u128 {
+    if y >= Q64_ONE {
+        return Q64_LN2;
+    }
+    let z = (y << 64) / (2 * Q64_ONE + y);
+    let z2 = q64_mul(z, z);
+
+    let mut sum = z;
+    let mut power = z;
+    let mut n = 3u128;
+    loop {
+        power = q64_mul(power, z2);
+        let term = power / n;
+        if term == 0 {
+            break;
+        }
+        sum += term;
+        n += 2;
+    }
+    2 * sum
+}
+
+// b * value for a Q64 value, in lamports; the math error bound is pushed in the
+// requested direction so the rounding always favours the maker.
+pub(crate) fn q64_scale_lamports(b: u64, value: u128, round_up: bool) -> Result<u128> {
+    let exact = (b as u128).checked_mul(value).ok_or(PredictError::MathOverflow)?;
+    let err = (b as u128).checked_mul(Q64_MAX_ERROR_ULPS).ok_or(PredictError::MathOverflow)?;
+    if round_up {
+        let v = exact.checked_add(err).ok_or(PredictError::MathOverflow)?;
+        Ok(v.div_ceil(Q64_ONE))
+    } else {
+        Ok(exact.saturating_sub(err) / Q64_ONE)
+    }
+}
+
+// Cost function C(q) = b * ln(e^(q_true/b) + e^(q_false/b)), in lamports.
+// Computed as max(q) + b * ln(1 + e^(-|q_true - q_false| / b)), rounded as requested.
+#[inline(never)]
+pub(crate) fn lmsr_cost(b: u64, q_true: u64, q_false: u64, round_up: bool) -> Result<u128> {
+    require!(b > 0, PredictError::InvalidLiquidityParam);
+    let m = q_true.max(q_false) as u128;
+    let d = q_true.abs_diff(q_false) as u128;
+
+    let e = if d >= Q64_EXP_NEG_CUTOFF * b as u128 {
+        0
+    } else {
+        q64_exp_neg((d << 64) / b as u128)
+    };
+    let spread = q64_scale_lamports(b, q64_ln1p(e), round_up)?;
+
+    m.checked_add(spread).ok_or_else(|| error!(PredictError::MathOverflow))
+}
+
+// Max possible loss of a binary LMSR maker is b * ln(2), rounded up.
+pub(crate) fn lmsr_subsidy(b: u64) -> Result<u64> {
+    let subsidy = q64_scale_lamports(b, Q64_LN2, true)?;
+    u64::try_from(subsidy).map_err(|_| error!(PredictError::MathOverflow))
+}
+
+pub(crate) fn lmsr_quantities_after(
+    market: &LmsrMarket,
+    side: u8,
+    amount: u64,
+    is_buy: bool,
+) -> Result<(u64, u64)> {
+    let apply = |q: u64| -> Result<u64> {
+        if is_buy {
+            q.checked_add(amount).ok_or_else(|| error!(PredictError::MathOverflow))
+        } else {
+            q.checked_sub(amount).ok_or_else(|| error!(PredictError::LmsrInsufficientInventory))
+        }
+    };
+
+    match side {
+        1 => Ok((apply(market.q_true)?, market.q_false)),
+        2 => Ok((market.q_true, apply(market.q_false)?)),
+        _ => err!(PredictError::InvalidSide),
+    }
+}
+
+// What the user pays to buy `amount` of `side` (rounded up, in favour of the maker).
+pub(crate) fn lmsr_buy_cost(market: &LmsrMarket, side: u8, amount: u64) -> Result<(u64, u64, u64)> {
+    let (q_true, q_false) = lmsr_quantities_after(market, side, amount, true)?;
+
+    let before = lmsr_cost(market.b, market.q_true, market.q_false, false)?;
+    let after = lmsr_cost(market.b, q_true, q_false, true)?;
+
+    let cost = after.checked_sub(before).ok_or(PredictError::MathOverflow)?;
+    let cost = u64::try_from(cost).map_err(|_| error!(PredictError::MathOverflow))?;
+
+    Ok((cost.max(1), q_true, q_false))
+}
+
+// What the user receives for selling `amount` of `side` back (rounded down).
+pub(crate) fn lmsr_sell_proceeds(market: &LmsrMarket, side: u8, amount: u64) -> Result<(u64, u64, u64)> {
+    let (q_true, q_false) = lmsr_quantities_after(market, side, amount, false)?;
+
+    let before = lmsr_cost(market.b, market.q_true, market.q_false, false)?;
+    let after = lmsr_cost(market.b, q_true, q_false, true)?;
+
+    let proceeds = before.saturating_sub(after);
+    let proceeds = u64::try_from(proceeds).map_err(|_| error!(PredictError::MathOverflow))?;
+
+    Ok((proceeds, q_true, q_false))
+}
+
+// What the maker owes to holders of tokens it sold, given the final result.
+pub(crate) fn lmsr_liability(ev: &Event, market: &LmsrMarket) -> Result<u64> {
+    if ev.result_status == RESULT_RESOLVED_WINNER {
+        return match ev.winning_option {
+            1 => Ok(market.q_true),
+            2 => Ok(market.q_false),
+            _ => err!(PredictError::InvalidWinningOption),
+        };
+    }
+
+    // No-winner cases pay 0.5 per token on both sides (rounded up to stay safe)
+    let both = market.q_true.checked_add(market.q_false).ok_or(PredictError::MathOverflow)?;
+    Ok(both.div_ceil(2))
+}
+
+// ============================================================
+// Constant-product pools
+// ============================================================
+// out = reserve_out * in_after_fee / (reserve_in + in_after_fee)
+// The full amount_in is added to reserve_in, so the fee stays with the LPs.
+pub(crate) fn cp_amount_out(reserve_in: u64, reserve_out: u64, amount_in: u64, fee_bps: u16) -> Result<u64> {
+    require!(reserve_in > 0 && reserve_out > 0, PredictError::PoolEmpty);
+
+    let in_after_fee = (amount_in as u128)
+        .checked_mul(BPS_DENOM as u128 - fee_bps as u128)
+        .ok_or(PredictError::MathOverflow)?
+        / BPS_DENOM as u128;
+
+    let out = (reserve_out as u128)
+        .checked_mul(in_after_fee)
+        .ok_or(PredictError::MathOverflow)?
+        .checked_div((reserve_in as u128).checked_add(in_after_fee).ok_or(PredictError::MathOverflow)?)
+        .ok_or(PredictError::MathOverflow)?;
+
+    u64::try_from(out).map_err(|_| error!(PredictError::MathOverflow))
+}
+
+// ============================================================
+// Scalar payouts
+// ============================================================
+// LONG payout per whole token, clamped to [0, LAMPORTS_PER_TOKEN]; SHORT gets the rest.
+pub(crate) fn scalar_long_payout_per_token(lower: i64, upper: i64, value: i64) -> Result<u64> {
+    if value <= lower {
+        return Ok(0);
+    }
+    if value >= upper {
+        return Ok(LAMPORTS_PER_TOKEN);
+    }
+
+    let num = (value as i128 - lower as i128) as u128;
+    let den = (upper as i128 - lower as i128) as u128;
+    let ppt = num
+        .checked_mul(LAMPORTS_PER_TOKEN as u128)
+        .ok_or(PredictError::MathOverflow)?
+        / den;
+
+    u64::try_from(ppt).map_err(|_| error!(PredictError::MathOverflow))
+}
@@ -0,0 +1,508 @@
+use crate::*;
+
+#[account]
+pub struct EventCounter {
+    pub creator: Pubkey,
+    pub count: u64,
+}
+
+#[account]
+pub struct Event {
+    pub creator: Pubkey,
+    pub event_id: u64,
+    pub truth_question: Pubkey,
+    pub title: String,
+
+    pub bet_end_time: i64,
+    pub commit_end_time: i64,
+    pub reveal_end_time: i64,
+    pub created_at: i64,
+
+    pub total_collateral_lamports: u64,
+    pub total_issued_per_side: u64,
+    pub collateral_vault: Pubkey,
+    pub true_mint: Pubkey,
+    pub false_mint: Pubkey,
+
+    pub resolved: bool,
+    pub winning_option: u8,
+    pub winning_percent_bps: u16,    
+    pub votes_option_1: u64,         
+    pub votes_option_2: u64,         
+    pub consensus_threshold_bps: u16,
+    pub resolved_at: i64,            
+    pub result_status: u8,    
+
+    pub total_truth_commission_sent: u64,
+    pub pending_creator_commission: u64,
+    pub pending_house_commission: u64,
+
+    pub unclaimed_swept: bool,
+    pub swept_at: i64,
+
+    pub outstanding_true: u64,
+    pub outstanding_false: u64,
+    pub category: u8,
+
+    pub has_lmsr: bool,
+    pub raydium_pool: Pubkey,   // TRUE/FALSE Raydium CPMM pool for single-side buys
+    pub kind: u8,               // EVENT_KIND_*
+    pub collateral_mint: Pubkey,    // default = native SOL
+    pub collateral_decimals: u8,
+
+    // ProtocolConfig snapshot taken at creation
+    pub house_wallet: Pubkey,
+    pub buy_fee_bps: u16,
+    pub redeem_fee_bps: u16,
+    pub unclaimed_sweep_delay_secs: i64,
+
+    // buy fee split, bps of the fee
+    pub truth_share_bps: u16,
+    pub creator_share_bps: u16,
+    pub house_share_bps: u16,
+
+    // dynamic buy fee (FEE_CURVE_*), see current_buy_fee_bps
+    pub fee_curve: u8,
+    pub fee_curve_start: i64,
+    pub fee_curve_max_bps: u16,
+    pub fee_curve_steps: u8,
+    pub early_bird_end_time: i64,
+    pub early_bird_discount_bps: u16,
+
+    // referrer part of the house cut (bps of house cut) and total paid out for this event
+    pub referral_share_bps: u16,
+    pub total_referral_commission: u64,
+
+    // commission returned to holders (creator's on a no-winner result with clawback,
+    // creator's and house's on a void), shared pro rata over the position tokens
+    // outstanding at resolution
+    pub creator_clawback: bool,
+    pub forfeited_commission: u64,
+    pub forfeit_base_units: u64,
+
+    // creator commission recipients: co-creators get their share, creator_payout the rest
+    pub creator_payout: Pubkey,
+    pub co_creator_count: u8,
+    pub co_creators: [CreatorShare; MAX_CO_CREATORS],
+
+    // creator commission vesting (VESTING_*), see claimable_creator_commission
+    pub creator_vesting: u8,
+    pub vesting_tranches: u8,
+    pub vesting_holdback_bps: u16,
+    pub claimed_creator_commission: u64,
+
+    // lamports paid to the house wallet / treasury by this event
+    pub house_lamports_paid: u64,
+
+    // per-event emergency halt
+    pub pause: PauseState,
+
+    // escape hatch for events that never resolve, see emergency_exit
+    pub emergency_exit_delay_secs: i64,
+    pub emergency_exit_active: bool,
+
+    // dispute window snapshot; redemptions wait for dispute_deadline and any open dispute
+    pub dispute_window_secs: i64,
+    pub dispute_bond_lamports: u64,
+    pub dispute_deadline: i64,
+    pub dispute_status: u8,     // DISPUTE_*
+
+    // revotes on tie / below-threshold results; earlier rounds are kept in revote_history
+    pub revote_max_rounds: u8,
+    pub revote_funding_bps: u16,    // of the pending creator commission, added to the new question's reward
+    pub revote_round: u8,
+    pub revote_history: [RevoteRound; MAX_REVOTE_ROUNDS],
+
+    // set by every buy path (pair, token, conditional and LMSR); creator settings lock on it
+    pub has_buys: bool,
+
+    pub layout_version: u8,     // EVENT_LAYOUT_VERSION
+}
+
+// Event as stored before the layout version was introduced, read by migrate_event
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyEvent {
+    pub creator: Pubkey,
+    pub event_id: u64,
+    pub truth_question: Pubkey,
+    pub title: String,
+
+    pub bet_end_time: i64,
+    pub commit_end_time: i64,
+    pub reveal_end_time: i64,
+    pub created_at: i64,
+
+    pub total_collateral_lamports: u64,
+    pub total_issued_per_side: u64,
+    pub collateral_vault: Pubkey,
+    pub true_mint: Pubkey,
+    pub false_mint: Pubkey,
+
+    pub resolved: bool,
+    pub winning_option: u8,
+    pub winning_percent_bps: u16,
+    pub votes_option_1: u64,
+    pub votes_option_2: u64,
+    pub consensus_threshold_bps: u16,
+    pub resolved_at: i64,
+    pub result_status: u8,
+
+    pub total_truth_commission_sent: u64,
+    pub pending_creator_commission: u64,
+    pub pending_house_commission: u64,
+
+    pub unclaimed_swept: bool,
+    pub swept_at: i64,
+
+    pub outstanding_true: u64,
+    pub outstanding_false: u64,
+    pub category: u8,
+}
+
+#[account]
+pub struct LmsrMarket {
+    pub event: Pubkey,
+    pub b: u64,                 // liquidity parameter, in lamports
+    pub q_true: u64,            // TRUE tokens sold by the maker (net)
+    pub q_false: u64,           // FALSE tokens sold by the maker (net)
+    pub subsidy_lamports: u64,  // b * ln(2), paid by the creator
+    pub reserve_lamports: u64,  // subsidy + costs received - proceeds paid
+    pub bump: u8,
+}
+
+#[account]
+pub struct Pool {
+    pub event: Pubkey,
+    pub side: u8,               // 1 = TRUE/SOL, 2 = FALSE/SOL
+    pub fee_bps: u16,           // swap fee, stays in the pool for LPs
+    pub token_reserve: u64,
+    pub sol_reserve: u64,       // excludes the sol vault rent keep
+    pub lp_supply: u64,
+    pub lp_mint: Pubkey,
+    pub token_vault: Pubkey,
+    pub sol_vault: Pubkey,
+    pub bump: u8,
+    pub sol_vault_bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Order {
+    pub id: u64,
+    pub owner: Pubkey,
+    pub token_side: u8,         // 1 = TRUE, 2 = FALSE
+    pub is_bid: bool,
+    pub price: u64,             // lamports per whole token
+    pub remaining: u64,         // token base units
+    pub locked_lamports: u64,   // bids only: escrow still backing this order
+}
+
+// An evicted order is queued as a fill from its owner to itself that only refunds escrow.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Fill {
+    pub bidder: Pubkey,
+    pub asker: Pubkey,
+    pub token_side: u8,
+    pub quantity: u64,          // tokens to the bidder
+    pub lamports: u64,          // lamports to the asker
+    pub bid_refund: u64,        // unused bid escrow back to the bidder
+}
+
+#[account]
+pub struct OrderBook {
+    pub event: Pubkey,
+    pub next_order_id: u64,
+    pub true_escrow: Pubkey,
+    pub false_escrow: Pubkey,
+    pub bump: u8,
+    pub escrow_bump: u8,
+    pub orders: Vec<Order>,     // max ORDER_BOOK_MAX_ORDERS
+    pub fills: Vec<Fill>,       // event queue, max ORDER_BOOK_MAX_FILLS
+}
+
+#[account]
+pub struct OpenOrders {
+    pub event: Pubkey,
+    pub owner: Pubkey,
+    pub free_lamports: u64,
+    pub free_true: u64,
+    pub free_false: u64,
+    pub bump: u8,
+}
+
+// N-outcome event settled through one Truth question per outcome. Shares the fee schedule,
+// commission vault, clawback, volume tiers, pauses, unclaimed sweep and emergency exit with
+// binary events. Out of scope on purpose: vesting, payout address / co-creators, fee curve,
+// referrals, void, dispute window, revote, token collateral, Token-2022 and the makers
+// (LMSR, pools, order book, single-side); those only exist on the binary Event.
+#[account]
+pub struct CategoricalEvent {
+    pub creator: Pubkey,
+    pub event_id: u64,
+    pub title: String,
+    pub category: u8,
+
+    pub bet_end_time: i64,
+    pub commit_end_time: i64,
+    pub reveal_end_time: i64,
+    pub created_at: i64,
+
+    pub outcome_count: u8,
+    pub collateral_vault: Pubkey,
+    pub outcome_mints: Vec<Pubkey>,     // one per outcome
+    pub truth_questions: Vec<Pubkey>,   // one yes/no question per outcome
+    pub outcome_results: Vec<u8>,       // OUTCOME_*
+    pub votes_yes: Vec<u64>,
+    pub votes_no: Vec<u64>,
+    pub outstanding: Vec<u64>,
+
+    pub total_collateral_lamports: u64,
+    pub total_issued_per_outcome: u64,
+    pub pending_truth_commission: u64,
+    pub total_truth_commission_sent: u64,
+    pub pending_creator_commission: u64,
+    pub pending_house_commission: u64,
+    pub consensus_threshold_bps: u16,
+
+    // creator commission forfeited to the holders on a no-winner result
+    pub creator_clawback: bool,
+    pub forfeited_commission: u64,
+    pub forfeit_base_units: u64,
+
+    pub resolved: bool,
+    pub winning_outcome: u8,
+    pub result_status: u8,
+    pub resolved_at: i64,
+    pub unclaimed_swept: bool,
+    pub swept_at: i64,
+
+    // ProtocolConfig snapshot taken at creation
+    pub house_wallet: Pubkey,
+    pub fee_schedule: FeeSchedule,
+    pub redeem_fee_bps: u16,
+    pub unclaimed_sweep_delay_secs: i64,
+    pub emergency_exit_delay_secs: i64,
+
+    // lamports paid to the house wallet / treasury by this event
+    pub house_lamports_paid: u64,
+
+    pub pause: PauseState,
+}
+
+#[account]
+pub struct ScalarMarket {
+    pub event: Pubkey,
+    pub lower: i64,
+    pub upper: i64,
+    pub reported_value: i64,
+    pub value_reported: bool,
+    pub long_payout_per_token: u64, // lamports per whole LONG token, set at resolution
+    pub bump: u8,
+}
+
+#[account]
+pub struct ConditionalMarket {
+    pub event: Pubkey,          // child event
+    pub parent_event: Pubkey,
+    pub parent_side: u8,        // 1 = parent TRUE, 2 = parent FALSE
+    pub collateral_mint: Pubkey,
+    pub token_vault: Pubkey,
+    pub bump: u8,
+    pub total_collateral: u64,  // parent-token base units held for child holders
+}
+
+#[account]
+pub struct ProtocolConfig {
+    pub admin: Pubkey,
+    pub house_wallet: Pubkey,
+    // Truth Network wallet receiving the Truth cut of token-collateral events
+    pub truth_fee_recipient: Pubkey,
+    pub consensus_threshold_bps: u16,
+    pub buy_fee_bps: u16,
+    pub redeem_fee_bps: u16,
+    pub unclaimed_sweep_delay_secs: i64,
+    pub true_token_uri: String,
+    pub false_token_uri: String,
+    pub bump: u8,
+
+    // bounds for creator-chosen fee schedules
+    pub min_buy_fee_bps: u16,
+    pub max_buy_fee_bps: u16,
+    pub min_truth_share_bps: u16,
+    pub min_house_share_bps: u16,
+    pub max_creator_share_bps: u16,
+
+    // part of the house cut paid to a referrer, bps of the house cut
+    pub referral_share_bps: u16,
+
+    // ascending volume tiers; unused tiers have a zero discount
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+
+    // creator commission goes to token holders when an event ends without a winner
+    pub creator_clawback: bool,
+
+    // nominated admin, takes over once it calls accept_admin
+    pub pending_admin: Pubkey,
+
+    // distribute_treasury split; unused entries have a zero weight
+    pub treasury_beneficiaries: [TreasuryBeneficiary; MAX_TREASURY_BENEFICIARIES],
+
+    // protocol-wide emergency pause
+    pub pause: PauseState,
+
+    // delay after reveal_end_time before emergency_exit opens on an unresolved event
+    pub emergency_exit_delay_secs: i64,
+
+    // challenge period after fetch_and_store_winner (0 = off) and the bond to challenge
+    pub dispute_window_secs: i64,
+    pub dispute_bond_lamports: u64,
+
+    // revote rounds allowed per event (0 = off) and the commission share funding each one
+    pub revote_max_rounds: u8,
+    pub revote_funding_bps: u16,
+
+    // governance key allowed to void events, kept apart from the admin (unset = admin)
+    pub void_authority: Pubkey,
+}
+
+#[account]
+pub struct Dispute {
+    pub event: Pubkey,
+    pub challenger: Pubkey,
+    pub bond_lamports: u64,
+    pub proposed_option: u8,    // 0 = no winner, 1 / 2 = winning side
+    pub challenged_at: i64,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct PauseState {
+    pub flags: u8,              // PAUSE_* classes blocked, 0 = not paused
+    pub paused_by: Pubkey,
+    pub reason: String,
+    pub paused_at: i64,
+    pub expires_at: i64,        // the pause lifts by itself after this
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct TreasuryBeneficiary {
+    pub wallet: Pubkey,
+    pub weight_bps: u16,
+}
+
+// Program-owned house account; house commission and unclaimed sweeps land here when
+// the config house wallet points at it. Lamports above rent are the undistributed balance.
+#[account]
+pub struct HouseTreasury {
+    pub total_received: u64,
+    pub total_distributed: u64,
+    pub inflow_count: u64,
+    pub last_distributed_at: i64,
+    pub bump: u8,
+}
+
+// One per lamport inflow into the HouseTreasury, so indexers can attribute revenue per event
+#[event]
+pub struct TreasuryInflow {
+    pub event: Pubkey,
+    pub lamports: u64,
+    pub inflow_count: u64,
+}
+
+#[account]
+pub struct PendingChange {
+    pub params: ConfigParams,
+    pub proposed_at: i64,
+    pub eta: i64,           // earliest execution time
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct FeeTier {
+    pub min_volume: u64,    // rolling 30-day volume, lamports
+    pub discount_bps: u16,  // bps off the buy fee
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ConfigParams {
+    pub house_wallet: Pubkey,
+    pub truth_fee_recipient: Pubkey,
+    pub consensus_threshold_bps: u16,
+    pub buy_fee_bps: u16,
+    pub redeem_fee_bps: u16,
+    pub unclaimed_sweep_delay_secs: i64,
+    pub true_token_uri: String,
+    pub false_token_uri: String,
+    pub min_buy_fee_bps: u16,
+    pub max_buy_fee_bps: u16,
+    pub min_truth_share_bps: u16,
+    pub min_house_share_bps: u16,
+    pub max_creator_share_bps: u16,
+    pub referral_share_bps: u16,
+    pub fee_tiers: [FeeTier; MAX_FEE_TIERS],
+    pub creator_clawback: bool,
+    pub treasury_beneficiaries: [TreasuryBeneficiary; MAX_TREASURY_BENEFICIARIES],
+    pub emergency_exit_delay_secs: i64,
+    pub dispute_window_secs: i64,
+    pub dispute_bond_lamports: u64,
+    pub revote_max_rounds: u8,
+    pub revote_funding_bps: u16,
+    pub void_authority: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct RevoteRound {
+    pub question: Pubkey,
+    pub votes_option_1: u64,
+    pub votes_option_2: u64,
+    pub result_status: u8,      // RESULT_FINALIZED_TIE or RESULT_FINALIZED_BELOW_THRESHOLD
+    pub funding_lamports: u64,  // sent to the next round's question
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CreatorShare {
+    pub wallet: Pubkey,
+    pub share_bps: u16,     // bps of the creator commission
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FeeSchedule {
+    pub fee_bps: u16,
+    pub truth_share_bps: u16,
+    pub creator_share_bps: u16,
+}
+
+#[account]
+pub struct ReferralAccount {
+    pub referrer: Pubkey,
+    pub pending_lamports: u64,
+    pub total_earned_lamports: u64,
+    pub total_claimed_lamports: u64,
+    pub bump: u8,
+    pub vault_bump: u8,
+}
+
+#[account]
+pub struct UserStats {
+    pub owner: Pubkey,
+    pub lifetime_volume: u64,
+    pub window_start: i64,
+    pub current_window_volume: u64,
+    pub previous_window_volume: u64,
+    pub events_participated: u32,
+    pub total_payouts: u64,
+    pub realized_pnl: i64,
+    pub bump: u8,
+}
+
+// Per-user, per-event cost basis used for realised PnL
+#[account]
+pub struct UserPosition {
+    pub owner: Pubkey,
+    pub event: Pubkey,
+    pub cost_basis_true: u64,
+    pub cost_basis_false: u64,
+    pub units_true: u64,    // tokens bought on each side and not yet redeemed
+    pub units_false: u64,
+    pub payouts: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,356 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  RESULT_FINALIZED_NO_VOTES,
+  SystemProgram,
+  TEST_SWEEP_DELAY_SECS,
+  TOKEN_PROGRAM_ID,
+  ataAddress,
+  chainTime,
+  configPda,
+  createAtaIx,
+  createTruthQuestion,
+  ensureConfig,
+  ensureCounter,
+  expectError,
+  feeSplits,
+  lamports,
+  newUser,
+  pda,
+  program,
+  rentExempt,
+  send,
+  tokenBalance,
+  truth,
+  userPositionPda,
+  userStatsPda,
+  waitUntil,
+} from "./helpers";
+
+const OUTCOMES = 3;
+
+describe("user-005: categorical events", () => {
+  let creator: Keypair;
+  let user: Keypair;
+  let cev: PublicKey;
+  let collateralVault: PublicKey;
+  let commissionVault: PublicKey;
+  let mintAuthority: PublicKey;
+  let mints: PublicKey[];
+  let questions: { question: PublicKey; vault: PublicKey }[];
+  let betEnd: number;
+  let revealEnd: number;
+
+  const outcomeAccounts = (owner: PublicKey) =>
+    mints.reduce(
+      (acc, mint) =>
+        acc.concat([
+          { pubkey: mint, isSigner: false, isWritable: true },
+          { pubkey: ataAddress(owner, mint), isSigner: false, isWritable: true },
+        ]),
+      [] as { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[]
+    );
+
+  const create = async (qs: PublicKey[]) => {
+    const counter = await ensureCounter(creator);
+    const { count } = await program.account.eventCounter.fetch(counter);
+    const event = pda([Buffer.from("categorical_event"), creator.publicKey.toBuffer(), count.toArrayLike(Buffer, "le", 8)]);
+    await program.methods
+      .createCategoricalEvent(
+        "Which test outcome will the oracle pick?",
+        0,
+        new BN(betEnd),
+        new BN(betEnd + 2),
+        new BN(revealEnd),
+        qs,
+        null
+      )
+      .accountsPartial({
+        creator: creator.publicKey,
+        counter,
+        categoricalEvent: event,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+    return event;
+  };
+
+  const createMint = (index: number) =>
+    program.methods
+      .createCategoricalMint(index)
+      .accountsPartial({
+        creator: creator.publicKey,
+        categoricalEvent: cev,
+        mintAuthority,
+        outcomeMint: pda([Buffer.from("categorical_mint"), cev.toBuffer(), Buffer.from([index])]),
+        collateralVault,
+        commissionVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([creator])
+      .rpc();
+
+  const finalize = (index: number, q: { question: PublicKey; vault: PublicKey }) =>
+    program.methods
+      .finalizeCategoricalOutcome(index)
+      .accountsPartial({
+        categoricalEvent: cev,
+        config: configPda,
+        truthNetworkQuestion: q.question,
+        truthNetworkVault: q.vault,
+        truthNetworkProgram: truth.programId,
+        collateralVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+  before(async () => {
+    await ensureConfig();
+    creator = await newUser(20);
+    user = await newUser(20);
+
+    betEnd = (await chainTime()) + 40;
+    revealEnd = betEnd + 4;
+    questions = [];
+    for (let i = 0; i < OUTCOMES; i++) {
+      questions.push(await createTruthQuestion(creator, `Is outcome ${i} the answer?`, betEnd + 2, revealEnd));
+    }
+  });
+
+  it("needs between 3 and 16 distinct outcome questions", async () => {
+    await expectError(create(questions.slice(0, 2).map((q) => q.question)), "InvalidOutcomeCount");
+    const q = questions[0].question;
+    await expectError(create([q, questions[1].question, q]), "DuplicateTruthQuestion");
+  });
+
+  it("creates the event and one mint per outcome", async () => {
+    cev = await create(questions.map((q) => q.question));
+    collateralVault = pda([Buffer.from("collateral_vault"), cev.toBuffer()]);
+    commissionVault = pda([Buffer.from("commission_vault"), cev.toBuffer()]);
+    mintAuthority = pda([Buffer.from("mint_authority"), cev.toBuffer()]);
+
+    await expectError(createMint(OUTCOMES), "InvalidOutcomeIndex");
+    for (let i = 0; i < OUTCOMES; i++) {
+      await createMint(i);
+    }
+
+    const event = await program.account.categoricalEvent.fetch(cev);
+    mints = event.outcomeMints;
+    expect(event.outcomeCount).to.equal(OUTCOMES);
+    expect(event.collateralVault.equals(collateralVault)).to.equal(true);
+    expect(mints.every((m) => !m.equals(PublicKey.default))).to.equal(true);
+
+    await send(mints.map((m) => createAtaIx(user.publicKey, user.publicKey, m)), [user]);
+  });
+
+  it("mints a full set of outcome tokens per buy", async () => {
+    const buy = (remaining: ReturnType<typeof outcomeAccounts>) =>
+      program.methods
+        .buyCategoricalSet(new BN(LAMPORTS_PER_SOL))
+        .accountsPartial({
+          user: user.publicKey,
+          categoricalEvent: cev,
+          config: configPda,
+          collateralVault,
+          commissionVault,
+          mintAuthority,
+          userStats: userStatsPda(user.publicKey),
+          userPosition: userPositionPda(cev, user.publicKey),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(remaining)
+        .signers([user])
+        .rpc();
+
+    await expectError(buy(outcomeAccounts(user.publicKey).slice(0, 4)), "InvalidRemainingAccounts");
+    const collateralBefore = await lamports(collateralVault);
+    const commissionBefore = await lamports(commissionVault);
+    await buy(outcomeAccounts(user.publicKey));
+
+    const event = await program.account.categoricalEvent.fetch(cev);
+    const s = event.feeSchedule;
+    const { net, truthCut, creatorCut, houseCut } = feeSplits(
+      LAMPORTS_PER_SOL,
+      s.feeBps,
+      s.truthShareBps,
+      s.creatorShareBps
+    );
+    // creator + house cut go to the commission vault, the truth cut waits with the collateral
+    expect((await lamports(commissionVault)) - commissionBefore).to.equal(creatorCut + houseCut);
+    expect((await lamports(collateralVault)) - collateralBefore).to.equal(net + truthCut);
+    expect(event.pendingCreatorCommission.toNumber()).to.equal(creatorCut);
+    for (const m of mints) {
+      expect((await tokenBalance(ataAddress(user.publicKey, m))).toNumber()).to.equal(net);
+    }
+    expect(event.totalIssuedPerOutcome.toNumber()).to.equal(net);
+    expect(event.outstanding.map((o) => o.toNumber())).to.deep.equal([net, net, net]);
+
+    // counts towards the fee tiers; the TRUE / FALSE position stays empty
+    const stats = await program.account.userStats.fetch(userStatsPda(user.publicKey));
+    expect(stats.lifetimeVolume.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    const position = await program.account.userPosition.fetch(userPositionPda(cev, user.publicKey));
+    expect(position.unitsTrue.toNumber()).to.equal(0);
+    expect(position.costBasisTrue.toNumber()).to.equal(0);
+  });
+
+  it("redeems a full set for its face value while betting is open", async () => {
+    const amount = new BN(LAMPORTS_PER_SOL / 10);
+    const before = await lamports(user.publicKey);
+    await program.methods
+      .redeemCategoricalSet(amount)
+      .accountsPartial({
+        user: user.publicKey,
+        categoricalEvent: cev,
+        config: configPda,
+        collateralVault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(outcomeAccounts(user.publicKey))
+      .signers([user])
+      .rpc();
+    expect((await lamports(user.publicKey)) - before).to.be.closeTo(amount.toNumber(), 10_000);
+  });
+
+  it("finalizes each outcome only after betting and the Truth reveal", async () => {
+    await expectError(finalize(0, questions[0]), "BettingStillActive");
+    await expectError(
+      program.methods
+        .settleCategoricalEvent()
+        .accountsPartial({
+          categoricalEvent: cev,
+          config: configPda,
+          houseTreasury: (await program.account.categoricalEvent.fetch(cev)).houseWallet,
+          collateralVault,
+          commissionVault,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc(),
+      "OutcomesNotFinalized"
+    );
+
+    await waitUntil(revealEnd + 1);
+    await expectError(finalize(0, questions[1]), "TruthQuestionMismatch");
+    for (let i = 0; i < OUTCOMES; i++) {
+      await finalize(i, questions[i]);
+    }
+
+    const event = await program.account.categoricalEvent.fetch(cev);
+    expect(event.pendingTruthCommission.toNumber()).to.equal(0);
+    await expectError(finalize(0, questions[0]), "OutcomeAlreadyFinalized");
+  });
+
+  it("settles with no votes and pays 1/N per outcome token plus the forfeited commission", async () => {
+    const event = await program.account.categoricalEvent.fetch(cev);
+    const houseCut = event.pendingHouseCommission.toNumber();
+    const creatorCut = event.pendingCreatorCommission;
+    const houseBefore = await lamports(event.houseWallet);
+    const collateralBefore = await lamports(collateralVault);
+    await program.methods
+      .settleCategoricalEvent()
+      .accountsPartial({
+        categoricalEvent: cev,
+        config: configPda,
+        houseTreasury: event.houseWallet,
+        collateralVault,
+        commissionVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    const settled = await program.account.categoricalEvent.fetch(cev);
+    expect(settled.resolved).to.equal(true);
+    expect(settled.resultStatus).to.equal(RESULT_FINALIZED_NO_VOTES);
+    expect(settled.pendingHouseCommission.toNumber()).to.equal(0);
+    expect((await lamports(event.houseWallet)) - houseBefore).to.equal(houseCut);
+
+    // clawback: the creator commission moves to the collateral vault for the holders
+    expect(settled.pendingCreatorCommission.toNumber()).to.equal(0);
+    expect(settled.forfeitedCommission.toString()).to.equal(creatorCut.toString());
+    expect(settled.forfeitBaseUnits.toString()).to.equal(
+      settled.outstanding.reduce((a, o) => a.add(o), new BN(0)).toString()
+    );
+    expect((await lamports(collateralVault)) - collateralBefore).to.equal(creatorCut.toNumber());
+
+    const ata = ataAddress(user.publicKey, mints[1]);
+    const amount = await tokenBalance(ata);
+    const before = await lamports(user.publicKey);
+    await program.methods
+      .redeemCategoricalAfterFinal(1, amount)
+      .accountsPartial({
+        user: user.publicKey,
+        categoricalEvent: cev,
+        config: configPda,
+        collateralVault,
+        mint: mints[1],
+        userAta: ata,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+    const share = settled.forfeitedCommission.mul(amount).div(settled.forfeitBaseUnits);
+    const expected = amount.divn(OUTCOMES).add(share).toNumber();
+    expect((await lamports(user.publicKey)) - before).to.equal(expected);
+    expect((await tokenBalance(ata)).toNumber()).to.equal(0);
+  });
+
+  it("sweeps the collateral vault only", async () => {
+    const event = await program.account.categoricalEvent.fetch(cev);
+    const commissionBefore = await lamports(commissionVault);
+
+    await waitUntil(event.resolvedAt.toNumber() + TEST_SWEEP_DELAY_SECS + 1);
+    const unclaimed = (await lamports(collateralVault)) - (await rentExempt(0));
+    const houseBefore = await lamports(event.houseWallet);
+    await program.methods
+      .sweepCategoricalUnclaimedToHouse()
+      .accountsPartial({
+        categoricalEvent: cev,
+        config: configPda,
+        houseTreasury: event.houseWallet,
+        collateralVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    expect((await lamports(event.houseWallet)) - houseBefore).to.equal(unclaimed);
+    expect(await lamports(commissionVault)).to.equal(commissionBefore);
+
+    // the commission went back to the holders at settlement
+    await expectError(
+      program.methods
+        .claimCategoricalCreatorCommission()
+        .accountsPartial({
+          creator: creator.publicKey,
+          categoricalEvent: cev,
+          commissionVault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc(),
+      "NothingToClaim"
+    );
+
+    // both vaults are down to rent, so the event can be closed
+    await program.methods
+      .deleteCategoricalEvent()
+      .accountsPartial({
+        creator: creator.publicKey,
+        categoricalEvent: cev,
+        collateralVault,
+        commissionVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+    expect(await lamports(commissionVault)).to.equal(0);
+    expect(await lamports(collateralVault)).to.equal(0);
+  });
+});
@@ -0,0 +1,76 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  RESULT_FINALIZED_NO_VOTES,
+  SIDE_FALSE,
+  SIDE_TRUE,
+  TEST_BUY_FEE_BPS,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  createEvent,
+  feeSplits,
+  lamports,
+  newUser,
+  program,
+  redeemNoWinnerIx,
+  resolveEvent,
+  tokenBalance,
+  waitFinal,
+} from "./helpers";
+
+describe("user-016: creator commission clawback", () => {
+  let creator: Keypair;
+  let alice: Keypair;
+  let bob: Keypair;
+  let ev: TestEvent;
+
+  before(async () => {
+    creator = await newUser(20);
+    alice = await newUser(20);
+    bob = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 20 });
+  });
+
+  it("snapshots the clawback policy into the event", async () => {
+    expect((await program.account.event.fetch(ev.event)).creatorClawback).to.equal(true);
+  });
+
+  it("forfeits the creator commission to holders when nobody votes", async () => {
+    const event = await program.account.event.fetch(ev.event);
+    const a = feeSplits(LAMPORTS_PER_SOL, TEST_BUY_FEE_BPS, event.truthShareBps, event.creatorShareBps);
+    const b = feeSplits(3 * LAMPORTS_PER_SOL, TEST_BUY_FEE_BPS, event.truthShareBps, event.creatorShareBps);
+    await buyPair(ev, alice, LAMPORTS_PER_SOL);
+    await buyPair(ev, bob, 3 * LAMPORTS_PER_SOL);
+
+    const collateralBefore = await lamports(ev.collateralVault);
+    await resolveEvent(ev);
+
+    const resolved = await program.account.event.fetch(ev.event);
+    expect(resolved.resultStatus).to.equal(RESULT_FINALIZED_NO_VOTES);
+    expect(resolved.pendingCreatorCommission.toNumber()).to.equal(0);
+    expect(resolved.forfeitedCommission.toNumber()).to.equal(a.creatorCut + b.creatorCut);
+    expect(resolved.forfeitBaseUnits.toNumber()).to.equal(2 * (a.net + b.net));
+    expect((await lamports(ev.collateralVault)) - collateralBefore).to.equal(a.creatorCut + b.creatorCut);
+  });
+
+  it("adds each token's share of the forfeit to the no-winner payout", async () => {
+    await waitFinal(ev);
+    const event = await program.account.event.fetch(ev.event);
+    const forfeited = event.forfeitedCommission;
+    const base = event.forfeitBaseUnits;
+
+    const redeem = async (user: Keypair, side: number) => {
+      const units = await tokenBalance(ataAddress(user.publicKey, side === SIDE_TRUE ? ev.trueMint : ev.falseMint));
+      const expected = units.divn(2).add(forfeited.mul(units).div(base));
+      const before = await lamports(user.publicKey);
+      await redeemNoWinnerIx(ev, user, side, units).signers([user]).rpc();
+      expect((await lamports(user.publicKey)) - before).to.equal(expected.toNumber());
+    };
+
+    await redeem(alice, SIDE_TRUE);
+    await redeem(alice, SIDE_FALSE);
+    await redeem(bob, SIDE_TRUE);
+  });
+});
@@ -0,0 +1,125 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+  TEST_BUY_FEE_BPS,
+  TestEvent,
+  buyPair,
+  connection,
+  createEvent,
+  expectError,
+  feeSplits,
+  houseTreasuryPda,
+  lamports,
+  newUser,
+  program,
+  resolveEvent,
+  waitFinal,
+} from "./helpers";
+
+// creator clawback is on in the test config, so the creator commission only becomes claimable
+// after a winner is final; with no Truth votes it goes back to the holders instead.
+describe("user-015: commission vault", () => {
+  let creator: Keypair;
+  let buyer: Keypair;
+  let ev: TestEvent;
+  let split: ReturnType<typeof feeSplits>;
+  let commissionRent: number;
+
+  const claim = (e: TestEvent) =>
+    program.methods
+      .claimCreatorCommission()
+      .accountsPartial({
+        creator: creator.publicKey,
+        event: e.event,
+        creatorPayout: creator.publicKey,
+        commissionVault: e.commissionVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+  const deleteEvent = (e: TestEvent) =>
+    program.methods
+      .deleteEvent()
+      .accountsPartial({
+        creator: creator.publicKey,
+        event: e.event,
+        collateralVault: e.collateralVault,
+        commissionVault: e.commissionVault,
+        lmsrMarket: null,
+        tokenVault: null,
+        truthTokenVault: null,
+        commissionTokenVault: null,
+        collateralMint: null,
+        conditionalMarket: null,
+        conditionalVault: null,
+        trueMint: null,
+        falseMint: null,
+        mintAuthority: null,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    buyer = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 20 });
+    commissionRent = await lamports(ev.commissionVault);
+  });
+
+  it("moves creator and house cuts out of the collateral vault at buy time", async () => {
+    const event = await program.account.event.fetch(ev.event);
+    split = feeSplits(LAMPORTS_PER_SOL, TEST_BUY_FEE_BPS, event.truthShareBps, event.creatorShareBps);
+    const collateralBefore = await lamports(ev.collateralVault);
+
+    await buyPair(ev, buyer, LAMPORTS_PER_SOL);
+
+    expect((await lamports(ev.collateralVault)) - collateralBefore).to.equal(split.net);
+    expect((await lamports(ev.commissionVault)) - commissionRent).to.equal(split.creatorCut + split.houseCut);
+    const after = await program.account.event.fetch(ev.event);
+    expect(after.pendingCreatorCommission.toNumber()).to.equal(split.creatorCut);
+    expect(after.pendingHouseCommission.toNumber()).to.equal(split.houseCut);
+  });
+
+  it("holds the creator commission while betting is open", async () => {
+    await expectError(claim(ev), "BettingStillActive");
+  });
+
+  it("pays the house cut from the commission vault at resolution", async () => {
+    const houseBefore = await lamports(houseTreasuryPda);
+    const collateralBefore = await lamports(ev.collateralVault);
+    await resolveEvent(ev);
+
+    expect((await lamports(houseTreasuryPda)) - houseBefore).to.equal(split.houseCut);
+    // no votes: the forfeited creator cut goes to the holders' collateral
+    expect((await lamports(ev.collateralVault)) - collateralBefore).to.equal(split.creatorCut);
+    expect(await lamports(ev.commissionVault)).to.equal(commissionRent);
+
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.pendingHouseCommission.toNumber()).to.equal(0);
+    expect(event.pendingCreatorCommission.toNumber()).to.equal(0);
+    expect(event.houseLamportsPaid.toNumber()).to.equal(split.houseCut);
+
+    await waitFinal(ev);
+    await expectError(claim(ev), "NothingToClaim");
+  });
+
+  it("keeps events with outstanding tokens", async () => {
+    await expectError(deleteEvent(ev), "OutstandingTokens");
+  });
+
+  it("closes an untraded event together with its commission vault", async () => {
+    const empty = await createEvent(creator, { betSecs: 5 });
+    await resolveEvent(empty);
+    await waitFinal(empty);
+
+    await deleteEvent(empty);
+    expect(await connection.getAccountInfo(empty.event)).to.equal(null);
+    expect(await lamports(empty.commissionVault)).to.equal(0);
+    expect(await lamports(empty.collateralVault)).to.equal(0);
+  });
+});
@@ -0,0 +1,177 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  SIDE_TRUE,
+  SystemProgram,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  buyPairIx,
+  configPda,
+  createEvent,
+  expectError,
+  newUser,
+  pda,
+  positionAtas,
+  program,
+  resolveEvent,
+  tokenBalance,
+  userPositionPda,
+  userStatsPda,
+  waitFinal,
+} from "./helpers";
+
+function conditionalPdas(event: PublicKey) {
+  return {
+    conditionalMarket: pda([Buffer.from("conditional_market"), event.toBuffer()]),
+    tokenVault: pda([Buffer.from("conditional_vault"), event.toBuffer()]),
+  };
+}
+
+describe("user-007: conditional events", () => {
+  let creator: Keypair;
+  let user: Keypair;
+  let parent: TestEvent;
+  let child: TestEvent;
+  let c: ReturnType<typeof conditionalPdas>;
+
+  const initConditional = (event: PublicKey, parentEvent: PublicKey, collateralMint: PublicKey) =>
+    program.methods
+      .initConditionalMarket(SIDE_TRUE)
+      .accountsPartial({
+        creator: creator.publicKey,
+        event,
+        parentEvent,
+        collateralMint,
+        ...conditionalPdas(event),
+        tokenProgram: parent.tokenProgram,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([creator])
+      .rpc();
+
+  const pairAccounts = () => ({
+    user: user.publicKey,
+    event: child.event,
+    config: configPda,
+    conditionalMarket: c.conditionalMarket,
+    tokenVault: c.tokenVault,
+    collateralMint: parent.trueMint,
+    userCollateralAta: ataAddress(user.publicKey, parent.trueMint),
+    trueMint: child.trueMint,
+    falseMint: child.falseMint,
+    userTrueAta: ataAddress(user.publicKey, child.trueMint),
+    userFalseAta: ataAddress(user.publicKey, child.falseMint),
+    tokenProgram: child.tokenProgram,
+  });
+
+  const buyConditional = (amount: BN) =>
+    program.methods
+      .buyConditionalPositions(amount)
+      .accountsPartial({
+        ...pairAccounts(),
+        parentEvent: parent.event,
+        mintAuthority: child.mintAuthority,
+        userStats: userStatsPda(user.publicKey),
+        userPosition: userPositionPda(child.event, user.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    user = await newUser(20);
+    parent = await createEvent(creator, { betSecs: 40 });
+    await buyPair(parent, user, 2 * LAMPORTS_PER_SOL);
+  });
+
+  it("backs a child event with one side of the parent", async () => {
+    child = await createEvent(creator, {
+      betSecs: 150,
+      beforeMints: async (event) => {
+        await expectError(initConditional(event, event, parent.trueMint), "InvalidParentEvent");
+        await expectError(initConditional(event, parent.event, parent.falseMint), "InvalidMint");
+        await initConditional(event, parent.event, parent.trueMint);
+      },
+    });
+    c = conditionalPdas(child.event);
+    await positionAtas(child, user);
+
+    const market = await program.account.conditionalMarket.fetch(c.conditionalMarket);
+    expect(market.parentEvent.equals(parent.event)).to.equal(true);
+    expect(market.collateralMint.equals(parent.trueMint)).to.equal(true);
+    expect(market.tokenVault.equals(c.tokenVault)).to.equal(true);
+    expect((await program.account.event.fetch(child.event)).kind).to.equal(2);
+  });
+
+  it("does not take SOL for a conditional event", async () => {
+    await expectError(buyPairIx(child, user, LAMPORTS_PER_SOL).signers([user]).rpc(), "InvalidEventKind");
+  });
+
+  it("mints child pairs 1:1 against deposited parent tokens", async () => {
+    const amount = new BN(LAMPORTS_PER_SOL / 2);
+    await buyConditional(amount);
+
+    expect((await tokenBalance(c.tokenVault)).toString()).to.equal(amount.toString());
+    expect((await tokenBalance(ataAddress(user.publicKey, child.trueMint))).toString()).to.equal(amount.toString());
+    expect((await tokenBalance(ataAddress(user.publicKey, child.falseMint))).toString()).to.equal(amount.toString());
+    const market = await program.account.conditionalMarket.fetch(c.conditionalMarket);
+    expect(market.totalCollateral.toString()).to.equal(amount.toString());
+
+    // parent tokens build the position but don't count as lamport volume
+    const stats = await program.account.userStats.fetch(userStatsPda(user.publicKey));
+    const position = await program.account.userPosition.fetch(userPositionPda(child.event, user.publicKey));
+    expect(position.unitsTrue.toString()).to.equal(amount.toString());
+    expect(position.unitsFalse.toString()).to.equal(amount.toString());
+    expect(position.costBasisTrue.add(position.costBasisFalse).toString()).to.equal(amount.toString());
+    expect(stats.lifetimeVolume.toNumber()).to.equal(2 * LAMPORTS_PER_SOL);
+  });
+
+  it("returns parent tokens for burned child pairs while betting is open", async () => {
+    const amount = new BN(LAMPORTS_PER_SOL / 10);
+    const before = await tokenBalance(ataAddress(user.publicKey, parent.trueMint));
+    await program.methods
+      .redeemConditionalPairWhileActive(amount)
+      .accountsPartial(pairAccounts())
+      .signers([user])
+      .rpc();
+    const after = await tokenBalance(ataAddress(user.publicKey, parent.trueMint));
+    expect(after.sub(before).toString()).to.equal(amount.toString());
+  });
+
+  it("refunds half a parent token per child token when the condition fails", async () => {
+    // no votes: the parent never resolves to TRUE
+    await resolveEvent(parent);
+    await expectError(buyConditional(new BN(LAMPORTS_PER_SOL / 10)), "EventResolved");
+    await waitFinal(parent);
+
+    const ata = ataAddress(user.publicKey, child.trueMint);
+    const amount = await tokenBalance(ata);
+    const before = await tokenBalance(ataAddress(user.publicKey, parent.trueMint));
+    await program.methods
+      .redeemConditionalAfterFinal(SIDE_TRUE, amount)
+      .accountsPartial({
+        user: user.publicKey,
+        event: child.event,
+        config: configPda,
+        parentEvent: parent.event,
+        conditionalMarket: c.conditionalMarket,
+        tokenVault: c.tokenVault,
+        collateralMint: parent.trueMint,
+        userCollateralAta: ataAddress(user.publicKey, parent.trueMint),
+        mint: child.trueMint,
+        userAta: ata,
+        tokenProgram: child.tokenProgram,
+      })
+      .signers([user])
+      .rpc();
+
+    const after = await tokenBalance(ataAddress(user.publicKey, parent.trueMint));
+    expect(after.sub(before).toString()).to.equal(amount.divn(2).toString());
+    expect((await tokenBalance(ata)).toNumber()).to.equal(0);
+  });
+});
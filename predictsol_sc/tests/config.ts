@@ -0,0 +1,136 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  SIDE_TRUE,
+  SystemProgram,
+  TEST_BUY_FEE_BPS,
+  TEST_DISPUTE_WINDOW_SECS,
+  TEST_REDEEM_FEE_BPS,
+  TEST_SWEEP_DELAY_SECS,
+  admin,
+  buyPair,
+  configPda,
+  createEvent,
+  ensureConfig,
+  expectError,
+  houseTreasuryPda,
+  lamports,
+  newUser,
+  program,
+  programDataPda,
+  redeemNoWinnerIx,
+  rentExempt,
+  resolveEvent,
+  testConfigParams,
+  waitUntil,
+} from "./helpers";
+
+const EVENT_LAYOUT_VERSION = 1;
+
+describe("user-010: protocol config", () => {
+  let creator: Keypair;
+
+  before(async () => {
+    await ensureConfig();
+    creator = await newUser(20);
+  });
+
+  it("holds the parameters it was initialized with", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    expect(config.admin.equals(admin.publicKey)).to.equal(true);
+    expect(config.houseWallet.equals(houseTreasuryPda)).to.equal(true);
+    expect(config.buyFeeBps).to.equal(TEST_BUY_FEE_BPS);
+    expect(config.redeemFeeBps).to.equal(TEST_REDEEM_FEE_BPS);
+    expect(config.consensusThresholdBps).to.equal(6_000);
+    expect(config.disputeWindowSecs.toNumber()).to.equal(TEST_DISPUTE_WINDOW_SECS);
+  });
+
+  it("can only be initialized once", async () => {
+    await expectError(
+      program.methods
+        .initializeConfig(testConfigParams() as any)
+        .accountsPartial({
+          admin: admin.publicKey,
+          config: configPda,
+          program: program.programId,
+          programData: programDataPda(),
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc(),
+      "already in use"
+    );
+  });
+
+  it("snapshots the config into each new event", async () => {
+    const ev = await createEvent(creator);
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const event = await program.account.event.fetch(ev.event);
+
+    expect(event.layoutVersion).to.equal(EVENT_LAYOUT_VERSION);
+    expect(event.houseWallet.equals(config.houseWallet)).to.equal(true);
+    expect(event.consensusThresholdBps).to.equal(config.consensusThresholdBps);
+    expect(event.redeemFeeBps).to.equal(config.redeemFeeBps);
+    expect(event.unclaimedSweepDelaySecs.toString()).to.equal(config.unclaimedSweepDelaySecs.toString());
+    expect(event.buyFeeBps).to.equal(config.buyFeeBps);
+  });
+
+  it("leaves current-layout events alone on migrate_event", async () => {
+    const ev = await createEvent(creator);
+    const migrate = (signer: Keypair) =>
+      program.methods
+        .migrateEvent()
+        .accountsPartial({
+          admin: signer.publicKey,
+          config: configPda,
+          event: ev.event,
+          collateralVault: ev.collateralVault,
+          commissionVault: ev.commissionVault,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers(signer === admin ? [] : [signer])
+        .rpc();
+
+    await expectError(migrate(creator), "Unauthorized");
+    await expectError(migrate(admin), "EventAlreadyMigrated");
+  });
+
+  it("sweeps unclaimed collateral to the house after the configured delay", async () => {
+    const holder = await newUser(5);
+    const ev = await createEvent(creator, { betSecs: 10 });
+    await buyPair(ev, holder, LAMPORTS_PER_SOL);
+    await resolveEvent(ev);
+
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.unclaimedSweepDelaySecs.toNumber()).to.equal(TEST_SWEEP_DELAY_SECS);
+    const sweep = () =>
+      program.methods
+        .sweepUnclaimedToHouse()
+        .accountsPartial({
+          event: ev.event,
+          config: configPda,
+          houseTreasury: event.houseWallet,
+          collateralVault: ev.collateralVault,
+          lmsrMarket: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+    await waitUntil(event.disputeDeadline.toNumber() + 1);
+    await expectError(sweep(), "SweepNotYetAvailable");
+
+    await waitUntil(event.resolvedAt.toNumber() + TEST_SWEEP_DELAY_SECS + 1);
+    const unclaimed = (await lamports(ev.collateralVault)) - (await rentExempt(0));
+    const houseBefore = await lamports(event.houseWallet);
+    await sweep();
+
+    expect((await lamports(event.houseWallet)) - houseBefore).to.equal(unclaimed);
+    const swept = await program.account.event.fetch(ev.event);
+    expect(swept.unclaimedSwept).to.equal(true);
+    await expectError(sweep(), "AlreadySwept");
+    await expectError(
+      redeemNoWinnerIx(ev, holder, SIDE_TRUE, LAMPORTS_PER_SOL / 10).signers([holder]).rpc(),
+      "RedemptionExpired"
+    );
+  });
+});
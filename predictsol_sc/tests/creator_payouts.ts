@@ -0,0 +1,112 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+  TestEvent,
+  buyPair,
+  createEvent,
+  expectError,
+  lamports,
+  newUser,
+  program,
+} from "./helpers";
+
+const VESTING_LINEAR = 1;
+
+describe("user-017: creator payout address and co-creator splits", () => {
+  let creator: Keypair;
+  let payout: Keypair;
+  let sponsor: Keypair;
+  let dao: Keypair;
+  let ev: TestEvent;
+
+  const setPayouts = (signer: Keypair, wallet: PublicKey, coCreators: { wallet: PublicKey; shareBps: number }[]) =>
+    program.methods
+      .setCreatorPayouts(wallet, coCreators)
+      .accountsPartial({ creator: signer.publicKey, event: ev.event })
+      .signers([signer])
+      .rpc();
+
+  const claim = (recipients: PublicKey[]) =>
+    program.methods
+      .claimCreatorCommission()
+      .accountsPartial({
+        creator: creator.publicKey,
+        event: ev.event,
+        creatorPayout: payout.publicKey,
+        commissionVault: ev.commissionVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(recipients.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .signers([creator])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    // recipients are funded so small shares don't fall under the rent-exempt minimum
+    payout = await newUser(1);
+    sponsor = await newUser(1);
+    dao = await newUser(1);
+    ev = await createEvent(creator, { betSecs: 60 });
+  });
+
+  it("validates the recipients", async () => {
+    const other = await newUser(1);
+    await expectError(setPayouts(other, payout.publicKey, []), "Unauthorized");
+    await expectError(setPayouts(creator, PublicKey.default, []), "InvalidCreatorShares");
+    await expectError(setPayouts(creator, payout.publicKey, [{ wallet: sponsor.publicKey, shareBps: 0 }]), "InvalidCreatorShares");
+    await expectError(
+      setPayouts(creator, payout.publicKey, [
+        { wallet: sponsor.publicKey, shareBps: 6_000 },
+        { wallet: dao.publicKey, shareBps: 5_000 },
+      ]),
+      "InvalidCreatorShares"
+    );
+    const six = [0, 1, 2, 3, 4, 5].map(() => ({ wallet: Keypair.generate().publicKey, shareBps: 100 }));
+    await expectError(setPayouts(creator, payout.publicKey, six), "InvalidCreatorShares");
+  });
+
+  it("records the payout address and co-creator shares", async () => {
+    await setPayouts(creator, payout.publicKey, [
+      { wallet: sponsor.publicKey, shareBps: 2_000 },
+      { wallet: dao.publicKey, shareBps: 3_000 },
+    ]);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.creatorPayout.equals(payout.publicKey)).to.equal(true);
+    expect(event.coCreatorCount).to.equal(2);
+    expect(event.coCreators[0].wallet.equals(sponsor.publicKey)).to.equal(true);
+    expect(event.coCreators[1].shareBps).to.equal(3_000);
+  });
+
+  it("locks the recipients after the first buy", async () => {
+    // linear vesting makes part of the commission claimable while betting is open
+    await program.methods
+      .setCreatorVesting(VESTING_LINEAR, 0, 2_000)
+      .accountsPartial({ creator: creator.publicKey, event: ev.event })
+      .signers([creator])
+      .rpc();
+    await buyPair(ev, await newUser(20), 10 * LAMPORTS_PER_SOL);
+    await expectError(setPayouts(creator, creator.publicKey, []), "CreatorPayoutsLocked");
+  });
+
+  it("splits a claim between the payout address and the co-creators", async () => {
+    await expectError(claim([sponsor.publicKey]), "InvalidRemainingAccounts");
+    await expectError(claim([dao.publicKey, sponsor.publicKey]), "InvalidRemainingAccounts");
+
+    const [payoutBefore, sponsorBefore, daoBefore] = await Promise.all([
+      lamports(payout.publicKey),
+      lamports(sponsor.publicKey),
+      lamports(dao.publicKey),
+    ]);
+    await claim([sponsor.publicKey, dao.publicKey]);
+
+    const amount = (await program.account.event.fetch(ev.event)).claimedCreatorCommission.toNumber();
+    expect(amount).to.be.greaterThan(0);
+    const sponsorShare = Math.floor((amount * 2_000) / 10_000);
+    const daoShare = Math.floor((amount * 3_000) / 10_000);
+    expect((await lamports(sponsor.publicKey)) - sponsorBefore).to.equal(sponsorShare);
+    expect((await lamports(dao.publicKey)) - daoBefore).to.equal(daoShare);
+    expect((await lamports(payout.publicKey)) - payoutBefore).to.equal(amount - sponsorShare - daoShare);
+  });
+});
@@ -0,0 +1,144 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  DISPUTE_OPEN,
+  DISPUTE_SETTLED,
+  LAMPORTS_PER_SOL,
+  RESULT_FINALIZED_NO_VOTES,
+  RESULT_RESOLVED_WINNER,
+  SIDE_TRUE,
+  SystemProgram,
+  TEST_BUY_FEE_BPS,
+  TEST_DISPUTE_BOND,
+  TestEvent,
+  admin,
+  buyPair,
+  configPda,
+  createEvent,
+  disputePda,
+  expectError,
+  feeSplits,
+  lamports,
+  newUser,
+  program,
+  redeemNoWinnerIx,
+  resolveEvent,
+  waitFinal,
+} from "./helpers";
+
+// Every event here resolves with no votes (winning option 0); challenges propose side 1.
+describe("user-024: dispute window", () => {
+  let creator: Keypair;
+  let holder: Keypair;
+  let challenger: Keypair;
+  let stranger: Keypair;
+
+  const challenge = (ev: TestEvent, option: number) =>
+    program.methods
+      .challengeResult(option)
+      .accountsPartial({
+        challenger: challenger.publicKey,
+        event: ev.event,
+        dispute: disputePda(ev.event),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([challenger])
+      .rpc();
+
+  const resolveDispute = async (ev: TestEvent, signer: Keypair, finalOption: number) =>
+    program.methods
+      .resolveDispute(finalOption)
+      .accountsPartial({
+        admin: signer.publicKey,
+        config: configPda,
+        event: ev.event,
+        dispute: disputePda(ev.event),
+        challenger: challenger.publicKey,
+        houseTreasury: (await program.account.event.fetch(ev.event)).houseWallet,
+        commissionVault: ev.commissionVault,
+        collateralVault: ev.collateralVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signer === admin ? [] : [signer])
+      .rpc();
+
+  const redeem = (ev: TestEvent) =>
+    redeemNoWinnerIx(ev, holder, SIDE_TRUE, LAMPORTS_PER_SOL / 10).signers([holder]).rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    holder = await newUser(20);
+    challenger = await newUser(5);
+    stranger = await newUser(2);
+  });
+
+  it("freezes a challenged result until the admin upholds it and slashes the bond", async () => {
+    const ev = await createEvent(creator, { betSecs: 20 });
+    await buyPair(ev, holder, LAMPORTS_PER_SOL);
+    await expectError(challenge(ev, 1), "EventNotResolved");
+
+    await resolveEvent(ev);
+    await expectError(redeem(ev), "DisputeWindowActive");
+    await expectError(challenge(ev, 0), "InvalidWinningOption");
+    await expectError(challenge(ev, 3), "InvalidWinningOption");
+
+    await challenge(ev, 1);
+    const dispute = await program.account.dispute.fetch(disputePda(ev.event));
+    expect(dispute.challenger.equals(challenger.publicKey)).to.equal(true);
+    expect(dispute.proposedOption).to.equal(1);
+    expect(dispute.bondLamports.toString()).to.equal(TEST_DISPUTE_BOND.toString());
+    expect((await program.account.event.fetch(ev.event)).disputeStatus).to.equal(DISPUTE_OPEN);
+
+    await waitFinal(ev);
+    await expectError(redeem(ev), "DisputeOpen");
+    await expectError(
+      program.methods
+        .settleExpiredDispute()
+        .accountsPartial({ event: ev.event, dispute: disputePda(ev.event), challenger: challenger.publicKey })
+        .rpc(),
+      "DisputeNotTimedOut"
+    );
+    await expectError(resolveDispute(ev, stranger, 0), "Unauthorized");
+
+    const event = await program.account.event.fetch(ev.event);
+    const houseBefore = await lamports(event.houseWallet);
+    await resolveDispute(ev, admin, 0);
+
+    expect((await lamports(event.houseWallet)) - houseBefore).to.equal(TEST_DISPUTE_BOND.toNumber());
+    const settled = await program.account.event.fetch(ev.event);
+    expect(settled.disputeStatus).to.equal(DISPUTE_SETTLED);
+    expect(settled.resultStatus).to.equal(RESULT_FINALIZED_NO_VOTES);
+    await redeem(ev);
+  });
+
+  it("returns the bond when the admin overturns the result", async () => {
+    const ev = await createEvent(creator, { betSecs: 20 });
+    const e = await program.account.event.fetch(ev.event);
+    const { creatorCut } = feeSplits(LAMPORTS_PER_SOL, TEST_BUY_FEE_BPS, e.truthShareBps, e.creatorShareBps);
+    await buyPair(ev, holder, LAMPORTS_PER_SOL);
+    await resolveEvent(ev);
+    // no votes: the creator commission went back to the holders
+    expect((await program.account.event.fetch(ev.event)).forfeitedCommission.toNumber()).to.equal(creatorCut);
+
+    const before = await lamports(challenger.publicKey);
+    await challenge(ev, 1);
+    await resolveDispute(ev, admin, 1);
+
+    // bond and dispute rent back to the challenger
+    expect(await lamports(challenger.publicKey)).to.equal(before);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.resultStatus).to.equal(RESULT_RESOLVED_WINNER);
+    expect(event.winningOption).to.equal(1);
+    expect(event.disputeStatus).to.equal(DISPUTE_SETTLED);
+    // a winner after all: the creator gets the commission back
+    expect(event.pendingCreatorCommission.toNumber()).to.equal(creatorCut);
+    expect(event.forfeitedCommission.toNumber()).to.equal(0);
+  });
+
+  it("closes the window after the dispute deadline", async () => {
+    const ev = await createEvent(creator, { betSecs: 5 });
+    await resolveEvent(ev);
+    await waitFinal(ev);
+    await expectError(challenge(ev, 1), "DisputeWindowClosed");
+  });
+});
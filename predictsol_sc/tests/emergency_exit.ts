@@ -0,0 +1,441 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  RESULT_EMERGENCY_EXIT,
+  SIDE_FALSE,
+  SIDE_TRUE,
+  SystemProgram,
+  TEST_EMERGENCY_EXIT_DELAY_SECS,
+  TOKEN_PROGRAM_ID,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  buyPairWithToken,
+  chainTime,
+  configPda,
+  createAta,
+  createAtaIx,
+  createEvent,
+  createMint,
+  createTokenEvent,
+  createTruthQuestion,
+  ensureCounter,
+  expectError,
+  lamports,
+  mintTo,
+  newUser,
+  pda,
+  positionAtas,
+  program,
+  rentExempt,
+  resolveEvent,
+  send,
+  tokenBalance,
+  tokenVaultPdas,
+  userPositionPda,
+  userStatsPda,
+  waitUntil,
+} from "./helpers";
+
+const OUTCOMES = 3;
+
+// Needs a `test-delays` build so the exit opens TEST_EMERGENCY_EXIT_DELAY_SECS after the reveal.
+describe("user-022: emergency exit", () => {
+  let creator: Keypair;
+  let holder: Keypair;
+  let ev: TestEvent;
+  let settled: TestEvent;
+  let houseWallet: PublicKey;
+
+  const exit = (target: TestEvent, side: number, amount: BN) => {
+    const mint = side === SIDE_TRUE ? target.trueMint : target.falseMint;
+    return program.methods
+      .emergencyExit(side, amount)
+      .accountsPartial({
+        user: holder.publicKey,
+        event: target.event,
+        collateralVault: target.collateralVault,
+        commissionVault: target.commissionVault,
+        houseTreasury: houseWallet,
+        lmsrMarket: null,
+        mint,
+        userAta: ataAddress(holder.publicKey, mint),
+        tokenProgram: target.tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc();
+  };
+
+  before(async () => {
+    creator = await newUser(20);
+    holder = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 20 });
+    settled = await createEvent(creator, { betSecs: 20 });
+    houseWallet = (await program.account.event.fetch(ev.event)).houseWallet;
+    await buyPair(ev, holder, LAMPORTS_PER_SOL);
+    await buyPair(settled, holder, LAMPORTS_PER_SOL);
+  });
+
+  it("snapshots the exit delay from the config", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.emergencyExitDelaySecs.toString()).to.equal(config.emergencyExitDelaySecs.toString());
+    expect(event.emergencyExitDelaySecs.toNumber()).to.equal(TEST_EMERGENCY_EXIT_DELAY_SECS);
+    expect(event.emergencyExitActive).to.equal(false);
+  });
+
+  it("stays closed until the delay past the reveal has run", async () => {
+    await expectError(exit(ev, SIDE_TRUE, new BN(1_000)), "EmergencyExitNotAvailable");
+    await waitUntil(ev.revealEnd + 1);
+    await expectError(exit(ev, SIDE_TRUE, new BN(1_000)), "EmergencyExitNotAvailable");
+  });
+
+  it("settles the event on the first exit and pays a pro-rata share of the vault", async () => {
+    await waitUntil(ev.revealEnd + TEST_EMERGENCY_EXIT_DELAY_SECS + 1);
+    const event = await program.account.event.fetch(ev.event);
+    const houseCut = event.pendingHouseCommission.toNumber();
+    const creatorCut = event.pendingCreatorCommission;
+    const outstanding = event.outstandingTrue.add(event.outstandingFalse);
+
+    // the creator commission joins the vault before the share is taken
+    const available = new BN((await lamports(ev.collateralVault)) - (await rentExempt(0))).add(creatorCut);
+    const amount = event.outstandingTrue.divn(2);
+    const houseBefore = await lamports(houseWallet);
+    const before = await lamports(holder.publicKey);
+    await exit(ev, SIDE_TRUE, amount);
+
+    const expected = available.mul(amount).div(outstanding);
+    expect((await lamports(holder.publicKey)) - before).to.equal(expected.toNumber());
+
+    const after = await program.account.event.fetch(ev.event);
+    expect(after.resolved).to.equal(true);
+    expect(after.resultStatus).to.equal(RESULT_EMERGENCY_EXIT);
+    expect(after.emergencyExitActive).to.equal(true);
+    expect(after.outstandingTrue.toString()).to.equal(event.outstandingTrue.sub(amount).toString());
+    expect(after.pendingHouseCommission.toNumber()).to.equal(0);
+    expect((await lamports(houseWallet)) - houseBefore).to.equal(houseCut);
+    expect(after.pendingCreatorCommission.toNumber()).to.equal(0);
+    expect(after.forfeitedCommission.toString()).to.equal(creatorCut.toString());
+  });
+
+  it("pays the rest of the vault to later exits and keeps the event from resolving", async () => {
+    const event = await program.account.event.fetch(ev.event);
+    await exit(ev, SIDE_FALSE, event.outstandingFalse);
+    await exit(ev, SIDE_TRUE, event.outstandingTrue);
+
+    const after = await program.account.event.fetch(ev.event);
+    expect(after.outstandingTrue.toNumber()).to.equal(0);
+    expect(after.outstandingFalse.toNumber()).to.equal(0);
+    // only rounding dust is left above rent
+    expect((await lamports(ev.collateralVault)) - (await rentExempt(0))).to.be.at.most(2);
+
+    await expectError(resolveEvent(ev), "EventAlreadyResolved");
+  });
+
+  it("is not available once the event resolved normally", async () => {
+    await resolveEvent(settled);
+    await waitUntil(settled.revealEnd + TEST_EMERGENCY_EXIT_DELAY_SECS + 1);
+    await expectError(exit(settled, SIDE_TRUE, new BN(1_000)), "EventAlreadyResolved");
+    expect((await program.account.event.fetch(settled.event)).emergencyExitActive).to.equal(false);
+  });
+});
+
+describe("user-022: emergency exit for token, conditional and categorical events", () => {
+  let creator: Keypair;
+  let holder: Keypair;
+
+  // token collateral
+  let mint: PublicKey;
+  let tev: TestEvent;
+
+  // conditional child of `parent`
+  let parent: TestEvent;
+  let child: TestEvent;
+  const conditionalMarket = (event: PublicKey) => pda([Buffer.from("conditional_market"), event.toBuffer()]);
+  const conditionalVault = (event: PublicKey) => pda([Buffer.from("conditional_vault"), event.toBuffer()]);
+
+  // categorical
+  let cev: PublicKey;
+  let cevRevealEnd: number;
+  let outcomeMints: PublicKey[];
+
+  const setupToken = async () => {
+    mint = await createMint(creator, 6);
+    await mintTo(creator, mint, await createAta(holder, holder.publicKey, mint), new BN(1_000_000_000));
+    tev = await createTokenEvent(creator, mint, { betSecs: 30 });
+    await buyPairWithToken(tev, holder, mint, new BN(100_000_000));
+  };
+
+  const setupConditional = async () => {
+    parent = await createEvent(creator, { betSecs: 30 });
+    await buyPair(parent, holder, LAMPORTS_PER_SOL);
+    child = await createEvent(creator, {
+      betSecs: 30,
+      beforeMints: async (event) => {
+        await program.methods
+          .initConditionalMarket(SIDE_TRUE)
+          .accountsPartial({
+            creator: creator.publicKey,
+            event,
+            parentEvent: parent.event,
+            collateralMint: parent.trueMint,
+            conditionalMarket: conditionalMarket(event),
+            tokenVault: conditionalVault(event),
+            tokenProgram: parent.tokenProgram,
+            systemProgram: SystemProgram.programId,
+            rent: SYSVAR_RENT_PUBKEY,
+          })
+          .signers([creator])
+          .rpc();
+      },
+    });
+    await positionAtas(child, holder);
+    await program.methods
+      .buyConditionalPositions(new BN(LAMPORTS_PER_SOL / 2))
+      .accountsPartial({
+        user: holder.publicKey,
+        event: child.event,
+        config: configPda,
+        parentEvent: parent.event,
+        conditionalMarket: conditionalMarket(child.event),
+        tokenVault: conditionalVault(child.event),
+        collateralMint: parent.trueMint,
+        userCollateralAta: ataAddress(holder.publicKey, parent.trueMint),
+        mintAuthority: child.mintAuthority,
+        trueMint: child.trueMint,
+        falseMint: child.falseMint,
+        userTrueAta: ataAddress(holder.publicKey, child.trueMint),
+        userFalseAta: ataAddress(holder.publicKey, child.falseMint),
+        userStats: userStatsPda(holder.publicKey),
+        userPosition: userPositionPda(child.event, holder.publicKey),
+        tokenProgram: child.tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([holder])
+      .rpc();
+  };
+
+  const setupCategorical = async () => {
+    const counter = await ensureCounter(creator);
+    const { count } = await program.account.eventCounter.fetch(counter);
+    cev = pda([Buffer.from("categorical_event"), creator.publicKey.toBuffer(), count.toArrayLike(Buffer, "le", 8)]);
+    const collateralVault = pda([Buffer.from("collateral_vault"), cev.toBuffer()]);
+    const commissionVault = pda([Buffer.from("commission_vault"), cev.toBuffer()]);
+    const mintAuthority = pda([Buffer.from("mint_authority"), cev.toBuffer()]);
+
+    const betEnd = (await chainTime()) + 30;
+    cevRevealEnd = betEnd + 4;
+    const questions: PublicKey[] = [];
+    for (let i = 0; i < OUTCOMES; i++) {
+      questions.push((await createTruthQuestion(creator, `Is exit outcome ${i} the answer?`, betEnd + 2, cevRevealEnd)).question);
+    }
+    await program.methods
+      .createCategoricalEvent(
+        "Which outcome will never be settled?",
+        0,
+        new BN(betEnd),
+        new BN(betEnd + 2),
+        new BN(cevRevealEnd),
+        questions,
+        null
+      )
+      .accountsPartial({
+        creator: creator.publicKey,
+        counter,
+        categoricalEvent: cev,
+        config: configPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    for (let i = 0; i < OUTCOMES; i++) {
+      await program.methods
+        .createCategoricalMint(i)
+        .accountsPartial({
+          creator: creator.publicKey,
+          categoricalEvent: cev,
+          mintAuthority,
+          outcomeMint: pda([Buffer.from("categorical_mint"), cev.toBuffer(), Buffer.from([i])]),
+          collateralVault,
+          commissionVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          rent: SYSVAR_RENT_PUBKEY,
+        })
+        .signers([creator])
+        .rpc();
+    }
+    outcomeMints = (await program.account.categoricalEvent.fetch(cev)).outcomeMints;
+    await send(outcomeMints.map((m) => createAtaIx(holder.publicKey, holder.publicKey, m)), [holder]);
+
+    await program.methods
+      .buyCategoricalSet(new BN(LAMPORTS_PER_SOL))
+      .accountsPartial({
+        user: holder.publicKey,
+        categoricalEvent: cev,
+        config: configPda,
+        collateralVault,
+        commissionVault,
+        mintAuthority,
+        userStats: userStatsPda(holder.publicKey),
+        userPosition: userPositionPda(cev, holder.publicKey),
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        outcomeMints.reduce(
+          (acc, m) =>
+            acc.concat([
+              { pubkey: m, isSigner: false, isWritable: true },
+              { pubkey: ataAddress(holder.publicKey, m), isSigner: false, isWritable: true },
+            ]),
+          [] as { pubkey: PublicKey; isSigner: boolean; isWritable: boolean }[]
+        )
+      )
+      .signers([holder])
+      .rpc();
+  };
+
+  before(async () => {
+    creator = await newUser(30);
+    holder = await newUser(30);
+    await setupToken();
+    await setupConditional();
+    await setupCategorical();
+  });
+
+  it("pays token-collateral holders pro rata plus their share of the forfeited commission", async () => {
+    const v = tokenVaultPdas(tev.event);
+    const exitToken = (amount: BN) =>
+      program.methods
+        .emergencyExitToken(SIDE_TRUE, amount)
+        .accountsPartial({
+          user: holder.publicKey,
+          event: tev.event,
+          collateralVault: v.collateralVault,
+          tokenVault: v.tokenVault,
+          commissionTokenVault: v.commissionTokenVault,
+          collateralMint: mint,
+          userCollateralAta: ataAddress(holder.publicKey, mint),
+          mint: tev.trueMint,
+          userAta: ataAddress(holder.publicKey, tev.trueMint),
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([holder])
+        .rpc();
+
+    await expectError(exitToken(new BN(1_000)), "EmergencyExitNotAvailable");
+    await waitUntil(tev.revealEnd + TEST_EMERGENCY_EXIT_DELAY_SECS + 1);
+
+    const event = await program.account.event.fetch(tev.event);
+    const creatorCut = event.pendingCreatorCommission;
+    const outstanding = event.outstandingTrue.add(event.outstandingFalse);
+    const vault = await tokenBalance(v.tokenVault);
+    const amount = event.outstandingTrue.divn(2);
+
+    const ata = ataAddress(holder.publicKey, mint);
+    const before = await tokenBalance(ata);
+    await exitToken(amount);
+
+    const expected = vault.mul(amount).div(outstanding).add(creatorCut.mul(amount).div(outstanding));
+    expect((await tokenBalance(ata)).sub(before).toString()).to.equal(expected.toString());
+
+    const after = await program.account.event.fetch(tev.event);
+    expect(after.resultStatus).to.equal(RESULT_EMERGENCY_EXIT);
+    expect(after.forfeitedCommission.toString()).to.equal(creatorCut.toString());
+    expect(after.forfeitBaseUnits.toString()).to.equal(outstanding.toString());
+    // the house commission waits for sweep_house_commission_token
+    expect(after.pendingHouseCommission.toString()).to.equal(event.pendingHouseCommission.toString());
+  });
+
+  it("pays conditional holders pro rata in parent tokens", async () => {
+    const exitConditional = (amount: BN) =>
+      program.methods
+        .emergencyExitConditional(SIDE_TRUE, amount)
+        .accountsPartial({
+          user: holder.publicKey,
+          event: child.event,
+          conditionalMarket: conditionalMarket(child.event),
+          tokenVault: conditionalVault(child.event),
+          collateralMint: parent.trueMint,
+          userCollateralAta: ataAddress(holder.publicKey, parent.trueMint),
+          mint: child.trueMint,
+          userAta: ataAddress(holder.publicKey, child.trueMint),
+          tokenProgram: child.tokenProgram,
+        })
+        .signers([holder])
+        .rpc();
+
+    await waitUntil(child.revealEnd + TEST_EMERGENCY_EXIT_DELAY_SECS + 1);
+    const event = await program.account.event.fetch(child.event);
+    const outstanding = event.outstandingTrue.add(event.outstandingFalse);
+    const vault = await tokenBalance(conditionalVault(child.event));
+    const amount = event.outstandingTrue;
+
+    const ata = ataAddress(holder.publicKey, parent.trueMint);
+    const before = await tokenBalance(ata);
+    await exitConditional(amount);
+
+    const expected = vault.mul(amount).div(outstanding);
+    expect((await tokenBalance(ata)).sub(before).toString()).to.equal(expected.toString());
+    expect((await tokenBalance(conditionalVault(child.event))).toString()).to.equal(vault.sub(expected).toString());
+
+    const after = await program.account.event.fetch(child.event);
+    expect(after.resultStatus).to.equal(RESULT_EMERGENCY_EXIT);
+    expect(after.outstandingTrue.toNumber()).to.equal(0);
+    const market = await program.account.conditionalMarket.fetch(conditionalMarket(child.event));
+    expect(market.totalCollateral.toString()).to.equal(vault.sub(expected).toString());
+  });
+
+  it("pays categorical holders pro rata across every outcome", async () => {
+    const collateralVault = pda([Buffer.from("collateral_vault"), cev.toBuffer()]);
+    const commissionVault = pda([Buffer.from("commission_vault"), cev.toBuffer()]);
+    const event = await program.account.categoricalEvent.fetch(cev);
+    const exitCategorical = (amount: BN) =>
+      program.methods
+        .emergencyExitCategorical(0, amount)
+        .accountsPartial({
+          user: holder.publicKey,
+          categoricalEvent: cev,
+          collateralVault,
+          commissionVault,
+          houseTreasury: event.houseWallet,
+          mint: outcomeMints[0],
+          userAta: ataAddress(holder.publicKey, outcomeMints[0]),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([holder])
+        .rpc();
+
+    await expectError(exitCategorical(new BN(1_000)), "EmergencyExitNotAvailable");
+    await waitUntil(cevRevealEnd + TEST_EMERGENCY_EXIT_DELAY_SECS + 1);
+
+    const pending = await program.account.categoricalEvent.fetch(cev);
+    const houseCut = pending.pendingHouseCommission.toNumber();
+    const creatorCut = pending.pendingCreatorCommission;
+    const outstanding = pending.outstanding.reduce((a, o) => a.add(o), new BN(0));
+    // the held Truth cut and the forfeited creator commission both stay with the holders
+    const available = new BN((await lamports(collateralVault)) - (await rentExempt(0))).add(creatorCut);
+    const amount = pending.outstanding[0];
+
+    const houseBefore = await lamports(pending.houseWallet);
+    const before = await lamports(holder.publicKey);
+    await exitCategorical(amount);
+
+    const expected = available.mul(amount).div(outstanding);
+    expect((await lamports(holder.publicKey)) - before).to.equal(expected.toNumber());
+    expect((await lamports(pending.houseWallet)) - houseBefore).to.equal(houseCut);
+
+    const after = await program.account.categoricalEvent.fetch(cev);
+    expect(after.resolved).to.equal(true);
+    expect(after.resultStatus).to.equal(RESULT_EMERGENCY_EXIT);
+    expect(after.pendingTruthCommission.toNumber()).to.equal(0);
+    expect(after.pendingCreatorCommission.toNumber()).to.equal(0);
+    expect(after.outstanding[0].toNumber()).to.equal(0);
+  });
+});
@@ -0,0 +1,92 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  TEST_BUY_FEE_BPS,
+  TestEvent,
+  buyPair,
+  chainTime,
+  configPda,
+  createEvent,
+  expectError,
+  newUser,
+  program,
+} from "./helpers";
+
+const FLAT = 0;
+const LINEAR = 1;
+const STEP = 2;
+
+describe("user-012: dynamic buy fee", () => {
+  let creator: Keypair;
+  let other: Keypair;
+
+  const setCurve = (
+    ev: TestEvent,
+    signer: Keypair,
+    curve: number,
+    start: number,
+    maxFeeBps: number,
+    steps: number,
+    earlyBirdEnd = 0,
+    earlyBirdDiscountBps = 0
+  ) =>
+    program.methods
+      .setFeeCurve(curve, new BN(start), maxFeeBps, steps, new BN(earlyBirdEnd), earlyBirdDiscountBps)
+      .accountsPartial({ creator: signer.publicKey, event: ev.event, config: configPda })
+      .signers([signer])
+      .rpc();
+
+  // fee actually charged on a 1 SOL buy by a fresh wallet (no volume discount)
+  const chargedFeeBps = async (ev: TestEvent) => {
+    await buyPair(ev, await newUser(5), LAMPORTS_PER_SOL);
+    const event = await program.account.event.fetch(ev.event);
+    return ((LAMPORTS_PER_SOL - event.totalIssuedPerSide.toNumber()) * 10_000) / LAMPORTS_PER_SOL;
+  };
+
+  before(async () => {
+    creator = await newUser(30);
+    other = await newUser(5);
+  });
+
+  it("validates the curve parameters", async () => {
+    const ev = await createEvent(creator, { betSecs: 120 });
+    const now = await chainTime();
+
+    await expectError(setCurve(ev, other, LINEAR, now, 300, 0), "Unauthorized");
+    await expectError(setCurve(ev, creator, 3, now, 300, 0), "InvalidFeeCurve");
+    await expectError(setCurve(ev, creator, LINEAR, ev.betEnd, 300, 0), "InvalidFeeCurve");
+    await expectError(setCurve(ev, creator, LINEAR, now, TEST_BUY_FEE_BPS - 1, 0), "InvalidFeeCurve");
+    await expectError(setCurve(ev, creator, LINEAR, now, 501, 0), "InvalidFeeCurve");
+    await expectError(setCurve(ev, creator, STEP, now, 300, 0), "InvalidFeeCurve");
+    await expectError(setCurve(ev, creator, STEP, now, 300, 25), "InvalidFeeCurve");
+    await expectError(setCurve(ev, creator, FLAT, 0, 0, 0, ev.betEnd + 1, 1_000), "InvalidFeeCurve");
+    await expectError(setCurve(ev, creator, FLAT, 0, 0, 0, ev.betEnd, 10_001), "InvalidFeeCurve");
+    // early bird must end before the curve starts rising
+    await expectError(setCurve(ev, creator, LINEAR, now, 300, 0, now + 10, 1_000), "InvalidFeeCurve");
+
+    await setCurve(ev, creator, LINEAR, now, 300, 0);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.feeCurve).to.equal(LINEAR);
+    expect(event.feeCurveMaxBps).to.equal(300);
+  });
+
+  it("charges the full step once a single-step curve has started", async () => {
+    const ev = await createEvent(creator, { betSecs: 120 });
+    await setCurve(ev, creator, STEP, (await chainTime()) - 10, 400, 1);
+    expect(await chargedFeeBps(ev)).to.equal(400);
+  });
+
+  it("discounts the base fee during the early-bird window", async () => {
+    const ev = await createEvent(creator, { betSecs: 120 });
+    await setCurve(ev, creator, FLAT, 0, 0, 0, ev.betEnd, 5_000);
+    expect(await chargedFeeBps(ev)).to.equal(TEST_BUY_FEE_BPS / 2);
+  });
+
+  it("locks the curve after the first buy", async () => {
+    const ev = await createEvent(creator, { betSecs: 120 });
+    expect(await chargedFeeBps(ev)).to.equal(TEST_BUY_FEE_BPS);
+    await expectError(setCurve(ev, creator, LINEAR, await chainTime(), 300, 0), "FeeCurveLocked");
+  });
+});
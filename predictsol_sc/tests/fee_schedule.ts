@@ -0,0 +1,73 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  TEST_BUY_FEE_BPS,
+  buyPair,
+  createEvent,
+  expectError,
+  feeSplits,
+  lamports,
+  newUser,
+  program,
+} from "./helpers";
+
+// config bounds from testConfigParams: fee 0..=500, truth >= 1000, house >= 1000, creator <= 5000
+describe("user-011: per-event fee schedule", () => {
+  let creator: Keypair;
+  let buyer: Keypair;
+
+  before(async () => {
+    creator = await newUser(20);
+    buyer = await newUser(20);
+  });
+
+  it("rejects schedules outside the config bounds", async () => {
+    const bad = [
+      { feeBps: 600, truthShareBps: 3_000, creatorShareBps: 3_000 },
+      { feeBps: 200, truthShareBps: 500, creatorShareBps: 3_000 },
+      { feeBps: 200, truthShareBps: 3_000, creatorShareBps: 6_000 },
+      { feeBps: 200, truthShareBps: 5_000, creatorShareBps: 4_500 }, // house 500
+    ];
+    for (const feeSchedule of bad) {
+      await expectError(createEvent(creator, { feeSchedule }), "InvalidFeeSchedule");
+    }
+  });
+
+  it("falls back to the protocol default split", async () => {
+    const ev = await createEvent(creator);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.buyFeeBps).to.equal(TEST_BUY_FEE_BPS);
+    expect(event.truthShareBps).to.equal(3_333);
+    expect(event.creatorShareBps).to.equal(3_333);
+  });
+
+  it("charges the creator's schedule and routes each cut", async () => {
+    const schedule = { feeBps: 300, truthShareBps: 2_000, creatorShareBps: 5_000 };
+    const ev = await createEvent(creator, { feeSchedule: schedule });
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.buyFeeBps).to.equal(300);
+    expect(event.truthShareBps).to.equal(2_000);
+    expect(event.creatorShareBps).to.equal(5_000);
+    expect(event.houseShareBps).to.equal(3_000);
+
+    const amount = LAMPORTS_PER_SOL;
+    const split = feeSplits(amount, schedule.feeBps, schedule.truthShareBps, schedule.creatorShareBps);
+    const [truthBefore, commissionBefore, collateralBefore] = await Promise.all([
+      lamports(ev.questionVault),
+      lamports(ev.commissionVault),
+      lamports(ev.collateralVault),
+    ]);
+
+    await buyPair(ev, buyer, amount);
+
+    expect((await lamports(ev.questionVault)) - truthBefore).to.equal(split.truthCut);
+    expect((await lamports(ev.commissionVault)) - commissionBefore).to.equal(split.creatorCut + split.houseCut);
+    expect((await lamports(ev.collateralVault)) - collateralBefore).to.equal(split.net);
+
+    const after = await program.account.event.fetch(ev.event);
+    expect(after.totalIssuedPerSide.toNumber()).to.equal(split.net);
+    expect(after.pendingCreatorCommission.toNumber()).to.equal(split.creatorCut);
+    expect(after.pendingHouseCommission.toNumber()).to.equal(split.houseCut);
+  });
+});
@@ -0,0 +1,123 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  SystemProgram,
+  TEST_CONFIG_TIMELOCK_SECS,
+  admin,
+  configPda,
+  connection,
+  ensureConfig,
+  expectError,
+  newUser,
+  pendingChangePda,
+  program,
+  testConfigParams,
+  waitUntil,
+} from "./helpers";
+
+describe("user-019: timelocked governance", () => {
+  let stranger: Keypair;
+  let nominee: Keypair;
+
+  const propose = (signer: Keypair, overrides: Record<string, unknown>) =>
+    program.methods
+      .proposeConfigChange(testConfigParams(overrides) as any)
+      .accountsPartial({
+        admin: signer.publicKey,
+        config: configPda,
+        pendingChange: pendingChangePda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signer === admin ? [] : [signer])
+      .rpc();
+
+  const cancel = (signer: Keypair) =>
+    program.methods
+      .cancelConfigChange()
+      .accountsPartial({ admin: signer.publicKey, config: configPda, pendingChange: pendingChangePda })
+      .signers(signer === admin ? [] : [signer])
+      .rpc();
+
+  const execute = () =>
+    program.methods
+      .executeConfigChange()
+      .accountsPartial({ admin: admin.publicKey, config: configPda, pendingChange: pendingChangePda })
+      .rpc();
+
+  const nominate = (signer: Keypair, next: Keypair) =>
+    program.methods
+      .nominateAdmin(next.publicKey)
+      .accountsPartial({ admin: signer.publicKey, config: configPda })
+      .signers(signer === admin ? [] : [signer])
+      .rpc();
+
+  const accept = (signer: Keypair) =>
+    program.methods
+      .acceptAdmin()
+      .accountsPartial({ newAdmin: signer.publicKey, config: configPda })
+      .signers(signer === admin ? [] : [signer])
+      .rpc();
+
+  before(async () => {
+    await ensureConfig();
+    stranger = await newUser(2);
+    nominee = await newUser(2);
+  });
+
+  it("queues config changes behind the timelock", async () => {
+    await expectError(propose(stranger, { consensusThresholdBps: 7_000 }), "Unauthorized");
+    await expectError(propose(admin, { consensusThresholdBps: 5_000 }), "InvalidConfig");
+
+    await propose(admin, { consensusThresholdBps: 7_000 });
+    const pending = await program.account.pendingChange.fetch(pendingChangePda);
+    expect(pending.params.consensusThresholdBps).to.equal(7_000);
+    expect(pending.eta.sub(pending.proposedAt).toNumber()).to.equal(TEST_CONFIG_TIMELOCK_SECS);
+
+    // one change at a time
+    await expectError(propose(admin, { consensusThresholdBps: 8_000 }), "already in use");
+    await expectError(execute(), "TimelockNotElapsed");
+  });
+
+  it("lets the admin cancel a queued change", async () => {
+    await expectError(cancel(stranger), "Unauthorized");
+    await cancel(admin);
+    expect(await connection.getAccountInfo(pendingChangePda)).to.equal(null);
+    expect((await program.account.protocolConfig.fetch(configPda)).consensusThresholdBps).to.equal(6_000);
+  });
+
+  it("applies a queued change once the timelock has run", async () => {
+    await propose(admin, { revoteFundingBps: 6_000 });
+    const pending = await program.account.pendingChange.fetch(pendingChangePda);
+    await waitUntil(pending.eta.toNumber());
+    await execute();
+
+    expect(await connection.getAccountInfo(pendingChangePda)).to.equal(null);
+    const config = await program.account.protocolConfig.fetch(configPda);
+    expect(config.revoteFundingBps).to.equal(6_000);
+    expect(config.consensusThresholdBps).to.equal(6_000);
+
+    // restore the test config for the remaining suites
+    await propose(admin, {});
+    await waitUntil((await program.account.pendingChange.fetch(pendingChangePda)).eta.toNumber());
+    await execute();
+    expect((await program.account.protocolConfig.fetch(configPda)).revoteFundingBps).to.equal(5_000);
+  });
+
+  it("hands the admin role over in two steps", async () => {
+    await expectError(nominate(stranger, stranger), "Unauthorized");
+    await nominate(admin, nominee);
+    expect((await program.account.protocolConfig.fetch(configPda)).pendingAdmin.equals(nominee.publicKey)).to.equal(true);
+
+    await expectError(accept(stranger), "Unauthorized");
+    await accept(nominee);
+    let config = await program.account.protocolConfig.fetch(configPda);
+    expect(config.admin.equals(nominee.publicKey)).to.equal(true);
+    await expectError(propose(admin, { consensusThresholdBps: 7_000 }), "Unauthorized");
+
+    // hand it back so the remaining suites keep the provider wallet as admin
+    await nominate(nominee, admin);
+    await accept(admin);
+    config = await program.account.protocolConfig.fetch(configPda);
+    expect(config.admin.equals(admin.publicKey)).to.equal(true);
+  });
+});
@@ -0,0 +1,711 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  Transaction,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { expect } from "chai";
+import { PredictolSc } from "../target/types/predictol_sc";
+import truthIdl from "../idls/truth_network.json";
+
+// Shared setup for the ts-mocha suites. The local validator clones the Truth Network
+// and Metaplex programs (see [test.validator] in Anchor.toml); every suite runs in the
+// same process, so the protocol config below is created once and shared.
+
+anchor.setProvider(anchor.AnchorProvider.env());
+
+export const provider = anchor.getProvider() as anchor.AnchorProvider;
+export const connection = provider.connection;
+export const program = anchor.workspace.PredictolSc as Program<PredictolSc>;
+export const truth = new Program(truthIdl as anchor.Idl, provider);
+export const admin = (provider.wallet as anchor.Wallet).payer;
+
+export const TOKEN_PROGRAM_ID = anchor.utils.token.TOKEN_PROGRAM_ID;
+export const ASSOCIATED_PROGRAM_ID = anchor.utils.token.ASSOCIATED_PROGRAM_ID;
+export const TOKEN_2022_PROGRAM_ID = new PublicKey("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+export const METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+export const BPF_LOADER_UPGRADEABLE_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+
+export const BPS_DENOM = 10_000;
+export const SIDE_TRUE = 1;
+export const SIDE_FALSE = 2;
+
+export const RESULT_PENDING = 0;
+export const RESULT_RESOLVED_WINNER = 1;
+export const RESULT_FINALIZED_NO_VOTES = 2;
+export const RESULT_VOIDED = 7;
+export const RESULT_EMERGENCY_EXIT = 6;
+
+export const DISPUTE_NONE = 0;
+export const DISPUTE_OPEN = 1;
+export const DISPUTE_SETTLED = 2;
+
+export const PAUSE_BUY = 1 << 0;
+export const PAUSE_PAIR_REDEEM = 1 << 1;
+export const PAUSE_FINAL_REDEEM = 1 << 2;
+export const PAUSE_SWEEP = 1 << 3;
+export const PAUSE_RESOLVE = 1 << 4;
+
+// ------------------------------------------------------------
+// protocol config used by every suite
+// ------------------------------------------------------------
+export const truthFeeRecipient = Keypair.generate();
+export const voidAuthority = Keypair.generate();
+export const beneficiaries = [Keypair.generate(), Keypair.generate()];
+
+export const TEST_BUY_FEE_BPS = 100;
+export const TEST_REDEEM_FEE_BPS = 0;
+export const TEST_SWEEP_DELAY_SECS = 30;
+export const TEST_EMERGENCY_EXIT_DELAY_SECS = 10;
+export const TEST_CONFIG_TIMELOCK_SECS = 5; // `test-delays` build
+export const TEST_REFERRAL_SHARE_BPS = 2_000;
+export const TEST_DISPUTE_WINDOW_SECS = 20;
+export const TEST_DISPUTE_BOND = new BN(LAMPORTS_PER_SOL / 10);
+
+// unused tiers come first so the table stays ascending
+export const TEST_FEE_TIERS = [
+  { minVolume: new BN(0), discountBps: 0 },
+  { minVolume: new BN(0), discountBps: 0 },
+  { minVolume: new BN(2 * LAMPORTS_PER_SOL), discountBps: 1_000 },
+  { minVolume: new BN(20 * LAMPORTS_PER_SOL), discountBps: 2_500 },
+];
+
+export function pda(seeds: (Buffer | Uint8Array)[], programId = program.programId): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+export const configPda = pda([Buffer.from("protocol_config")]);
+export const pendingChangePda = pda([Buffer.from("pending_change")]);
+export const houseTreasuryPda = pda([Buffer.from("house_treasury")]);
+
+export function testConfigParams(overrides: Record<string, unknown> = {}) {
+  return {
+    houseWallet: houseTreasuryPda,
+    consensusThresholdBps: 6_000,
+    buyFeeBps: TEST_BUY_FEE_BPS,
+    redeemFeeBps: TEST_REDEEM_FEE_BPS,
+    // needs a `test-delays` build, see Anchor.toml
+    unclaimedSweepDelaySecs: new BN(TEST_SWEEP_DELAY_SECS),
+    trueTokenUri: "https://predictsol.com/tokens/true.json",
+    falseTokenUri: "https://predictsol.com/tokens/false.json",
+    minBuyFeeBps: 0,
+    maxBuyFeeBps: 500,
+    minTruthShareBps: 1_000,
+    minHouseShareBps: 1_000,
+    maxCreatorShareBps: 5_000,
+    referralShareBps: TEST_REFERRAL_SHARE_BPS,
+    feeTiers: TEST_FEE_TIERS,
+    creatorClawback: true,
+    treasuryBeneficiaries: [
+      { wallet: beneficiaries[0].publicKey, weightBps: 6_000 },
+      { wallet: beneficiaries[1].publicKey, weightBps: 4_000 },
+      { wallet: PublicKey.default, weightBps: 0 },
+      { wallet: PublicKey.default, weightBps: 0 },
+    ],
+    // needs a `test-delays` build too
+    emergencyExitDelaySecs: new BN(TEST_EMERGENCY_EXIT_DELAY_SECS),
+    disputeWindowSecs: new BN(TEST_DISPUTE_WINDOW_SECS),
+    disputeBondLamports: TEST_DISPUTE_BOND,
+    revoteMaxRounds: 1,
+    revoteFundingBps: 5_000,
+    truthFeeRecipient: truthFeeRecipient.publicKey,
+    voidAuthority: voidAuthority.publicKey,
+    ...overrides,
+  };
+}
+
+export function programDataPda(): PublicKey {
+  return pda([program.programId.toBuffer()], BPF_LOADER_UPGRADEABLE_ID);
+}
+
+let configReady: Promise<void> | null = null;
+
+// Creates the protocol config and the house treasury on first use.
+export function ensureConfig(): Promise<void> {
+  if (!configReady) {
+    configReady = (async () => {
+      const existing = await connection.getAccountInfo(configPda);
+      if (!existing) {
+        await program.methods
+          .initializeConfig(testConfigParams() as any)
+          .accountsPartial({
+            admin: admin.publicKey,
+            config: configPda,
+            program: program.programId,
+            programData: programDataPda(),
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+      const treasury = await connection.getAccountInfo(houseTreasuryPda);
+      if (!treasury || !treasury.owner.equals(program.programId)) {
+        await program.methods
+          .initHouseTreasury()
+          .accountsPartial({
+            admin: admin.publicKey,
+            config: configPda,
+            houseTreasury: houseTreasuryPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+      }
+    })();
+  }
+  return configReady;
+}
+
+// ------------------------------------------------------------
+// wallets, time and assertions
+// ------------------------------------------------------------
+export async function airdrop(to: PublicKey, sol = 10): Promise<void> {
+  const sig = await connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+  const latest = await connection.getLatestBlockhash();
+  await connection.confirmTransaction({ signature: sig, ...latest }, "confirmed");
+}
+
+export async function newUser(sol = 10): Promise<Keypair> {
+  const kp = Keypair.generate();
+  await airdrop(kp.publicKey, sol);
+  return kp;
+}
+
+export async function chainTime(): Promise<number> {
+  const slot = await connection.getSlot("confirmed");
+  return (await connection.getBlockTime(slot)) ?? Math.floor(Date.now() / 1000);
+}
+
+export async function waitUntil(unixTs: number): Promise<void> {
+  while ((await chainTime()) < unixTs) {
+    await new Promise((r) => setTimeout(r, 500));
+  }
+}
+
+export async function lamports(key: PublicKey): Promise<number> {
+  return connection.getBalance(key, "confirmed");
+}
+
+export async function tokenBalance(ata: PublicKey): Promise<BN> {
+  const info = await connection.getTokenAccountBalance(ata, "confirmed");
+  return new BN(info.value.amount);
+}
+
+export async function rentExempt(space = 0): Promise<number> {
+  return connection.getMinimumBalanceForRentExemption(space);
+}
+
+// Expects the call to fail with the given program error (or any error whose text contains `code`).
+export async function expectError(call: Promise<unknown>, code: string): Promise<void> {
+  let err: any = null;
+  try {
+    await call;
+  } catch (e) {
+    err = e;
+  }
+  expect(err, `expected ${code}`).to.not.equal(null);
+  const actual =
+    err instanceof anchor.AnchorError
+      ? err.error.errorCode.code
+      : `${err}\n${(err.logs ?? []).join("\n")}`;
+  expect(actual).to.include(code);
+}
+
+export async function send(ixs: TransactionInstruction[], signers: Keypair[]): Promise<string> {
+  const tx = new Transaction().add(...ixs);
+  return provider.sendAndConfirm(tx, signers);
+}
+
+// ------------------------------------------------------------
+// SPL token plumbing without @solana/spl-token
+// ------------------------------------------------------------
+export function ataAddress(owner: PublicKey, mint: PublicKey, tokenProgram = TOKEN_PROGRAM_ID): PublicKey {
+  return pda([owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()], ASSOCIATED_PROGRAM_ID);
+}
+
+export function createAtaIx(
+  payer: PublicKey,
+  owner: PublicKey,
+  mint: PublicKey,
+  tokenProgram = TOKEN_PROGRAM_ID
+): TransactionInstruction {
+  return new TransactionInstruction({
+    programId: ASSOCIATED_PROGRAM_ID,
+    keys: [
+      { pubkey: payer, isSigner: true, isWritable: true },
+      { pubkey: ataAddress(owner, mint, tokenProgram), isSigner: false, isWritable: true },
+      { pubkey: owner, isSigner: false, isWritable: false },
+      { pubkey: mint, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: tokenProgram, isSigner: false, isWritable: false },
+    ],
+    data: Buffer.from([1]), // CreateIdempotent
+  });
+}
+
+export async function createAta(
+  payer: Keypair,
+  owner: PublicKey,
+  mint: PublicKey,
+  tokenProgram = TOKEN_PROGRAM_ID
+): Promise<PublicKey> {
+  await send([createAtaIx(payer.publicKey, owner, mint, tokenProgram)], [payer]);
+  return ataAddress(owner, mint, tokenProgram);
+}
+
+// Classic SPL mint with `authority` as mint authority, used as token collateral.
+export async function createMint(authority: Keypair, decimals: number): Promise<PublicKey> {
+  const mint = Keypair.generate();
+  const space = 82;
+  const data = Buffer.alloc(1 + 1 + 32 + 1);
+  data.writeUInt8(20, 0); // InitializeMint2
+  data.writeUInt8(decimals, 1);
+  authority.publicKey.toBuffer().copy(data, 2);
+  data.writeUInt8(0, 34); // no freeze authority
+  await send(
+    [
+      SystemProgram.createAccount({
+        fromPubkey: authority.publicKey,
+        newAccountPubkey: mint.publicKey,
+        lamports: await rentExempt(space),
+        space,
+        programId: TOKEN_PROGRAM_ID,
+      }),
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [{ pubkey: mint.publicKey, isSigner: false, isWritable: true }],
+        data,
+      }),
+    ],
+    [authority, mint]
+  );
+  return mint.publicKey;
+}
+
+export async function mintTo(authority: Keypair, mint: PublicKey, dest: PublicKey, amount: BN): Promise<void> {
+  const data = Buffer.concat([Buffer.from([7]), amount.toArrayLike(Buffer, "le", 8)]); // MintTo
+  await send(
+    [
+      new TransactionInstruction({
+        programId: TOKEN_PROGRAM_ID,
+        keys: [
+          { pubkey: mint, isSigner: false, isWritable: true },
+          { pubkey: dest, isSigner: false, isWritable: true },
+          { pubkey: authority.publicKey, isSigner: true, isWritable: false },
+        ],
+        data,
+      }),
+    ],
+    [authority]
+  );
+}
+
+// ------------------------------------------------------------
+// Truth Network question
+// ------------------------------------------------------------
+export const TRUTH_REWARD = new BN(LAMPORTS_PER_SOL / 20); // Truth Network minimum
+
+export async function createTruthQuestion(
+  asker: Keypair,
+  text: string,
+  commitEnd: number,
+  revealEnd: number
+): Promise<{ question: PublicKey; vault: PublicKey }> {
+  const questionCounter = pda([Buffer.from("question_counter"), asker.publicKey.toBuffer()], truth.programId);
+  let counter: any = await (truth.account as any).questionCounter.fetchNullable(questionCounter);
+  if (!counter) {
+    await truth.methods
+      .initializeCounter()
+      .accountsPartial({ questionCounter, asker: asker.publicKey, systemProgram: SystemProgram.programId })
+      .signers([asker])
+      .rpc();
+    counter = await (truth.account as any).questionCounter.fetch(questionCounter);
+  }
+
+  const question = pda(
+    [Buffer.from("question"), asker.publicKey.toBuffer(), new BN(counter.count).toArrayLike(Buffer, "le", 8)],
+    truth.programId
+  );
+  const vault = pda([Buffer.from("vault"), question.toBuffer()], truth.programId);
+  await truth.methods
+    .createQuestion(text, TRUTH_REWARD, new BN(commitEnd), new BN(revealEnd))
+    .accountsPartial({
+      questionCounter,
+      question,
+      vault,
+      asker: asker.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([asker])
+    .rpc();
+  return { question, vault };
+}
+
+// ------------------------------------------------------------
+// binary events
+// ------------------------------------------------------------
+export interface TestEvent {
+  creator: Keypair;
+  event: PublicKey;
+  eventId: BN;
+  title: string;
+  question: PublicKey;
+  questionVault: PublicKey;
+  trueMint: PublicKey;
+  falseMint: PublicKey;
+  collateralVault: PublicKey;
+  commissionVault: PublicKey;
+  mintAuthority: PublicKey;
+  tokenProgram: PublicKey;
+  betEnd: number;
+  commitEnd: number;
+  revealEnd: number;
+}
+
+export interface EventOptions {
+  title?: string;
+  betSecs?: number;       // from now
+  commitSecs?: number;    // after bet end
+  revealSecs?: number;    // after commit end
+  feeSchedule?: { feeBps: number; truthShareBps: number; creatorShareBps: number } | null;
+  token2022?: boolean;
+  beforeMints?: (event: PublicKey) => Promise<void>; // e.g. init a scalar or conditional market
+}
+
+export function eventPdas(event: PublicKey) {
+  return {
+    trueMint: pda([Buffer.from("true_mint"), event.toBuffer()]),
+    falseMint: pda([Buffer.from("false_mint"), event.toBuffer()]),
+    collateralVault: pda([Buffer.from("collateral_vault"), event.toBuffer()]),
+    commissionVault: pda([Buffer.from("commission_vault"), event.toBuffer()]),
+    mintAuthority: pda([Buffer.from("mint_authority"), event.toBuffer()]),
+  };
+}
+
+export function metadataPda(mint: PublicKey): PublicKey {
+  return pda([Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), mint.toBuffer()], METADATA_PROGRAM_ID);
+}
+
+export function counterPda(creator: PublicKey): PublicKey {
+  return pda([Buffer.from("event_counter"), creator.toBuffer()]);
+}
+
+export async function ensureCounter(creator: Keypair): Promise<PublicKey> {
+  const counter = counterPda(creator.publicKey);
+  if (!(await connection.getAccountInfo(counter))) {
+    await program.methods
+      .initializeEventCounter()
+      .accountsPartial({ creator: creator.publicKey, counter, systemProgram: SystemProgram.programId })
+      .signers([creator])
+      .rpc();
+  }
+  return counter;
+}
+
+// Truth question + create_event_core + mints, with short windows so the suites can reach
+// resolution on a local validator.
+export async function createEvent(creator: Keypair, opts: EventOptions = {}): Promise<TestEvent> {
+  await ensureConfig();
+  const counter = await ensureCounter(creator);
+  const { count } = await program.account.eventCounter.fetch(counter);
+
+  const now = await chainTime();
+  const betEnd = now + (opts.betSecs ?? 30);
+  const commitEnd = betEnd + (opts.commitSecs ?? 2);
+  const revealEnd = commitEnd + (opts.revealSecs ?? 2);
+  const title = opts.title ?? `Will test event ${count.toString()} of ${creator.publicKey.toBase58().slice(0, 6)} resolve?`;
+
+  const { question, vault } = await createTruthQuestion(creator, title, commitEnd, revealEnd);
+
+  const event = pda([Buffer.from("event"), creator.publicKey.toBuffer(), count.toArrayLike(Buffer, "le", 8)]);
+  await program.methods
+    .createEventCore(title, 0, new BN(betEnd), new BN(commitEnd), new BN(revealEnd), question, opts.feeSchedule ?? null)
+    .accountsPartial({
+      creator: creator.publicKey,
+      counter,
+      event,
+      config: configPda,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+  if (opts.beforeMints) {
+    await opts.beforeMints(event);
+  }
+
+  const p = eventPdas(event);
+  const tokenProgram = opts.token2022 ? TOKEN_2022_PROGRAM_ID : TOKEN_PROGRAM_ID;
+  if (opts.token2022) {
+    await program.methods
+      .createEventMints2022()
+      .accountsPartial({
+        creator: creator.publicKey,
+        event,
+        ...p,
+        config: configPda,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+  } else {
+    await program.methods
+      .createEventMints()
+      .accountsPartial({
+        creator: creator.publicKey,
+        event,
+        ...p,
+        metadataProgram: METADATA_PROGRAM_ID,
+        trueMetadata: metadataPda(p.trueMint),
+        falseMetadata: metadataPda(p.falseMint),
+        config: configPda,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([creator])
+      .rpc();
+  }
+
+  return {
+    creator,
+    event,
+    eventId: count,
+    title,
+    question,
+    questionVault: vault,
+    ...p,
+    tokenProgram,
+    betEnd,
+    commitEnd,
+    revealEnd,
+  };
+}
+
+export function userStatsPda(user: PublicKey): PublicKey {
+  return pda([Buffer.from("user_stats"), user.toBuffer()]);
+}
+
+export function userPositionPda(event: PublicKey, user: PublicKey): PublicKey {
+  return pda([Buffer.from("user_position"), event.toBuffer(), user.toBuffer()]);
+}
+
+export function referralPda(referrer: PublicKey): PublicKey {
+  return pda([Buffer.from("referral"), referrer.toBuffer()]);
+}
+
+export function referralVaultPda(referrer: PublicKey): PublicKey {
+  return pda([Buffer.from("referral_vault"), referrer.toBuffer()]);
+}
+
+export function disputePda(event: PublicKey): PublicKey {
+  return pda([Buffer.from("dispute"), event.toBuffer()]);
+}
+
+// TRUE / FALSE token accounts of `user` for the event, created if missing.
+export async function positionAtas(ev: TestEvent, user: Keypair): Promise<{ trueAta: PublicKey; falseAta: PublicKey }> {
+  await send(
+    [
+      createAtaIx(user.publicKey, user.publicKey, ev.trueMint, ev.tokenProgram),
+      createAtaIx(user.publicKey, user.publicKey, ev.falseMint, ev.tokenProgram),
+    ],
+    [user]
+  );
+  return {
+    trueAta: ataAddress(user.publicKey, ev.trueMint, ev.tokenProgram),
+    falseAta: ataAddress(user.publicKey, ev.falseMint, ev.tokenProgram),
+  };
+}
+
+export function buyPairIx(ev: TestEvent, user: Keypair, amount: number | BN, referrer: PublicKey | null = null) {
+  return program.methods.buyPositionsWithFee(new BN(amount)).accountsPartial({
+    user: user.publicKey,
+    event: ev.event,
+    collateralVault: ev.collateralVault,
+    commissionVault: ev.commissionVault,
+    mintAuthority: ev.mintAuthority,
+    trueMint: ev.trueMint,
+    falseMint: ev.falseMint,
+    userTrueAta: ataAddress(user.publicKey, ev.trueMint, ev.tokenProgram),
+    userFalseAta: ataAddress(user.publicKey, ev.falseMint, ev.tokenProgram),
+    truthNetworkQuestion: ev.question,
+    truthNetworkVault: ev.questionVault,
+    userStats: userStatsPda(user.publicKey),
+    userPosition: userPositionPda(ev.event, user.publicKey),
+    config: configPda,
+    referral: referrer ? referralPda(referrer) : null,
+    referralVault: referrer ? referralVaultPda(referrer) : null,
+    tokenProgram: ev.tokenProgram,
+    systemProgram: SystemProgram.programId,
+  });
+}
+
+// Buys TRUE + FALSE pairs for `amount` lamports (fee included).
+export async function buyPair(
+  ev: TestEvent,
+  user: Keypair,
+  amount: number | BN,
+  referrer: PublicKey | null = null
+): Promise<string> {
+  await positionAtas(ev, user);
+  return buyPairIx(ev, user, amount, referrer).signers([user]).rpc();
+}
+
+// ------------------------------------------------------------
+// SPL token collateral events
+// ------------------------------------------------------------
+export function tokenVaultPdas(event: PublicKey) {
+  return {
+    collateralVault: pda([Buffer.from("collateral_vault"), event.toBuffer()]),
+    tokenVault: pda([Buffer.from("token_collateral_vault"), event.toBuffer()]),
+    truthTokenVault: pda([Buffer.from("truth_token_vault"), event.toBuffer()]),
+    commissionTokenVault: pda([Buffer.from("commission_token_vault"), event.toBuffer()]),
+  };
+}
+
+export function initTokenCollateralIx(creator: Keypair, event: PublicKey, mint: PublicKey) {
+  return program.methods
+    .initTokenCollateral()
+    .accountsPartial({
+      creator: creator.publicKey,
+      event,
+      collateralMint: mint,
+      ...tokenVaultPdas(event),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .signers([creator]);
+}
+
+// Event collateralised by `mint`, switched over before its position mints exist.
+export async function createTokenEvent(creator: Keypair, mint: PublicKey, opts: EventOptions = {}): Promise<TestEvent> {
+  return createEvent(creator, {
+    ...opts,
+    beforeMints: async (event) => {
+      await initTokenCollateralIx(creator, event, mint).rpc();
+    },
+  });
+}
+
+// Buys TRUE + FALSE pairs for `amount` collateral tokens (fee included).
+export async function buyPairWithToken(ev: TestEvent, user: Keypair, mint: PublicKey, amount: BN): Promise<string> {
+  await positionAtas(ev, user);
+  const v = tokenVaultPdas(ev.event);
+  return program.methods
+    .buyPositionsWithToken(amount)
+    .accountsPartial({
+      user: user.publicKey,
+      event: ev.event,
+      config: configPda,
+      tokenVault: v.tokenVault,
+      collateralMint: mint,
+      userCollateralAta: ataAddress(user.publicKey, mint),
+      mintAuthority: ev.mintAuthority,
+      trueMint: ev.trueMint,
+      falseMint: ev.falseMint,
+      userTrueAta: ataAddress(user.publicKey, ev.trueMint),
+      userFalseAta: ataAddress(user.publicKey, ev.falseMint),
+      truthNetworkQuestion: ev.question,
+      truthTokenVault: v.truthTokenVault,
+      commissionTokenVault: v.commissionTokenVault,
+      userStats: userStatsPda(user.publicKey),
+      userPosition: userPositionPda(ev.event, user.publicKey),
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc();
+}
+
+// Waits for the reveal window and stores the Truth Network result. With no votes cast the
+// event ends as RESULT_FINALIZED_NO_VOTES.
+export async function resolveEvent(ev: TestEvent): Promise<void> {
+  const event = await program.account.event.fetch(ev.event);
+  await waitUntil(ev.revealEnd + 1);
+  await program.methods
+    .fetchAndStoreWinner()
+    .accountsPartial({
+      event: ev.event,
+      config: configPda,
+      truthNetworkQuestion: event.truthQuestion,
+      truthNetworkProgram: truth.programId,
+      houseTreasury: event.houseWallet,
+      commissionVault: ev.commissionVault,
+      collateralVault: ev.collateralVault,
+      systemProgram: SystemProgram.programId,
+    })
+    .rpc();
+}
+
+// Governance void, signed by the configured void authority.
+export async function voidEvent(ev: TestEvent): Promise<void> {
+  await airdrop(voidAuthority.publicKey, 1);
+  await program.methods
+    .voidEvent()
+    .accountsPartial({
+      authority: voidAuthority.publicKey,
+      config: configPda,
+      event: ev.event,
+      commissionVault: ev.commissionVault,
+      collateralVault: ev.collateralVault,
+      dispute: null,
+      challenger: null,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([voidAuthority])
+    .rpc();
+}
+
+// Waits until the event's dispute window has passed.
+export async function waitFinal(ev: TestEvent): Promise<void> {
+  const event = await program.account.event.fetch(ev.event);
+  await waitUntil(event.disputeDeadline.toNumber() + 1);
+}
+
+export function redeemPairIx(ev: TestEvent, user: Keypair, amount: number | BN) {
+  return program.methods.redeemPairWhileActive(new BN(amount)).accountsPartial({
+    user: user.publicKey,
+    event: ev.event,
+    config: configPda,
+    collateralVault: ev.collateralVault,
+    trueMint: ev.trueMint,
+    falseMint: ev.falseMint,
+    userTrueAta: ataAddress(user.publicKey, ev.trueMint, ev.tokenProgram),
+    userFalseAta: ataAddress(user.publicKey, ev.falseMint, ev.tokenProgram),
+    userStats: userStatsPda(user.publicKey),
+    userPosition: userPositionPda(ev.event, user.publicKey),
+    tokenProgram: ev.tokenProgram,
+    systemProgram: SystemProgram.programId,
+  });
+}
+
+export function redeemNoWinnerIx(ev: TestEvent, user: Keypair, side: number, amount: number | BN) {
+  const mint = side === SIDE_TRUE ? ev.trueMint : ev.falseMint;
+  return program.methods.redeemNoWinnerAfterFinal(side, new BN(amount)).accountsPartial({
+    user: user.publicKey,
+    event: ev.event,
+    config: configPda,
+    collateralVault: ev.collateralVault,
+    mint,
+    userAta: ataAddress(user.publicKey, mint, ev.tokenProgram),
+    userStats: userStatsPda(user.publicKey),
+    userPosition: userPositionPda(ev.event, user.publicKey),
+    tokenProgram: ev.tokenProgram,
+    systemProgram: SystemProgram.programId,
+  });
+}
+
+// Expected (fee, truth, creator, house, net) for a buy, mirroring compute_fee_splits.
+export function feeSplits(lamportsIn: number, feeBps: number, truthShareBps: number, creatorShareBps: number) {
+  const fee = Math.floor((lamportsIn * feeBps) / BPS_DENOM);
+  const truthCut = Math.floor((fee * truthShareBps) / BPS_DENOM);
+  const creatorCut = Math.floor((fee * creatorShareBps) / BPS_DENOM);
+  return { fee, truthCut, creatorCut, houseCut: fee - truthCut - creatorCut, net: lamportsIn - fee };
+}
+
+export { BN, Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram };
@@ -0,0 +1,265 @@
+import { expect } from "chai";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  SIDE_FALSE,
+  SIDE_TRUE,
+  SystemProgram,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  configPda,
+  connection,
+  createEvent,
+  expectError,
+  lamports,
+  newUser,
+  pda,
+  positionAtas,
+  program,
+  rentExempt,
+  resolveEvent,
+  tokenBalance,
+  userPositionPda,
+  userStatsPda,
+  waitFinal,
+  waitUntil,
+} from "./helpers";
+import { Keypair, PublicKey } from "@solana/web3.js";
+
+const B = new BN(LAMPORTS_PER_SOL);
+const ONE = new BN(LAMPORTS_PER_SOL); // one position token (9 decimals)
+
+function lmsrPda(event: PublicKey): PublicKey {
+  return pda([Buffer.from("lmsr_market"), event.toBuffer()]);
+}
+
+function initLmsr(ev: TestEvent, creator: Keypair, b: BN) {
+  return program.methods
+    .initLmsrMarket(b)
+    .accountsPartial({
+      creator: creator.publicKey,
+      event: ev.event,
+      lmsrMarket: lmsrPda(ev.event),
+      collateralVault: ev.collateralVault,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator]);
+}
+
+function trade(ev: TestEvent, user: Keypair, side: number) {
+  const mint = side === SIDE_TRUE ? ev.trueMint : ev.falseMint;
+  return {
+    user: user.publicKey,
+    event: ev.event,
+    config: configPda,
+    lmsrMarket: lmsrPda(ev.event),
+    collateralVault: ev.collateralVault,
+    mintAuthority: ev.mintAuthority,
+    mint,
+    userAta: ataAddress(user.publicKey, mint, ev.tokenProgram),
+    userStats: userStatsPda(user.publicKey),
+    userPosition: userPositionPda(ev.event, user.publicKey),
+    tokenProgram: ev.tokenProgram,
+    systemProgram: SystemProgram.programId,
+  };
+}
+
+describe("user-001: LMSR market maker", () => {
+  let creator: Keypair;
+  let trader: Keypair;
+  let ev: TestEvent;
+  let short: TestEvent;
+
+  before(async () => {
+    creator = await newUser(20);
+    trader = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 120 });
+    await positionAtas(ev, trader);
+  });
+
+  it("rejects a liquidity parameter below the minimum", async () => {
+    await expectError(initLmsr(ev, creator, new BN(1_000)).rpc(), "InvalidLiquidityParam");
+  });
+
+  it("only lets the event creator open the market", async () => {
+    await expectError(initLmsr(ev, trader, B).rpc(), "Unauthorized");
+  });
+
+  it("takes the b * ln(2) subsidy from the creator", async () => {
+    const vaultBefore = await lamports(ev.collateralVault);
+    await initLmsr(ev, creator, B).rpc();
+
+    const market = await program.account.lmsrMarket.fetch(lmsrPda(ev.event));
+    const subsidy = market.subsidyLamports.toNumber();
+    expect(subsidy).to.be.closeTo(Math.log(2) * LAMPORTS_PER_SOL, 2);
+    expect(market.reserveLamports.toNumber()).to.equal(subsidy);
+    expect(await lamports(ev.collateralVault)).to.equal(vaultBefore + subsidy);
+
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.hasLmsr).to.equal(true);
+  });
+
+  it("prices a buy on the LMSR curve and mints only the bought side", async () => {
+    const before = await lamports(trader.publicKey);
+    await program.methods
+      .buyOutcome(SIDE_TRUE, ONE, new BN(LAMPORTS_PER_SOL))
+      .accountsPartial(trade(ev, trader, SIDE_TRUE))
+      .signers([trader])
+      .rpc();
+
+    // b * ln((e^(q/b) + 1) / 2) for q = b
+    const expected = Math.log((Math.E + 1) / 2) * LAMPORTS_PER_SOL;
+    const market = await program.account.lmsrMarket.fetch(lmsrPda(ev.event));
+    const cost = market.reserveLamports.sub(market.subsidyLamports).toNumber();
+    expect(cost).to.be.closeTo(expected, 1_000);
+    expect(market.qTrue.toString()).to.equal(ONE.toString());
+    expect(market.qFalse.toNumber()).to.equal(0);
+
+    expect((await tokenBalance(ataAddress(trader.publicKey, ev.trueMint))).toString()).to.equal(ONE.toString());
+    expect((await tokenBalance(ataAddress(trader.publicKey, ev.falseMint))).toNumber()).to.equal(0);
+    expect(before - (await lamports(trader.publicKey))).to.be.at.least(cost);
+
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.outstandingTrue.toString()).to.equal(ONE.toString());
+    expect(event.hasBuys).to.equal(true);
+
+    // the whole cost lands on the TRUE side of the position
+    const position = await program.account.userPosition.fetch(userPositionPda(ev.event, trader.publicKey));
+    expect(position.unitsTrue.toString()).to.equal(ONE.toString());
+    expect(position.unitsFalse.toNumber()).to.equal(0);
+    expect(position.costBasisTrue.toNumber()).to.equal(cost);
+    const stats = await program.account.userStats.fetch(userStatsPda(trader.publicKey));
+    expect(stats.lifetimeVolume.toNumber()).to.equal(cost);
+  });
+
+  it("rejects a buy above max_cost", async () => {
+    await expectError(
+      program.methods
+        .buyOutcome(SIDE_TRUE, ONE, new BN(1))
+        .accountsPartial(trade(ev, trader, SIDE_TRUE))
+        .signers([trader])
+        .rpc(),
+      "SlippageExceeded"
+    );
+  });
+
+  it("rejects a mint that does not match the side", async () => {
+    await expectError(
+      program.methods
+        .buyOutcome(SIDE_FALSE, ONE, new BN(LAMPORTS_PER_SOL))
+        .accountsPartial(trade(ev, trader, SIDE_TRUE))
+        .signers([trader])
+        .rpc(),
+      "InvalidMint"
+    );
+  });
+
+  it("buys back sold tokens and pays proceeds from the reserve", async () => {
+    const marketBefore = await program.account.lmsrMarket.fetch(lmsrPda(ev.event));
+    const half = ONE.divn(2);
+
+    await expectError(
+      program.methods
+        .sellOutcome(SIDE_TRUE, half, new BN(LAMPORTS_PER_SOL))
+        .accountsPartial(trade(ev, trader, SIDE_TRUE))
+        .signers([trader])
+        .rpc(),
+      "SlippageExceeded"
+    );
+
+    const before = await lamports(trader.publicKey);
+    await program.methods
+      .sellOutcome(SIDE_TRUE, half, new BN(0))
+      .accountsPartial(trade(ev, trader, SIDE_TRUE))
+      .signers([trader])
+      .rpc();
+
+    const market = await program.account.lmsrMarket.fetch(lmsrPda(ev.event));
+    const proceeds = marketBefore.reserveLamports.sub(market.reserveLamports).toNumber();
+    expect(proceeds).to.be.greaterThan(0);
+    expect(market.qTrue.toString()).to.equal(ONE.sub(half).toString());
+    expect(market.reserveLamports.gte(BN.max(market.qTrue, market.qFalse))).to.equal(true);
+    expect((await lamports(trader.publicKey)) - before).to.be.closeTo(proceeds, 10_000);
+
+    const position = await program.account.userPosition.fetch(userPositionPda(ev.event, trader.publicKey));
+    expect(position.unitsTrue.toString()).to.equal(ONE.sub(half).toString());
+    expect(position.payouts.toNumber()).to.equal(proceeds);
+  });
+
+  it("does not buy back more than the maker sold", async () => {
+    // pair tokens were never sold by the maker
+    await buyPair(ev, trader, 2 * LAMPORTS_PER_SOL);
+    await expectError(
+      program.methods
+        .sellOutcome(SIDE_FALSE, ONE, new BN(0))
+        .accountsPartial(trade(ev, trader, SIDE_FALSE))
+        .signers([trader])
+        .rpc(),
+      "LmsrInsufficientInventory"
+    );
+  });
+
+  it("closes trading once betting ends", async () => {
+    short = await createEvent(creator, { betSecs: 10 });
+    await initLmsr(short, creator, B).rpc();
+    await positionAtas(short, trader);
+    await waitUntil(short.betEnd + 1);
+
+    await expectError(
+      program.methods
+        .buyOutcome(SIDE_TRUE, ONE, new BN(LAMPORTS_PER_SOL))
+        .accountsPartial(trade(short, trader, SIDE_TRUE))
+        .signers([trader])
+        .rpc(),
+      "BettingPeriodEnded"
+    );
+  });
+
+  it("opens at most one market per event", async () => {
+    await expectError(initLmsr(ev, creator, B).rpc(), "already in use");
+  });
+
+  it("refunds the unused subsidy when the creator deletes the event", async () => {
+    await resolveEvent(short);
+    await waitFinal(short);
+
+    const market = await program.account.lmsrMarket.fetch(lmsrPda(short.event));
+    const vault = await lamports(short.collateralVault);
+    expect(vault).to.equal((await rentExempt()) + market.subsidyLamports.toNumber());
+
+    // collateral vault (subsidy included), commission vault, market and event all go to the creator
+    const closed =
+      vault +
+      (await lamports(short.commissionVault)) +
+      (await lamports(lmsrPda(short.event))) +
+      (await lamports(short.event));
+    const before = await lamports(creator.publicKey);
+    await program.methods
+      .deleteEvent()
+      .accountsPartial({
+        creator: creator.publicKey,
+        event: short.event,
+        collateralVault: short.collateralVault,
+        commissionVault: short.commissionVault,
+        lmsrMarket: lmsrPda(short.event),
+        tokenVault: null,
+        truthTokenVault: null,
+        commissionTokenVault: null,
+        collateralMint: null,
+        conditionalMarket: null,
+        conditionalVault: null,
+        trueMint: null,
+        falseMint: null,
+        mintAuthority: null,
+        tokenProgram: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    expect((await lamports(creator.publicKey)) - before).to.equal(closed);
+    expect(await connection.getAccountInfo(lmsrPda(short.event))).to.equal(null);
+    expect(await lamports(short.collateralVault)).to.equal(0);
+  });
+});
@@ -0,0 +1,236 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  SIDE_TRUE,
+  SystemProgram,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  configPda,
+  createEvent,
+  expectError,
+  lamports,
+  newUser,
+  pda,
+  positionAtas,
+  program,
+  tokenBalance,
+  userStatsPda,
+} from "./helpers";
+
+const ONE = new BN(LAMPORTS_PER_SOL);
+
+function bookPdas(event: PublicKey) {
+  return {
+    orderBook: pda([Buffer.from("order_book"), event.toBuffer()]),
+    orderEscrow: pda([Buffer.from("order_escrow"), event.toBuffer()]),
+    trueEscrow: pda([Buffer.from("order_token_escrow"), event.toBuffer(), Buffer.from([1])]),
+    falseEscrow: pda([Buffer.from("order_token_escrow"), event.toBuffer(), Buffer.from([2])]),
+  };
+}
+
+function openOrdersPda(event: PublicKey, user: PublicKey): PublicKey {
+  return pda([Buffer.from("open_orders"), event.toBuffer(), user.toBuffer()]);
+}
+
+describe("user-003: central limit order book", () => {
+  let creator: Keypair;
+  let seller: Keypair;
+  let bidder: Keypair;
+  let ev: TestEvent;
+  let b: ReturnType<typeof bookPdas>;
+
+  const place = (user: Keypair, isBid: boolean, price: BN, quantity: BN) =>
+    program.methods
+      .placeOrder(SIDE_TRUE, isBid, price, quantity)
+      .accountsPartial({
+        user: user.publicKey,
+        event: ev.event,
+        config: configPda,
+        orderBook: b.orderBook,
+        openOrders: openOrdersPda(ev.event, user.publicKey),
+        orderEscrow: b.orderEscrow,
+        tokenEscrow: b.trueEscrow,
+        mint: ev.trueMint,
+        userTokenAta: ataAddress(user.publicKey, ev.trueMint, ev.tokenProgram),
+        tokenProgram: ev.tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+  const cancel = (user: Keypair, orderId: number) =>
+    program.methods
+      .cancelOrder(new BN(orderId))
+      .accountsPartial({
+        user: user.publicKey,
+        event: ev.event,
+        orderBook: b.orderBook,
+        openOrders: openOrdersPda(ev.event, user.publicKey),
+      })
+      .signers([user])
+      .rpc();
+
+  const settle = (user: Keypair) =>
+    program.methods
+      .settleFunds()
+      .accountsPartial({
+        user: user.publicKey,
+        event: ev.event,
+        config: configPda,
+        orderBook: b.orderBook,
+        openOrders: openOrdersPda(ev.event, user.publicKey),
+        orderEscrow: b.orderEscrow,
+        trueEscrow: b.trueEscrow,
+        falseEscrow: b.falseEscrow,
+        trueMint: ev.trueMint,
+        falseMint: ev.falseMint,
+        userTrueAta: ataAddress(user.publicKey, ev.trueMint, ev.tokenProgram),
+        userFalseAta: ataAddress(user.publicKey, ev.falseMint, ev.tokenProgram),
+        tokenProgram: ev.tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([user])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    seller = await newUser(20);
+    bidder = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 180 });
+    b = bookPdas(ev.event);
+    await buyPair(ev, seller, 2 * LAMPORTS_PER_SOL);
+    await positionAtas(ev, bidder);
+
+    await program.methods
+      .initOrderBook()
+      .accountsPartial({
+        payer: creator.publicKey,
+        event: ev.event,
+        ...b,
+        trueMint: ev.trueMint,
+        falseMint: ev.falseMint,
+        tokenProgram: ev.tokenProgram,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([creator])
+      .rpc();
+
+    for (const user of [seller, bidder]) {
+      await program.methods
+        .initOpenOrders()
+        .accountsPartial({
+          user: user.publicKey,
+          event: ev.event,
+          openOrders: openOrdersPda(ev.event, user.publicKey),
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    }
+  });
+
+  it("rejects prices outside (0, 1] SOL per token", async () => {
+    await expectError(place(bidder, true, new BN(0), ONE), "InvalidPrice");
+    await expectError(place(bidder, true, ONE.addn(1), ONE), "InvalidPrice");
+  });
+
+  it("rejects orders below the minimum notional", async () => {
+    // 0.01 token at 0.5 SOL = 0.005 SOL
+    await expectError(place(bidder, true, ONE.divn(2), ONE.divn(100)), "OrderTooSmall");
+  });
+
+  it("locks lamports for bids and tokens for asks", async () => {
+    const escrowBefore = await lamports(b.orderEscrow);
+    await place(bidder, true, new BN(600_000_000), ONE);
+    expect((await lamports(b.orderEscrow)) - escrowBefore).to.equal(600_000_000);
+
+    await place(seller, false, new BN(500_000_000), ONE);
+    expect((await tokenBalance(b.trueEscrow)).toString()).to.equal(ONE.toString());
+
+    const book = await program.account.orderBook.fetch(b.orderBook);
+    expect(book.orders.length).to.equal(2);
+    expect(book.nextOrderId.toNumber()).to.equal(2);
+  });
+
+  it("crosses the book at the resting order's price", async () => {
+    await program.methods.matchOrders(SIDE_TRUE, 4).accountsPartial({ event: ev.event, orderBook: b.orderBook }).rpc();
+
+    const book = await program.account.orderBook.fetch(b.orderBook);
+    expect(book.orders.length).to.equal(0);
+    expect(book.fills.length).to.equal(1);
+    const fill = book.fills[0];
+    expect(fill.bidder.equals(bidder.publicKey)).to.equal(true);
+    expect(fill.asker.equals(seller.publicKey)).to.equal(true);
+    expect(fill.quantity.toString()).to.equal(ONE.toString());
+    // the bid rested first, so it sets the price
+    expect(fill.lamports.toNumber()).to.equal(600_000_000);
+
+    await expectError(
+      program.methods.matchOrders(SIDE_TRUE, 4).accountsPartial({ event: ev.event, orderBook: b.orderBook }).rpc(),
+      "NothingToMatch"
+    );
+  });
+
+  it("credits fills to the owners' open orders accounts", async () => {
+    const bidderOo = openOrdersPda(ev.event, bidder.publicKey);
+    const sellerOo = openOrdersPda(ev.event, seller.publicKey);
+
+    // nothing is consumed without the owners' accounts
+    await expectError(
+      program.methods
+        .consumeEvents(8)
+        .accountsPartial({ event: ev.event, orderBook: b.orderBook })
+        .remainingAccounts([{ pubkey: bidderOo, isSigner: false, isWritable: true }])
+        .rpc(),
+      "NothingToConsume"
+    );
+
+    await program.methods
+      .consumeEvents(8)
+      .accountsPartial({ event: ev.event, orderBook: b.orderBook })
+      .remainingAccounts([
+        { pubkey: bidderOo, isSigner: false, isWritable: true },
+        { pubkey: sellerOo, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    const bidderState = await program.account.openOrders.fetch(bidderOo);
+    const sellerState = await program.account.openOrders.fetch(sellerOo);
+    expect(bidderState.freeTrue.toString()).to.equal(ONE.toString());
+    expect(sellerState.freeLamports.toNumber()).to.equal(600_000_000);
+    expect((await program.account.orderBook.fetch(b.orderBook)).fills.length).to.equal(0);
+  });
+
+  it("settles free balances to the owners' wallets", async () => {
+    const tokensBefore = await tokenBalance(ataAddress(bidder.publicKey, ev.trueMint));
+    await settle(bidder);
+    const tokensAfter = await tokenBalance(ataAddress(bidder.publicKey, ev.trueMint));
+    expect(tokensAfter.sub(tokensBefore).toString()).to.equal(ONE.toString());
+    // book fills are settled by crank and stay out of the user stats
+    expect(await program.account.userStats.fetchNullable(userStatsPda(bidder.publicKey))).to.equal(null);
+
+    const solBefore = await lamports(seller.publicKey);
+    await settle(seller);
+    expect((await lamports(seller.publicKey)) - solBefore).to.be.closeTo(600_000_000, 10_000);
+
+    await expectError(settle(seller), "NothingToClaim");
+  });
+
+  it("lets only the owner cancel an order and refunds its escrow", async () => {
+    await place(bidder, true, new BN(400_000_000), ONE);
+    const book = await program.account.orderBook.fetch(b.orderBook);
+    const id = book.orders[0].id.toNumber();
+
+    await expectError(cancel(seller, id), "Unauthorized");
+    await expectError(cancel(bidder, id + 100), "OrderNotFound");
+
+    await cancel(bidder, id);
+    const oo = await program.account.openOrders.fetch(openOrdersPda(ev.event, bidder.publicKey));
+    expect(oo.freeLamports.toNumber()).to.equal(400_000_000);
+    expect((await program.account.orderBook.fetch(b.orderBook)).orders.length).to.equal(0);
+  });
+});
@@ -0,0 +1,113 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  PAUSE_BUY,
+  PAUSE_FINAL_REDEEM,
+  PAUSE_PAIR_REDEEM,
+  TestEvent,
+  admin,
+  buyPair,
+  chainTime,
+  configPda,
+  createEvent,
+  expectError,
+  newUser,
+  program,
+  redeemPairIx,
+  waitUntil,
+} from "./helpers";
+
+const MAX_PAUSE_SECS = 7 * 24 * 60 * 60;
+
+// Lifting a pause starts a 7-day cooldown, so the protocol pause can only be exercised once
+// per validator run; the suite lifts it again before the later suites buy.
+describe("user-021: emergency pauses", () => {
+  let creator: Keypair;
+  let buyer: Keypair;
+  let stranger: Keypair;
+
+  const setEventPause = (ev: TestEvent, signer: Keypair, flags: number, reason = "test halt", secs = 30) =>
+    program.methods
+      .setEventPause(flags, reason, new BN(secs))
+      .accountsPartial({ authority: signer.publicKey, config: configPda, event: ev.event })
+      .signers(signer === admin ? [] : [signer])
+      .rpc();
+
+  const setProtocolPause = (signer: Keypair, flags: number, reason = "oracle incident", secs = 30) =>
+    program.methods
+      .setProtocolPause(flags, reason, new BN(secs))
+      .accountsPartial({ admin: signer.publicKey, config: configPda })
+      .signers(signer === admin ? [] : [signer])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    buyer = await newUser(20);
+    stranger = await newUser(2);
+  });
+
+  after(async () => {
+    const { pause } = await program.account.protocolConfig.fetch(configPda);
+    if (pause.flags !== 0 && (await chainTime()) < pause.expiresAt.toNumber()) {
+      await setProtocolPause(admin, 0);
+    }
+  });
+
+  it("validates event pauses", async () => {
+    const ev = await createEvent(creator, { betSecs: 120 });
+    await expectError(setEventPause(ev, stranger, PAUSE_BUY), "Unauthorized");
+    await expectError(setEventPause(ev, creator, 1 << 5), "InvalidPause");
+    await expectError(setEventPause(ev, creator, PAUSE_BUY, "test halt", 0), "InvalidPause");
+    await expectError(setEventPause(ev, creator, PAUSE_BUY, "test halt", MAX_PAUSE_SECS + 1), "InvalidPause");
+    await expectError(setEventPause(ev, creator, PAUSE_BUY, "x".repeat(65)), "InvalidPause");
+    // the creator cannot hold back final redemptions
+    await expectError(setEventPause(ev, creator, PAUSE_FINAL_REDEEM), "Unauthorized");
+  });
+
+  it("lets the creator halt buys on their event and lift it once", async () => {
+    const ev = await createEvent(creator, { betSecs: 120 });
+    await setEventPause(ev, creator, PAUSE_BUY, "bad wording", 60);
+
+    const { pause } = await program.account.event.fetch(ev.event);
+    expect(pause.flags).to.equal(PAUSE_BUY);
+    expect(pause.pausedBy.equals(creator.publicKey)).to.equal(true);
+    expect(pause.reason).to.equal("bad wording");
+    expect(pause.expiresAt.sub(pause.pausedAt).toNumber()).to.equal(60);
+    await expectError(buyPair(ev, buyer, LAMPORTS_PER_SOL), "EventPaused");
+
+    await setEventPause(ev, creator, 0);
+    await buyPair(ev, buyer, LAMPORTS_PER_SOL);
+    await expectError(setEventPause(ev, creator, PAUSE_BUY), "PauseCooldownActive");
+  });
+
+  it("expires an admin pause the creator cannot lift", async () => {
+    const ev = await createEvent(creator, { betSecs: 120 });
+    await buyPair(ev, buyer, LAMPORTS_PER_SOL);
+    await setEventPause(ev, admin, PAUSE_PAIR_REDEEM, "investigating", 5);
+
+    const redeem = () => redeemPairIx(ev, buyer, LAMPORTS_PER_SOL / 10).signers([buyer]).rpc();
+    await expectError(redeem(), "EventPaused");
+    await expectError(setEventPause(ev, creator, 0), "Unauthorized");
+
+    const { pause } = await program.account.event.fetch(ev.event);
+    await waitUntil(pause.expiresAt.toNumber() + 1);
+    await redeem();
+  });
+
+  it("halts every event through the protocol pause", async () => {
+    const ev = await createEvent(creator, { betSecs: 120 });
+    await expectError(setProtocolPause(stranger, PAUSE_BUY), "Unauthorized");
+
+    await setProtocolPause(admin, PAUSE_BUY);
+    const { pause } = await program.account.protocolConfig.fetch(configPda);
+    expect(pause.pausedBy.equals(admin.publicKey)).to.equal(true);
+    expect(pause.reason).to.equal("oracle incident");
+    await expectError(buyPair(ev, buyer, LAMPORTS_PER_SOL), "ProtocolPaused");
+
+    await setProtocolPause(admin, 0);
+    await buyPair(ev, buyer, LAMPORTS_PER_SOL);
+    await expectError(setProtocolPause(admin, PAUSE_BUY), "PauseCooldownActive");
+  });
+});
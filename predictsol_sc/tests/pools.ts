@@ -0,0 +1,299 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  SIDE_TRUE,
+  SystemProgram,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  configPda,
+  createAtaIx,
+  createEvent,
+  expectError,
+  lamports,
+  newUser,
+  pda,
+  program,
+  send,
+  tokenBalance,
+  userPositionPda,
+  userStatsPda,
+  waitUntil,
+} from "./helpers";
+
+const FEE_BPS = 30;
+const ONE = new BN(LAMPORTS_PER_SOL);
+
+function poolPdas(event: PublicKey, side: number) {
+  const s = Buffer.from([side]);
+  return {
+    pool: pda([Buffer.from("pool"), event.toBuffer(), s]),
+    lpMint: pda([Buffer.from("pool_lp_mint"), event.toBuffer(), s]),
+    tokenVault: pda([Buffer.from("pool_token_vault"), event.toBuffer(), s]),
+    solVault: pda([Buffer.from("pool_sol_vault"), event.toBuffer(), s]),
+  };
+}
+
+function cpOut(reserveIn: BN, reserveOut: BN, amountIn: BN, feeBps: number): BN {
+  const inAfterFee = amountIn.muln(10_000 - feeBps).divn(10_000);
+  return reserveOut.mul(inAfterFee).div(reserveIn.add(inAfterFee));
+}
+
+describe("user-002: constant-product pools", () => {
+  let creator: Keypair;
+  let lp: Keypair;
+  let trader: Keypair;
+  let ev: TestEvent;
+  let p: ReturnType<typeof poolPdas>;
+
+  const initPool = (signer: Keypair, side: number, feeBps: number) => {
+    const pp = poolPdas(ev.event, side);
+    return program.methods
+      .initPool(side, feeBps)
+      .accountsPartial({
+        creator: signer.publicKey,
+        event: ev.event,
+        pool: pp.pool,
+        mint: side === SIDE_TRUE ? ev.trueMint : ev.falseMint,
+        lpMint: pp.lpMint,
+        tokenVault: pp.tokenVault,
+        solVault: pp.solVault,
+        tokenProgram: ev.tokenProgram,
+        systemProgram: SystemProgram.programId,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([signer]);
+  };
+
+  const liquidityAccounts = (user: Keypair) => ({
+    user: user.publicKey,
+    event: ev.event,
+    config: configPda,
+    pool: p.pool,
+    lpMint: p.lpMint,
+    tokenVault: p.tokenVault,
+    mint: ev.trueMint,
+    solVault: p.solVault,
+    userTokenAta: ataAddress(user.publicKey, ev.trueMint, ev.tokenProgram),
+    userLpAta: ataAddress(user.publicKey, p.lpMint, ev.tokenProgram),
+    tokenProgram: ev.tokenProgram,
+    systemProgram: SystemProgram.programId,
+  });
+
+  const swapAccounts = (user: Keypair) => ({
+    user: user.publicKey,
+    event: ev.event,
+    config: configPda,
+    pool: p.pool,
+    tokenVault: p.tokenVault,
+    mint: ev.trueMint,
+    solVault: p.solVault,
+    userTokenAta: ataAddress(user.publicKey, ev.trueMint, ev.tokenProgram),
+    userStats: userStatsPda(user.publicKey),
+    userPosition: userPositionPda(ev.event, user.publicKey),
+    tokenProgram: ev.tokenProgram,
+    systemProgram: SystemProgram.programId,
+  });
+  const position = () => program.account.userPosition.fetch(userPositionPda(ev.event, trader.publicKey));
+
+  before(async () => {
+    creator = await newUser(20);
+    lp = await newUser(20);
+    trader = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 120 });
+    p = poolPdas(ev.event, SIDE_TRUE);
+    await buyPair(ev, lp, 4 * LAMPORTS_PER_SOL);
+    await buyPair(ev, trader, LAMPORTS_PER_SOL);
+  });
+
+  it("caps the swap fee", async () => {
+    await expectError(initPool(creator, SIDE_TRUE, 1_001).rpc(), "InvalidFeeBps");
+  });
+
+  it("only lets the event creator open a pool", async () => {
+    await expectError(initPool(lp, SIDE_TRUE, FEE_BPS).rpc(), "Unauthorized");
+  });
+
+  it("creates the pool with a program-owned LP mint", async () => {
+    await initPool(creator, SIDE_TRUE, FEE_BPS).rpc();
+
+    const pool = await program.account.pool.fetch(p.pool);
+    expect(pool.side).to.equal(SIDE_TRUE);
+    expect(pool.feeBps).to.equal(FEE_BPS);
+    expect(pool.lpSupply.toNumber()).to.equal(0);
+    expect(pool.lpMint.equals(p.lpMint)).to.equal(true);
+
+    await send([createAtaIx(lp.publicKey, lp.publicKey, p.lpMint, ev.tokenProgram)], [lp]);
+    await send([createAtaIx(trader.publicKey, trader.publicKey, p.lpMint, ev.tokenProgram)], [trader]);
+  });
+
+  it("rejects a first deposit below the minimum initial liquidity", async () => {
+    await expectError(
+      program.methods
+        .addLiquidity(new BN(1_000), new BN(1_000), new BN(0))
+        .accountsPartial(liquidityAccounts(lp))
+        .signers([lp])
+        .rpc(),
+      "InsufficientInitialLiquidity"
+    );
+  });
+
+  it("sets the price on the first deposit and mints sqrt(x * y) LP", async () => {
+    const sol = new BN(LAMPORTS_PER_SOL / 2);
+    await program.methods
+      .addLiquidity(ONE, sol, new BN(0))
+      .accountsPartial(liquidityAccounts(lp))
+      .signers([lp])
+      .rpc();
+
+    const pool = await program.account.pool.fetch(p.pool);
+    const expectedLp = Math.floor(Math.sqrt(ONE.toNumber() * sol.toNumber()));
+    expect(pool.tokenReserve.toString()).to.equal(ONE.toString());
+    expect(pool.solReserve.toString()).to.equal(sol.toString());
+    expect(pool.lpSupply.toNumber()).to.be.closeTo(expectedLp, 1);
+    expect((await tokenBalance(ataAddress(lp.publicKey, p.lpMint))).toString()).to.equal(pool.lpSupply.toString());
+  });
+
+  it("requires later deposits to match the pool ratio", async () => {
+    // 0.5 token needs 0.25 SOL at the current price
+    await expectError(
+      program.methods
+        .addLiquidity(ONE.divn(2), new BN(LAMPORTS_PER_SOL / 5), new BN(0))
+        .accountsPartial(liquidityAccounts(trader))
+        .signers([trader])
+        .rpc(),
+      "SlippageExceeded"
+    );
+
+    const before = await program.account.pool.fetch(p.pool);
+    await program.methods
+      .addLiquidity(ONE.divn(2), new BN(LAMPORTS_PER_SOL / 4), new BN(1))
+      .accountsPartial(liquidityAccounts(trader))
+      .signers([trader])
+      .rpc();
+    const after = await program.account.pool.fetch(p.pool);
+    expect(after.solReserve.sub(before.solReserve).toNumber()).to.equal(LAMPORTS_PER_SOL / 4);
+    expect(after.lpSupply.sub(before.lpSupply).toString()).to.equal(before.lpSupply.divn(2).toString());
+  });
+
+  it("swaps SOL for tokens along x * y = k with the fee kept in the pool", async () => {
+    const pool = await program.account.pool.fetch(p.pool);
+    const amountIn = new BN(LAMPORTS_PER_SOL / 10);
+    const expected = cpOut(pool.solReserve, pool.tokenReserve, amountIn, FEE_BPS);
+
+    await expectError(
+      program.methods
+        .swap(true, amountIn, expected.addn(1))
+        .accountsPartial(swapAccounts(trader))
+        .signers([trader])
+        .rpc(),
+      "SlippageExceeded"
+    );
+
+    const tokensBefore = await tokenBalance(ataAddress(trader.publicKey, ev.trueMint));
+    const positionBefore = await position();
+    await program.methods
+      .swap(true, amountIn, expected)
+      .accountsPartial(swapAccounts(trader))
+      .signers([trader])
+      .rpc();
+
+    const tokensAfter = await tokenBalance(ataAddress(trader.publicKey, ev.trueMint));
+    expect(tokensAfter.sub(tokensBefore).toString()).to.equal(expected.toString());
+
+    const after = await program.account.pool.fetch(p.pool);
+    const kBefore = pool.solReserve.mul(pool.tokenReserve);
+    const kAfter = after.solReserve.mul(after.tokenReserve);
+    expect(kAfter.gte(kBefore)).to.equal(true);
+
+    // a SOL -> token swap is a buy of that side
+    const positionAfter = await position();
+    expect(positionAfter.unitsTrue.sub(positionBefore.unitsTrue).toString()).to.equal(expected.toString());
+    expect(positionAfter.costBasisTrue.sub(positionBefore.costBasisTrue).toString()).to.equal(amountIn.toString());
+    expect(positionAfter.unitsFalse.toString()).to.equal(positionBefore.unitsFalse.toString());
+  });
+
+  it("swaps tokens back for SOL", async () => {
+    const pool = await program.account.pool.fetch(p.pool);
+    const amountIn = ONE.divn(10);
+    const expected = cpOut(pool.tokenReserve, pool.solReserve, amountIn, FEE_BPS);
+
+    const before = await lamports(trader.publicKey);
+    const positionBefore = await position();
+    await program.methods
+      .swap(false, amountIn, expected)
+      .accountsPartial(swapAccounts(trader))
+      .signers([trader])
+      .rpc();
+
+    expect((await lamports(trader.publicKey)) - before).to.be.closeTo(expected.toNumber(), 10_000);
+    const after = await program.account.pool.fetch(p.pool);
+    expect(pool.solReserve.sub(after.solReserve).toString()).to.equal(expected.toString());
+
+    const positionAfter = await position();
+    expect(positionBefore.unitsTrue.sub(positionAfter.unitsTrue).toString()).to.equal(amountIn.toString());
+    expect(positionAfter.payouts.sub(positionBefore.payouts).toString()).to.equal(expected.toString());
+  });
+
+  it("returns a pro rata share of both reserves on withdrawal", async () => {
+    const lpBalance = await tokenBalance(ataAddress(lp.publicKey, p.lpMint));
+    await expectError(
+      program.methods
+        .removeLiquidity(lpBalance.addn(1), new BN(0), new BN(0))
+        .accountsPartial(liquidityAccounts(lp))
+        .signers([lp])
+        .rpc(),
+      "InsufficientLpBalance"
+    );
+
+    const pool = await program.account.pool.fetch(p.pool);
+    const tokensOut = lpBalance.mul(pool.tokenReserve).div(pool.lpSupply);
+    const lamportsOut = lpBalance.mul(pool.solReserve).div(pool.lpSupply);
+
+    await expectError(
+      program.methods
+        .removeLiquidity(lpBalance, tokensOut.addn(1), new BN(0))
+        .accountsPartial(liquidityAccounts(lp))
+        .signers([lp])
+        .rpc(),
+      "SlippageExceeded"
+    );
+
+    const tokensBefore = await tokenBalance(ataAddress(lp.publicKey, ev.trueMint));
+    await program.methods
+      .removeLiquidity(lpBalance, tokensOut, lamportsOut)
+      .accountsPartial(liquidityAccounts(lp))
+      .signers([lp])
+      .rpc();
+
+    const tokensAfter = await tokenBalance(ataAddress(lp.publicKey, ev.trueMint));
+    expect(tokensAfter.sub(tokensBefore).toString()).to.equal(tokensOut.toString());
+    expect((await tokenBalance(ataAddress(lp.publicKey, p.lpMint))).toNumber()).to.equal(0);
+
+    const after = await program.account.pool.fetch(p.pool);
+    expect(after.lpSupply.toString()).to.equal(pool.lpSupply.sub(lpBalance).toString());
+  });
+
+  it("stops swaps once betting ends but still allows withdrawals", async () => {
+    await waitUntil(ev.betEnd + 1);
+    await expectError(
+      program.methods
+        .swap(true, new BN(LAMPORTS_PER_SOL / 10), new BN(0))
+        .accountsPartial(swapAccounts(trader))
+        .signers([trader])
+        .rpc(),
+      "BettingPeriodEnded"
+    );
+
+    const lpBalance = await tokenBalance(ataAddress(trader.publicKey, p.lpMint));
+    await program.methods
+      .removeLiquidity(lpBalance, new BN(0), new BN(0))
+      .accountsPartial(liquidityAccounts(trader))
+      .signers([trader])
+      .rpc();
+    const pool = await program.account.pool.fetch(p.pool);
+    expect(pool.lpSupply.toNumber()).to.equal(0);
+  });
+});
@@ -0,0 +1,104 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+  TEST_BUY_FEE_BPS,
+  TEST_REFERRAL_SHARE_BPS,
+  TestEvent,
+  buyPair,
+  createEvent,
+  expectError,
+  feeSplits,
+  lamports,
+  newUser,
+  program,
+  referralPda,
+  referralVaultPda,
+} from "./helpers";
+
+describe("user-013: referral fee sharing", () => {
+  let creator: Keypair;
+  let referrer: Keypair;
+  let buyer: Keypair;
+  let ev: TestEvent;
+
+  const claim = () =>
+    program.methods
+      .claimReferralRewards()
+      .accountsPartial({
+        referrer: referrer.publicKey,
+        referral: referralPda(referrer.publicKey),
+        referralVault: referralVaultPda(referrer.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([referrer])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    referrer = await newUser(5);
+    buyer = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 60 });
+  });
+
+  it("opens a referral account with its own vault", async () => {
+    await program.methods
+      .initReferralAccount()
+      .accountsPartial({
+        referrer: referrer.publicKey,
+        referral: referralPda(referrer.publicKey),
+        referralVault: referralVaultPda(referrer.publicKey),
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([referrer])
+      .rpc();
+
+    const referral = await program.account.referralAccount.fetch(referralPda(referrer.publicKey));
+    expect(referral.referrer.equals(referrer.publicKey)).to.equal(true);
+    expect(referral.pendingLamports.toNumber()).to.equal(0);
+    await expectError(claim(), "NothingToClaim");
+  });
+
+  it("pays the referrer a share of the house cut", async () => {
+    const event = await program.account.event.fetch(ev.event);
+    const split = feeSplits(LAMPORTS_PER_SOL, TEST_BUY_FEE_BPS, event.truthShareBps, event.creatorShareBps);
+    const referralCut = Math.floor((split.houseCut * TEST_REFERRAL_SHARE_BPS) / 10_000);
+    const vaultBefore = await lamports(referralVaultPda(referrer.publicKey));
+    const collateralBefore = await lamports(ev.collateralVault);
+
+    await buyPair(ev, buyer, LAMPORTS_PER_SOL, referrer.publicKey);
+
+    expect((await lamports(referralVaultPda(referrer.publicKey))) - vaultBefore).to.equal(referralCut);
+    const referral = await program.account.referralAccount.fetch(referralPda(referrer.publicKey));
+    expect(referral.pendingLamports.toNumber()).to.equal(referralCut);
+    expect(referral.totalEarnedLamports.toNumber()).to.equal(referralCut);
+
+    const after = await program.account.event.fetch(ev.event);
+    expect(after.pendingHouseCommission.toNumber()).to.equal(split.houseCut - referralCut);
+    expect(after.pendingCreatorCommission.toNumber()).to.equal(split.creatorCut);
+    expect(after.totalReferralCommission.toNumber()).to.equal(referralCut);
+
+    // the referral cut never passes through the collateral vault or its accounting
+    expect((await lamports(ev.collateralVault)) - collateralBefore).to.equal(split.net);
+    expect(after.totalCollateralLamports.sub(event.totalCollateralLamports).toNumber()).to.equal(
+      split.net + split.truthCut
+    );
+  });
+
+  it("rejects self-referral", async () => {
+    await expectError(buyPair(ev, referrer, LAMPORTS_PER_SOL / 10, referrer.publicKey), "SelfReferral");
+  });
+
+  it("lets the referrer claim the accrued rewards once", async () => {
+    const pending = (await program.account.referralAccount.fetch(referralPda(referrer.publicKey))).pendingLamports;
+    const before = await lamports(referrer.publicKey);
+    await claim();
+    expect((await lamports(referrer.publicKey)) - before).to.be.closeTo(pending.toNumber(), 10_000);
+
+    const referral = await program.account.referralAccount.fetch(referralPda(referrer.publicKey));
+    expect(referral.pendingLamports.toNumber()).to.equal(0);
+    expect(referral.totalClaimedLamports.toString()).to.equal(pending.toString());
+    await expectError(claim(), "NothingToClaim");
+  });
+});
@@ -0,0 +1,76 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  RESULT_FINALIZED_NO_VOTES,
+  SystemProgram,
+  TestEvent,
+  buyPair,
+  chainTime,
+  configPda,
+  createEvent,
+  createTruthQuestion,
+  expectError,
+  newUser,
+  program,
+  resolveEvent,
+} from "./helpers";
+
+// A tie or below-threshold result needs real Truth Network votes, which the local suites
+// don't cast; the revote path is covered up to its guards on pending and no-vote results.
+describe("user-025: revote on tie or below-threshold", () => {
+  let creator: Keypair;
+  let ev: TestEvent;
+  let next: { question: PublicKey; vault: PublicKey };
+
+  const requestRevote = (signer: Keypair, currentQuestion = ev.question) =>
+    program.methods
+      .requestRevote()
+      .accountsPartial({
+        creator: signer.publicKey,
+        config: configPda,
+        event: ev.event,
+        currentTruthQuestion: currentQuestion,
+        newTruthQuestion: next.question,
+        truthNetworkVault: next.vault,
+        commissionVault: ev.commissionVault,
+        collateralVault: ev.collateralVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 20 });
+    await buyPair(ev, await newUser(5), LAMPORTS_PER_SOL);
+
+    // same wording as the event, far enough out to still be open after resolution
+    const now = await chainTime();
+    next = await createTruthQuestion(creator, ev.title, now + 300, now + 600);
+  });
+
+  it("snapshots the revote policy into the event", async () => {
+    const config = await program.account.protocolConfig.fetch(configPda);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.revoteMaxRounds).to.equal(config.revoteMaxRounds);
+    expect(event.revoteFundingBps).to.equal(config.revoteFundingBps);
+    expect(event.revoteRound).to.equal(0);
+    expect(event.revoteHistory.every((r) => r.question.equals(PublicKey.default))).to.equal(true);
+  });
+
+  it("is only open to the creator", async () => {
+    await expectError(requestRevote(await newUser(1)), "Unauthorized");
+    await expectError(requestRevote(creator, next.question), "TruthQuestionMismatch");
+  });
+
+  it("needs a tie or below-threshold result", async () => {
+    await expectError(requestRevote(creator), "RevoteNotAllowed");
+
+    await resolveEvent(ev);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.resultStatus).to.equal(RESULT_FINALIZED_NO_VOTES);
+    await expectError(requestRevote(creator), "RevoteNotAllowed");
+    expect((await program.account.event.fetch(ev.event)).truthQuestion.equals(ev.question)).to.equal(true);
+  });
+});
@@ -0,0 +1,153 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  RESULT_FINALIZED_NO_VOTES,
+  SIDE_TRUE,
+  SystemProgram,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  configPda,
+  createEvent,
+  expectError,
+  lamports,
+  newUser,
+  pda,
+  program,
+  redeemNoWinnerIx,
+  tokenBalance,
+  truth,
+  waitUntil,
+} from "./helpers";
+
+function scalarPda(event: PublicKey): PublicKey {
+  return pda([Buffer.from("scalar_market"), event.toBuffer()]);
+}
+
+const initScalar = (creator: Keypair, event: PublicKey, lower: number, upper: number) =>
+  program.methods
+    .initScalarMarket(new BN(lower), new BN(upper))
+    .accountsPartial({
+      creator: creator.publicKey,
+      event,
+      scalarMarket: scalarPda(event),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([creator])
+    .rpc();
+
+// Without Truth votes the reported value is never confirmed, so the suite covers the
+// report window and the no-votes fallback; LONG/SHORT payouts need a voted question.
+describe("user-006: scalar events", () => {
+  let creator: Keypair;
+  let other: Keypair;
+  let user: Keypair;
+  let ev: TestEvent;
+
+  const report = (signer: Keypair, value: number) =>
+    program.methods
+      .reportScalarValue(new BN(value))
+      .accountsPartial({ creator: signer.publicKey, event: ev.event, scalarMarket: scalarPda(ev.event) })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    other = await newUser(5);
+    user = await newUser(20);
+  });
+
+  it("turns a fresh event into a range market before its mints exist", async () => {
+    ev = await createEvent(creator, {
+      betSecs: 30,
+      commitSecs: 10,
+      beforeMints: async (event) => {
+        await expectError(initScalar(creator, event, 100, 100), "InvalidScalarRange");
+        await expectError(initScalar(other, event, 0, 100), "Unauthorized");
+        await initScalar(creator, event, 0, 100);
+      },
+    });
+
+    const market = await program.account.scalarMarket.fetch(scalarPda(ev.event));
+    expect(market.lower.toNumber()).to.equal(0);
+    expect(market.upper.toNumber()).to.equal(100);
+    expect(market.valueReported).to.equal(false);
+    expect((await program.account.event.fetch(ev.event)).kind).to.equal(1);
+  });
+
+  it("cannot convert an event whose mints already exist", async () => {
+    const binary = await createEvent(creator);
+    await expectError(initScalar(creator, binary.event, 0, 100), "MintsAlreadyCreated");
+  });
+
+  it("mints LONG / SHORT pairs like a binary event", async () => {
+    await buyPair(ev, user, LAMPORTS_PER_SOL);
+    expect((await tokenBalance(ataAddress(user.publicKey, ev.trueMint))).toNumber()).to.be.greaterThan(0);
+  });
+
+  it("takes a single creator report between bet end and commit end", async () => {
+    await expectError(report(creator, 42), "BettingStillActive");
+    await waitUntil(ev.betEnd + 1);
+    await expectError(report(other, 42), "Unauthorized");
+
+    await report(creator, 42);
+    await expectError(report(creator, 43), "ScalarValueAlreadyReported");
+
+    const market = await program.account.scalarMarket.fetch(scalarPda(ev.event));
+    expect(market.valueReported).to.equal(true);
+    expect(market.reportedValue.toNumber()).to.equal(42);
+  });
+
+  it("resolves only through the scalar path and falls back to 0.5 per token without votes", async () => {
+    await waitUntil(ev.revealEnd + 1);
+    const event = await program.account.event.fetch(ev.event);
+    const accounts = {
+      event: ev.event,
+      config: configPda,
+      truthNetworkQuestion: event.truthQuestion,
+      truthNetworkProgram: truth.programId,
+      houseTreasury: event.houseWallet,
+      commissionVault: ev.commissionVault,
+      collateralVault: ev.collateralVault,
+      systemProgram: SystemProgram.programId,
+    };
+
+    await expectError(program.methods.fetchAndStoreWinner().accountsPartial(accounts).rpc(), "InvalidEventKind");
+    await program.methods
+      .fetchAndStoreScalarResult()
+      .accountsPartial({ ...accounts, scalarMarket: scalarPda(ev.event) })
+      .rpc();
+
+    const resolved = await program.account.event.fetch(ev.event);
+    expect(resolved.resultStatus).to.equal(RESULT_FINALIZED_NO_VOTES);
+    const market = await program.account.scalarMarket.fetch(scalarPda(ev.event));
+    expect(market.longPayoutPerToken.toNumber()).to.equal(0);
+
+    const ata = ataAddress(user.publicKey, ev.trueMint);
+    const amount = await tokenBalance(ata);
+    await expectError(
+      program.methods
+        .redeemScalarAfterFinal(SIDE_TRUE, amount)
+        .accountsPartial({
+          user: user.publicKey,
+          event: ev.event,
+          config: configPda,
+          scalarMarket: scalarPda(ev.event),
+          collateralVault: ev.collateralVault,
+          mint: ev.trueMint,
+          userAta: ata,
+          tokenProgram: ev.tokenProgram,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc(),
+      "InvalidResultStatus"
+    );
+
+    const before = await lamports(user.publicKey);
+    await redeemNoWinnerIx(ev, user, SIDE_TRUE, amount).signers([user]).rpc();
+    expect((await lamports(user.publicKey)) - before).to.be.at.least(amount.divn(2).toNumber() - 10_000);
+  });
+});
@@ -0,0 +1,212 @@
+import { expect } from "chai";
+import { Keypair, PublicKey, SYSVAR_RENT_PUBKEY, TransactionInstruction } from "@solana/web3.js";
+import {
+  ASSOCIATED_PROGRAM_ID,
+  BN,
+  BPS_DENOM,
+  LAMPORTS_PER_SOL,
+  SIDE_TRUE,
+  SystemProgram,
+  TEST_BUY_FEE_BPS,
+  TOKEN_PROGRAM_ID,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  chainTime,
+  configPda,
+  createEvent,
+  expectError,
+  newUser,
+  pda,
+  positionAtas,
+  program,
+  send,
+  tokenBalance,
+  userPositionPda,
+  userStatsPda,
+  waitUntil,
+} from "./helpers";
+
+// Raydium CPMM and its index 0 fee config are cloned from mainnet, see Anchor.toml
+const RAYDIUM_CPMM = new PublicKey("CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C");
+const AMM_CONFIG = new PublicKey("D4FPEruKEHrG5TenZ2mpDGEfu1iUvTiqBxvpU8HLBvC2");
+const CREATE_POOL_FEE = new PublicKey("DNXgeM9EiiaAbaWvwjHj9fQQLAX5ZsfHyvmYUNRAdNC8");
+const IX_INITIALIZE = Buffer.from([175, 175, 109, 31, 13, 152, 155, 237]);
+const TRADE_FEE_BPS = 25;
+
+interface CpmmPool {
+  poolState: PublicKey;
+  authority: PublicKey;
+  observation: PublicKey;
+  vaultOf: (mint: PublicKey) => PublicKey;
+}
+
+function cpmmPdas(mint0: PublicKey, mint1: PublicKey) {
+  const poolState = pda([Buffer.from("pool"), AMM_CONFIG.toBuffer(), mint0.toBuffer(), mint1.toBuffer()], RAYDIUM_CPMM);
+  return {
+    poolState,
+    authority: pda([Buffer.from("vault_and_lp_mint_auth_seed")], RAYDIUM_CPMM),
+    lpMint: pda([Buffer.from("pool_lp_mint"), poolState.toBuffer()], RAYDIUM_CPMM),
+    observation: pda([Buffer.from("observation"), poolState.toBuffer()], RAYDIUM_CPMM),
+    vaultOf: (mint: PublicKey) => pda([Buffer.from("pool_vault"), poolState.toBuffer(), mint.toBuffer()], RAYDIUM_CPMM),
+  };
+}
+
+// Opens a Raydium CPMM pool trading the event's TRUE against FALSE, seeded with `amount` of each.
+async function createCpmmPool(ev: TestEvent, creator: Keypair, amount: BN): Promise<CpmmPool> {
+  const [mint0, mint1] =
+    Buffer.compare(ev.trueMint.toBuffer(), ev.falseMint.toBuffer()) < 0
+      ? [ev.trueMint, ev.falseMint]
+      : [ev.falseMint, ev.trueMint];
+  const p = cpmmPdas(mint0, mint1);
+
+  const data = Buffer.concat([
+    IX_INITIALIZE,
+    amount.toArrayLike(Buffer, "le", 8),
+    amount.toArrayLike(Buffer, "le", 8),
+    new BN(0).toArrayLike(Buffer, "le", 8), // open_time, bumped to now + 1 by Raydium
+  ]);
+  const ix = new TransactionInstruction({
+    programId: RAYDIUM_CPMM,
+    keys: [
+      { pubkey: creator.publicKey, isSigner: true, isWritable: true },
+      { pubkey: AMM_CONFIG, isSigner: false, isWritable: false },
+      { pubkey: p.authority, isSigner: false, isWritable: false },
+      { pubkey: p.poolState, isSigner: false, isWritable: true },
+      { pubkey: mint0, isSigner: false, isWritable: false },
+      { pubkey: mint1, isSigner: false, isWritable: false },
+      { pubkey: p.lpMint, isSigner: false, isWritable: true },
+      { pubkey: ataAddress(creator.publicKey, mint0), isSigner: false, isWritable: true },
+      { pubkey: ataAddress(creator.publicKey, mint1), isSigner: false, isWritable: true },
+      { pubkey: ataAddress(creator.publicKey, p.lpMint), isSigner: false, isWritable: true },
+      { pubkey: p.vaultOf(mint0), isSigner: false, isWritable: true },
+      { pubkey: p.vaultOf(mint1), isSigner: false, isWritable: true },
+      { pubkey: CREATE_POOL_FEE, isSigner: false, isWritable: true },
+      { pubkey: p.observation, isSigner: false, isWritable: true },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: TOKEN_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: ASSOCIATED_PROGRAM_ID, isSigner: false, isWritable: false },
+      { pubkey: SystemProgram.programId, isSigner: false, isWritable: false },
+      { pubkey: SYSVAR_RENT_PUBKEY, isSigner: false, isWritable: false },
+    ],
+    data,
+  });
+  await send([ix], [creator]);
+  return p;
+}
+
+describe("user-004: single-side buys", () => {
+  let creator: Keypair;
+  let other: Keypair;
+  let ev: TestEvent;
+  let pool: CpmmPool;
+
+  const setPool = (signer: Keypair, poolState: PublicKey, event = ev) =>
+    program.methods
+      .setSingleSidePool()
+      .accountsPartial({
+        creator: signer.publicKey,
+        event: event.event,
+        poolState,
+      })
+      .signers([signer])
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    other = await newUser(5);
+    ev = await createEvent(creator, { betSecs: 60 });
+
+    // the creator seeds the TRUE / FALSE pool from a pair buy
+    await buyPair(ev, creator, 2 * LAMPORTS_PER_SOL);
+    pool = await createCpmmPool(ev, creator, new BN(LAMPORTS_PER_SOL));
+  });
+
+  it("only lets the event creator designate the pool", async () => {
+    await expectError(setPool(other, pool.poolState), "Unauthorized");
+  });
+
+  it("rejects a pool not owned by Raydium CPMM", async () => {
+    await expectError(setPool(creator, Keypair.generate().publicKey), "InvalidRaydiumPool");
+    // a real account with the wrong owner
+    await expectError(setPool(creator, ev.collateralVault), "InvalidRaydiumPool");
+
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.raydiumPool.equals(PublicKey.default)).to.equal(true);
+  });
+
+  it("designates a CPMM pool trading the event's TRUE against FALSE", async () => {
+    await setPool(creator, pool.poolState);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.raydiumPool.equals(pool.poolState)).to.equal(true);
+  });
+
+  it("buys a pair and swaps the unwanted side into the wanted one", async () => {
+    const buyer = await newUser(5);
+    const { trueAta, falseAta } = await positionAtas(ev, buyer);
+    const lamportsIn = LAMPORTS_PER_SOL;
+    const net = lamportsIn - (lamportsIn * TEST_BUY_FEE_BPS) / BPS_DENOM;
+
+    const inputVault = pool.vaultOf(ev.falseMint);
+    const outputVault = pool.vaultOf(ev.trueMint);
+    const reserveIn = await tokenBalance(inputVault);
+    const reserveOut = await tokenBalance(outputVault);
+
+    // Raydium opens the pool one second after creation
+    await waitUntil((await chainTime()) + 2);
+    await program.methods
+      .buySingleSide(SIDE_TRUE, new BN(lamportsIn), new BN(net))
+      .accountsPartial({
+        user: buyer.publicKey,
+        event: ev.event,
+        config: configPda,
+        collateralVault: ev.collateralVault,
+        commissionVault: ev.commissionVault,
+        mintAuthority: ev.mintAuthority,
+        trueMint: ev.trueMint,
+        falseMint: ev.falseMint,
+        userTrueAta: trueAta,
+        userFalseAta: falseAta,
+        truthNetworkQuestion: ev.question,
+        truthNetworkVault: ev.questionVault,
+        raydiumProgram: RAYDIUM_CPMM,
+        raydiumAuthority: pool.authority,
+        ammConfig: AMM_CONFIG,
+        poolState: pool.poolState,
+        inputVault,
+        outputVault,
+        observationState: pool.observation,
+        userStats: userStatsPda(buyer.publicKey),
+        userPosition: userPositionPda(ev.event, buyer.publicKey),
+        referral: null,
+        referralVault: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+    // all FALSE went into the pool, TRUE = the pair leg + what the pool paid out
+    const received = reserveOut.sub(await tokenBalance(outputVault));
+    expect((await tokenBalance(inputVault)).sub(reserveIn).toNumber()).to.equal(net);
+    expect((await tokenBalance(falseAta)).toNumber()).to.equal(0);
+    expect((await tokenBalance(trueAta)).toString()).to.equal(received.addn(net).toString());
+
+    // x * y = k after Raydium's trade fee
+    const inAfterFee = new BN(net).muln(BPS_DENOM - TRADE_FEE_BPS).divn(BPS_DENOM);
+    const expected = reserveOut.mul(inAfterFee).div(reserveIn.add(inAfterFee));
+    expect(received.toNumber()).to.be.closeTo(expected.toNumber(), 2);
+
+    const position = await program.account.userPosition.fetch(userPositionPda(ev.event, buyer.publicKey));
+    expect(position.unitsTrue.toString()).to.equal(received.addn(net).toString());
+    expect(position.unitsFalse.toNumber()).to.equal(0);
+    expect(position.costBasisTrue.toNumber()).to.equal(lamportsIn);
+  });
+
+  it("cannot change the pool after betting ends", async () => {
+    const short = await createEvent(creator, { betSecs: 10 });
+    await waitUntil(short.betEnd + 1);
+    await expectError(setPool(creator, Keypair.generate().publicKey, short), "BettingPeriodEnded");
+  });
+});
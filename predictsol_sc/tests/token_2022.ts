@@ -0,0 +1,102 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  SIDE_FALSE,
+  SystemProgram,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  TestEvent,
+  ataAddress,
+  buyPair,
+  configPda,
+  connection,
+  createEvent,
+  eventPdas,
+  expectError,
+  lamports,
+  newUser,
+  positionAtas,
+  program,
+  redeemNoWinnerIx,
+  redeemPairIx,
+  resolveEvent,
+  tokenBalance,
+  waitFinal,
+} from "./helpers";
+
+describe("user-009: Token-2022 position mints", () => {
+  let creator: Keypair;
+  let user: Keypair;
+  let ev: TestEvent;
+  let atas: { trueAta: PublicKey; falseAta: PublicKey };
+
+  before(async () => {
+    creator = await newUser(20);
+    user = await newUser(20);
+  });
+
+  it("only accepts the Token-2022 program", async () => {
+    await createEvent(creator, {
+      token2022: true,
+      beforeMints: async (event) => {
+        await expectError(
+          program.methods
+            .createEventMints2022()
+            .accountsPartial({
+              creator: creator.publicKey,
+              event,
+              ...eventPdas(event),
+              config: configPda,
+              tokenProgram: TOKEN_PROGRAM_ID,
+              systemProgram: SystemProgram.programId,
+            })
+            .signers([creator])
+            .rpc(),
+          "InvalidProgramId"
+        );
+      },
+    });
+  });
+
+  it("creates both mints under Token-2022 with on-mint metadata", async () => {
+    ev = await createEvent(creator, { token2022: true, betSecs: 30 });
+
+    for (const [mint, symbol] of [
+      [ev.trueMint, "TRUE"],
+      [ev.falseMint, "FALSE"],
+    ] as [PublicKey, string][]) {
+      const info = await connection.getAccountInfo(mint);
+      expect(info.owner.equals(TOKEN_2022_PROGRAM_ID)).to.equal(true);
+      expect(info.data.readUInt8(44)).to.equal(9); // decimals
+      expect(info.data.includes(Buffer.from(`-${symbol}`))).to.equal(true);
+      expect(info.data.includes(Buffer.from("PS-"))).to.equal(true);
+    }
+  });
+
+  it("buys and redeems pairs through Token-2022 accounts", async () => {
+    atas = await positionAtas(ev, user);
+    expect(atas.trueAta.equals(ataAddress(user.publicKey, ev.trueMint, TOKEN_2022_PROGRAM_ID))).to.equal(true);
+    await buyPair(ev, user, LAMPORTS_PER_SOL);
+    const held = await tokenBalance(atas.trueAta);
+    expect(held.gtn(0)).to.equal(true);
+
+    const amount = new BN(LAMPORTS_PER_SOL / 10);
+    const before = await lamports(user.publicKey);
+    await redeemPairIx(ev, user, amount).signers([user]).rpc();
+    expect((await lamports(user.publicKey)) - before).to.be.closeTo(amount.toNumber(), 10_000);
+    expect((await tokenBalance(atas.falseAta)).toString()).to.equal(held.sub(amount).toString());
+  });
+
+  it("settles a no-votes result through the same accounts", async () => {
+    await resolveEvent(ev);
+    await waitFinal(ev);
+
+    const amount = await tokenBalance(atas.falseAta);
+    const before = await lamports(user.publicKey);
+    await redeemNoWinnerIx(ev, user, SIDE_FALSE, amount).signers([user]).rpc();
+    expect((await lamports(user.publicKey)) - before).to.be.at.least(amount.divn(2).toNumber() - 10_000);
+    expect((await tokenBalance(atas.falseAta)).toNumber()).to.equal(0);
+  });
+});
@@ -0,0 +1,204 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  SIDE_TRUE,
+  TOKEN_PROGRAM_ID,
+  TestEvent,
+  ataAddress,
+  buyPairIx,
+  buyPairWithToken,
+  configPda,
+  createAta,
+  createEvent,
+  createMint,
+  expectError,
+  initTokenCollateralIx,
+  mintTo,
+  newUser,
+  positionAtas,
+  program,
+  resolveEvent,
+  tokenBalance,
+  tokenVaultPdas,
+  truthFeeRecipient,
+  userPositionPda,
+  userStatsPda,
+  waitFinal,
+} from "./helpers";
+
+const DECIMALS = 6;
+const UNIT = new BN(10 ** DECIMALS);
+
+describe("user-008: SPL token collateral", () => {
+  let creator: Keypair;
+  let other: Keypair;
+  let user: Keypair;
+  let mint: PublicKey;
+  let userCollateral: PublicKey;
+  let ev: TestEvent;
+  let v: ReturnType<typeof tokenVaultPdas>;
+
+  const initTokenCollateral = (signer: Keypair, event: PublicKey) =>
+    initTokenCollateralIx(signer, event, mint).rpc();
+
+  const sweepAccounts = () => ({
+    event: ev.event,
+    config: configPda,
+    collateralVault: v.collateralVault,
+    tokenVault: v.tokenVault,
+    commissionTokenVault: v.commissionTokenVault,
+    collateralMint: mint,
+    tokenProgram: TOKEN_PROGRAM_ID,
+  });
+
+  before(async () => {
+    creator = await newUser(20);
+    other = await newUser(5);
+    user = await newUser(5);
+    mint = await createMint(creator, DECIMALS);
+    userCollateral = await createAta(user, user.publicKey, mint);
+    await mintTo(creator, mint, userCollateral, UNIT.muln(1_000));
+  });
+
+  it("switches a fresh event to token collateral before its mints exist", async () => {
+    ev = await createEvent(creator, {
+      betSecs: 40,
+      beforeMints: async (event) => {
+        await expectError(initTokenCollateral(other, event), "Unauthorized");
+        await initTokenCollateral(creator, event);
+      },
+    });
+    v = tokenVaultPdas(ev.event);
+    await positionAtas(ev, user);
+
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.collateralMint.equals(mint)).to.equal(true);
+    expect(event.collateralDecimals).to.equal(DECIMALS);
+
+    const binary = await createEvent(creator);
+    await expectError(initTokenCollateral(creator, binary.event), "MintsAlreadyCreated");
+  });
+
+  it("refuses SOL on a token-collateral event", async () => {
+    await expectError(buyPairIx(ev, user, LAMPORTS_PER_SOL).signers([user]).rpc(), "TokenCollateralEvent");
+  });
+
+  it("splits a token buy between the collateral, Truth and commission vaults", async () => {
+    const amount = UNIT.muln(100);
+    await buyPairWithToken(ev, user, mint, amount);
+
+    const event = await program.account.event.fetch(ev.event);
+    const net = event.totalIssuedPerSide;
+    const vault = await tokenBalance(v.tokenVault);
+    const truthCut = await tokenBalance(v.truthTokenVault);
+    const commission = await tokenBalance(v.commissionTokenVault);
+
+    expect(net.lt(amount)).to.equal(true);
+    expect(vault.toString()).to.equal(net.toString());
+    expect(truthCut.gtn(0)).to.equal(true);
+    expect(vault.add(truthCut).add(commission).toString()).to.equal(amount.toString());
+    expect(commission.toString()).to.equal(event.pendingCreatorCommission.add(event.pendingHouseCommission).toString());
+    expect((await tokenBalance(ataAddress(user.publicKey, ev.trueMint))).toString()).to.equal(net.toString());
+
+    // token amounts build the position but not the lamport tier volume
+    const position = await program.account.userPosition.fetch(userPositionPda(ev.event, user.publicKey));
+    expect(position.unitsTrue.toString()).to.equal(net.toString());
+    expect(position.costBasisTrue.add(position.costBasisFalse).toString()).to.equal(amount.toString());
+    const stats = await program.account.userStats.fetch(userStatsPda(user.publicKey));
+    expect(stats.lifetimeVolume.toNumber()).to.equal(0);
+  });
+
+  it("redeems pairs for collateral tokens while betting is open", async () => {
+    const amount = UNIT.muln(10);
+    const before = await tokenBalance(userCollateral);
+    await program.methods
+      .redeemPairWhileActiveToken(amount)
+      .accountsPartial({
+        user: user.publicKey,
+        event: ev.event,
+        config: configPda,
+        collateralVault: v.collateralVault,
+        tokenVault: v.tokenVault,
+        commissionTokenVault: v.commissionTokenVault,
+        collateralMint: mint,
+        userCollateralAta: userCollateral,
+        trueMint: ev.trueMint,
+        falseMint: ev.falseMint,
+        userTrueAta: ataAddress(user.publicKey, ev.trueMint),
+        userFalseAta: ataAddress(user.publicKey, ev.falseMint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+    expect((await tokenBalance(userCollateral)).sub(before).toString()).to.equal(amount.toString());
+  });
+
+  it("pays the Truth cut to the configured recipient", async () => {
+    const truthAta = await createAta(creator, truthFeeRecipient.publicKey, mint);
+    const held = await tokenBalance(v.truthTokenVault);
+    const before = await tokenBalance(truthAta);
+    const claim = () =>
+      program.methods
+        .claimTruthCommissionToken()
+        .accountsPartial({
+          event: ev.event,
+          config: configPda,
+          collateralVault: v.collateralVault,
+          truthTokenVault: v.truthTokenVault,
+          collateralMint: mint,
+          truthCollateralAta: truthAta,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    await claim();
+    expect((await tokenBalance(truthAta)).sub(before).toString()).to.equal(held.toString());
+    await expectError(claim(), "NothingToClaim");
+  });
+
+  it("sweeps the house cut in tokens and refunds half per token without a winner", async () => {
+    await resolveEvent(ev);
+
+    const event = await program.account.event.fetch(ev.event);
+    const houseAta = await createAta(creator, event.houseWallet, mint);
+    const houseCut = event.pendingHouseCommission;
+    const houseBefore = await tokenBalance(houseAta);
+    const sweep = () =>
+      program.methods
+        .sweepHouseCommissionToken()
+        .accountsPartial({ ...sweepAccounts(), houseCollateralAta: houseAta })
+        .rpc();
+
+    await sweep();
+    expect((await tokenBalance(houseAta)).sub(houseBefore).toString()).to.equal(houseCut.toString());
+    await expectError(sweep(), "NothingToSweep");
+
+    await waitFinal(ev);
+    const ata = ataAddress(user.publicKey, ev.trueMint);
+    const amount = await tokenBalance(ata);
+    const before = await tokenBalance(userCollateral);
+    await program.methods
+      .redeemAfterFinalToken(SIDE_TRUE, amount)
+      .accountsPartial({
+        user: user.publicKey,
+        event: ev.event,
+        config: configPda,
+        collateralVault: v.collateralVault,
+        tokenVault: v.tokenVault,
+        commissionTokenVault: v.commissionTokenVault,
+        collateralMint: mint,
+        userCollateralAta: userCollateral,
+        mint: ev.trueMint,
+        userAta: ata,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([user])
+      .rpc();
+
+    const received = (await tokenBalance(userCollateral)).sub(before);
+    expect(received.gte(amount.divn(2))).to.equal(true);
+    expect((await tokenBalance(ata)).toNumber()).to.equal(0);
+  });
+});
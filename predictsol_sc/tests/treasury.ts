@@ -0,0 +1,86 @@
+import { expect } from "chai";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  TEST_BUY_FEE_BPS,
+  airdrop,
+  beneficiaries,
+  buyPair,
+  configPda,
+  connection,
+  createEvent,
+  expectError,
+  feeSplits,
+  houseTreasuryPda,
+  lamports,
+  newUser,
+  program,
+  resolveEvent,
+} from "./helpers";
+
+// beneficiaries from testConfigParams: 60% / 40%
+describe("user-020: house treasury", () => {
+  let creator: Keypair;
+
+  const distribute = (wallets: PublicKey[]) =>
+    program.methods
+      .distributeTreasury()
+      .accountsPartial({ config: configPda, houseTreasury: houseTreasuryPda })
+      .remainingAccounts(wallets.map((pubkey) => ({ pubkey, isSigner: false, isWritable: true })))
+      .rpc();
+
+  before(async () => {
+    creator = await newUser(20);
+    // keep the beneficiary wallets rent-exempt whatever their share turns out to be
+    for (const b of beneficiaries) {
+      await airdrop(b.publicKey, 1);
+    }
+  });
+
+  it("is owned by the program and set as the house wallet", async () => {
+    const info = await connection.getAccountInfo(houseTreasuryPda);
+    expect(info.owner.equals(program.programId)).to.equal(true);
+    const config = await program.account.protocolConfig.fetch(configPda);
+    expect(config.houseWallet.equals(houseTreasuryPda)).to.equal(true);
+  });
+
+  it("records the house commission of a resolved event as an inflow", async () => {
+    const ev = await createEvent(creator, { betSecs: 20 });
+    const event = await program.account.event.fetch(ev.event);
+    const { houseCut } = feeSplits(LAMPORTS_PER_SOL, TEST_BUY_FEE_BPS, event.truthShareBps, event.creatorShareBps);
+    await buyPair(ev, await newUser(5), LAMPORTS_PER_SOL);
+
+    const before = await program.account.houseTreasury.fetch(houseTreasuryPda);
+    const lamportsBefore = await lamports(houseTreasuryPda);
+    await resolveEvent(ev);
+
+    const after = await program.account.houseTreasury.fetch(houseTreasuryPda);
+    expect(after.totalReceived.sub(before.totalReceived).toNumber()).to.equal(houseCut);
+    expect(after.inflowCount.sub(before.inflowCount).toNumber()).to.equal(1);
+    expect((await lamports(houseTreasuryPda)) - lamportsBefore).to.equal(houseCut);
+  });
+
+  it("pays the beneficiaries by weight", async () => {
+    const [first, second] = beneficiaries.map((b) => b.publicKey);
+    await expectError(distribute([first]), "InvalidRemainingAccounts");
+    await expectError(distribute([second, first]), "InvalidRemainingAccounts");
+
+    const info = await connection.getAccountInfo(houseTreasuryPda);
+    const rent = await connection.getMinimumBalanceForRentExemption(info.data.length);
+    const available = info.lamports - rent;
+    const before = await program.account.houseTreasury.fetch(houseTreasuryPda);
+    const [firstBefore, secondBefore] = await Promise.all([lamports(first), lamports(second)]);
+
+    await distribute([first, second]);
+
+    const firstShare = Math.floor((available * 6_000) / 10_000);
+    const secondShare = Math.floor((available * 4_000) / 10_000);
+    expect((await lamports(first)) - firstBefore).to.equal(firstShare);
+    expect((await lamports(second)) - secondBefore).to.equal(secondShare);
+
+    const after = await program.account.houseTreasury.fetch(houseTreasuryPda);
+    expect(after.totalDistributed.sub(before.totalDistributed).toNumber()).to.equal(firstShare + secondShare);
+    expect(after.lastDistributedAt.toNumber()).to.be.greaterThan(0);
+    expect(await lamports(houseTreasuryPda)).to.equal(rent + available - firstShare - secondShare);
+  });
+});
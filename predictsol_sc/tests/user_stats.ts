@@ -0,0 +1,114 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  TEST_BUY_FEE_BPS,
+  TEST_FEE_TIERS,
+  TestEvent,
+  buyPair,
+  buyPairWithToken,
+  createAta,
+  createEvent,
+  createMint,
+  createTokenEvent,
+  mintTo,
+  newUser,
+  program,
+  redeemPairIx,
+  userPositionPda,
+  userStatsPda,
+} from "./helpers";
+
+describe("user-014: volume tiers and user stats", () => {
+  let creator: Keypair;
+  let trader: Keypair;
+  let first: TestEvent;
+  let second: TestEvent;
+
+  const stats = () => program.account.userStats.fetch(userStatsPda(trader.publicKey));
+  const position = (ev: TestEvent) => program.account.userPosition.fetch(userPositionPda(ev.event, trader.publicKey));
+
+  before(async () => {
+    creator = await newUser(20);
+    trader = await newUser(20);
+    first = await createEvent(creator, { betSecs: 120 });
+    second = await createEvent(creator, { betSecs: 120 });
+  });
+
+  it("opens the stats and position accounts on the first buy", async () => {
+    await buyPair(first, trader, LAMPORTS_PER_SOL);
+    const net = LAMPORTS_PER_SOL - (LAMPORTS_PER_SOL * TEST_BUY_FEE_BPS) / 10_000;
+
+    const s = await stats();
+    expect(s.owner.equals(trader.publicKey)).to.equal(true);
+    expect(s.lifetimeVolume.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    expect(s.currentWindowVolume.toNumber()).to.equal(LAMPORTS_PER_SOL);
+    expect(s.eventsParticipated).to.equal(1);
+
+    const p = await position(first);
+    expect(p.event.equals(first.event)).to.equal(true);
+    expect(p.costBasisTrue.toNumber()).to.equal(LAMPORTS_PER_SOL / 2);
+    expect(p.costBasisFalse.toNumber()).to.equal(LAMPORTS_PER_SOL / 2);
+    expect(p.unitsTrue.toNumber()).to.equal(net);
+    expect(p.unitsFalse.toNumber()).to.equal(net);
+  });
+
+  it("counts each event once and charges the full fee below the first tier", async () => {
+    const before = (await program.account.event.fetch(first.event)).totalIssuedPerSide.toNumber();
+    const amount = 1.5 * LAMPORTS_PER_SOL;
+    await buyPair(first, trader, amount);
+    const issued = (await program.account.event.fetch(first.event)).totalIssuedPerSide.toNumber() - before;
+    expect(issued).to.equal(amount - (amount * TEST_BUY_FEE_BPS) / 10_000);
+
+    const s = await stats();
+    expect(s.lifetimeVolume.toNumber()).to.equal(2.5 * LAMPORTS_PER_SOL);
+    expect(s.eventsParticipated).to.equal(1);
+  });
+
+  it("discounts the fee once the 30-day volume reaches a tier", async () => {
+    const tier = TEST_FEE_TIERS[2];
+    expect((await stats()).currentWindowVolume.gte(tier.minVolume)).to.equal(true);
+
+    await buyPair(second, trader, LAMPORTS_PER_SOL);
+    const feeBps = TEST_BUY_FEE_BPS - (TEST_BUY_FEE_BPS * tier.discountBps) / 10_000;
+    const event = await program.account.event.fetch(second.event);
+    expect(event.totalIssuedPerSide.toNumber()).to.equal(LAMPORTS_PER_SOL - (LAMPORTS_PER_SOL * feeBps) / 10_000);
+    expect((await stats()).eventsParticipated).to.equal(2);
+  });
+
+  it("realises PnL against the cost basis on redemption", async () => {
+    const units = (await position(second)).unitsTrue;
+    await redeemPairIx(second, trader, units).signers([trader]).rpc();
+
+    const p = await position(second);
+    expect(p.unitsTrue.toNumber()).to.equal(0);
+    expect(p.costBasisTrue.toNumber()).to.equal(0);
+    expect(p.costBasisFalse.toNumber()).to.equal(0);
+    expect(p.payouts.toString()).to.equal(units.toString());
+
+    // redeem fee is zero, so the loss is exactly the buy fee
+    const s = await stats();
+    expect(s.totalPayouts.toString()).to.equal(units.toString());
+    expect(s.realizedPnl.toNumber()).to.equal(units.toNumber() - LAMPORTS_PER_SOL);
+  });
+
+  it("applies the lamport volume tier to token-collateral buys", async () => {
+    const mint = await createMint(creator, 6);
+    await mintTo(creator, mint, await createAta(trader, trader.publicKey, mint), new BN(1_000_000_000));
+    const tokenEvent = await createTokenEvent(creator, mint, { betSecs: 120 });
+    const volumeBefore = (await stats()).lifetimeVolume;
+
+    const amount = 100_000_000;
+    await buyPairWithToken(tokenEvent, trader, mint, new BN(amount));
+    const feeBps = TEST_BUY_FEE_BPS - (TEST_BUY_FEE_BPS * TEST_FEE_TIERS[2].discountBps) / 10_000;
+    const event = await program.account.event.fetch(tokenEvent.event);
+    expect(event.totalIssuedPerSide.toNumber()).to.equal(amount - (amount * feeBps) / 10_000);
+
+    // token amounts don't add to the lamport volume
+    const s = await stats();
+    expect(s.lifetimeVolume.toString()).to.equal(volumeBefore.toString());
+    expect(s.eventsParticipated).to.equal(3);
+    expect((await position(tokenEvent)).costBasisFalse.toNumber()).to.equal(amount / 2);
+  });
+});
@@ -0,0 +1,106 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  LAMPORTS_PER_SOL,
+  SystemProgram,
+  TestEvent,
+  buyPair,
+  createEvent,
+  expectError,
+  lamports,
+  newUser,
+  program,
+  resolveEvent,
+  waitUntil,
+} from "./helpers";
+
+const VESTING_LINEAR = 1;
+const VESTING_TRANCHES = 2;
+const HOLDBACK_BPS = 2_000;
+
+describe("user-018: vested creator commission", () => {
+  let creator: Keypair;
+  let ev: TestEvent;
+
+  const setVesting = (signer: Keypair, vesting: number, tranches: number, holdbackBps: number) =>
+    program.methods
+      .setCreatorVesting(vesting, tranches, holdbackBps)
+      .accountsPartial({ creator: signer.publicKey, event: ev.event })
+      .signers([signer])
+      .rpc();
+
+  const claim = () =>
+    program.methods
+      .claimCreatorCommission()
+      .accountsPartial({
+        creator: creator.publicKey,
+        event: ev.event,
+        creatorPayout: creator.publicKey,
+        commissionVault: ev.commissionVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+  // claims and returns what the creator received
+  const claimed = async () => {
+    const before = await lamports(creator.publicKey);
+    await claim();
+    return (await lamports(creator.publicKey)) - before;
+  };
+
+  before(async () => {
+    creator = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 40 });
+  });
+
+  it("validates the schedule", async () => {
+    await expectError(setVesting(await newUser(1), VESTING_LINEAR, 0, HOLDBACK_BPS), "Unauthorized");
+    await expectError(setVesting(creator, 3, 0, HOLDBACK_BPS), "InvalidVesting");
+    await expectError(setVesting(creator, VESTING_TRANCHES, 0, HOLDBACK_BPS), "InvalidVesting");
+    await expectError(setVesting(creator, VESTING_TRANCHES, 13, HOLDBACK_BPS), "InvalidVesting");
+    await expectError(setVesting(creator, VESTING_LINEAR, 0, HOLDBACK_BPS - 1), "InvalidVesting");
+    await expectError(setVesting(creator, VESTING_LINEAR, 0, 10_001), "InvalidVesting");
+
+    await setVesting(creator, VESTING_TRANCHES, 2, HOLDBACK_BPS);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.creatorVesting).to.equal(VESTING_TRANCHES);
+    expect(event.vestingTranches).to.equal(2);
+    expect(event.vestingHoldbackBps).to.equal(HOLDBACK_BPS);
+  });
+
+  it("releases the commission tranche by tranche while betting is open", async () => {
+    await buyPair(ev, await newUser(20), 10 * LAMPORTS_PER_SOL);
+    await expectError(setVesting(creator, VESTING_LINEAR, 0, HOLDBACK_BPS), "CreatorPayoutsLocked");
+    await expectError(claim(), "NothingToClaim");
+
+    const event = await program.account.event.fetch(ev.event);
+    const earned = event.pendingCreatorCommission.toNumber();
+    const vestable = Math.floor((earned * (10_000 - HOLDBACK_BPS)) / 10_000);
+    const createdAt = event.createdAt.toNumber();
+    await waitUntil(createdAt + Math.ceil((ev.betEnd - createdAt) / 2) + 1);
+
+    expect(await claimed()).to.equal(Math.floor(vestable / 2));
+    await expectError(claim(), "NothingToClaim");
+
+    const after = await program.account.event.fetch(ev.event);
+    expect(after.claimedCreatorCommission.toNumber()).to.equal(Math.floor(vestable / 2));
+    expect(after.pendingCreatorCommission.toNumber()).to.equal(earned - Math.floor(vestable / 2));
+  });
+
+  it("keeps the holdback locked until resolution and claws it back on no votes", async () => {
+    const event = await program.account.event.fetch(ev.event);
+    const earned = event.pendingCreatorCommission.add(event.claimedCreatorCommission).toNumber();
+    const vestable = Math.floor((earned * (10_000 - HOLDBACK_BPS)) / 10_000);
+
+    await waitUntil(ev.betEnd + 1);
+    expect(await claimed()).to.equal(vestable - event.claimedCreatorCommission.toNumber());
+    await expectError(claim(), "NothingToClaim");
+
+    await resolveEvent(ev);
+    const resolved = await program.account.event.fetch(ev.event);
+    expect(resolved.claimedCreatorCommission.toNumber()).to.equal(vestable);
+    expect(resolved.pendingCreatorCommission.toNumber()).to.equal(0);
+    expect(resolved.forfeitedCommission.toNumber()).to.equal(earned - vestable);
+  });
+});
@@ -0,0 +1,164 @@
+import { expect } from "chai";
+import { Keypair } from "@solana/web3.js";
+import {
+  BN,
+  LAMPORTS_PER_SOL,
+  RESULT_VOIDED,
+  SIDE_FALSE,
+  SIDE_TRUE,
+  SystemProgram,
+  TEST_BUY_FEE_BPS,
+  TOKEN_PROGRAM_ID,
+  TestEvent,
+  admin,
+  ataAddress,
+  buyPair,
+  buyPairWithToken,
+  configPda,
+  createAta,
+  createEvent,
+  createMint,
+  createTokenEvent,
+  expectError,
+  feeSplits,
+  lamports,
+  mintTo,
+  newUser,
+  program,
+  redeemNoWinnerIx,
+  redeemPairIx,
+  tokenBalance,
+  tokenVaultPdas,
+  voidEvent,
+} from "./helpers";
+
+describe("user-023: void event", () => {
+  let creator: Keypair;
+  let alice: Keypair;
+  let bob: Keypair;
+  let ev: TestEvent;
+  let a: ReturnType<typeof feeSplits>;
+  let b: ReturnType<typeof feeSplits>;
+
+  const voidAs = (signer: Keypair) =>
+    program.methods
+      .voidEvent()
+      .accountsPartial({
+        authority: signer.publicKey,
+        config: configPda,
+        event: ev.event,
+        commissionVault: ev.commissionVault,
+        collateralVault: ev.collateralVault,
+        dispute: null,
+        challenger: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(signer === admin ? [] : [signer])
+      .rpc();
+
+  // lamports received by `user` for the instruction
+  const received = async (user: Keypair, send: () => Promise<unknown>) => {
+    const before = await lamports(user.publicKey);
+    await send();
+    return (await lamports(user.publicKey)) - before;
+  };
+
+  before(async () => {
+    creator = await newUser(20);
+    alice = await newUser(20);
+    bob = await newUser(20);
+    ev = await createEvent(creator, { betSecs: 60 });
+
+    const event = await program.account.event.fetch(ev.event);
+    a = feeSplits(LAMPORTS_PER_SOL, TEST_BUY_FEE_BPS, event.truthShareBps, event.creatorShareBps);
+    b = feeSplits(2 * LAMPORTS_PER_SOL, TEST_BUY_FEE_BPS, event.truthShareBps, event.creatorShareBps);
+    await buyPair(ev, alice, LAMPORTS_PER_SOL);
+    await buyPair(ev, bob, 2 * LAMPORTS_PER_SOL);
+  });
+
+  it("can only be voided by the void authority", async () => {
+    await expectError(voidAs(creator), "Unauthorized");
+    // a separate void authority is configured, so the admin can't void either
+    await expectError(voidAs(admin), "Unauthorized");
+  });
+
+  it("returns the creator and house commission to the holders", async () => {
+    const refund = a.creatorCut + a.houseCut + b.creatorCut + b.houseCut;
+    const collateralBefore = await lamports(ev.collateralVault);
+    await voidEvent(ev);
+
+    expect((await lamports(ev.collateralVault)) - collateralBefore).to.equal(refund);
+    const event = await program.account.event.fetch(ev.event);
+    expect(event.resultStatus).to.equal(RESULT_VOIDED);
+    expect(event.resolved).to.equal(true);
+    expect(event.pendingCreatorCommission.toNumber()).to.equal(0);
+    expect(event.pendingHouseCommission.toNumber()).to.equal(0);
+    expect(event.forfeitedCommission.toNumber()).to.equal(refund);
+    expect(event.forfeitBaseUnits.toNumber()).to.equal(2 * (a.net + b.net));
+
+    await expectError(voidEvent(ev), "EventAlreadyResolved");
+    await expectError(buyPair(ev, alice, LAMPORTS_PER_SOL), "EventResolved");
+  });
+
+  it("redeems pairs at full value and single sides at half", async () => {
+    const event = await program.account.event.fetch(ev.event);
+    const share = (units: number) =>
+      event.forfeitedCommission.mul(new BN(units)).div(event.forfeitBaseUnits).toNumber();
+
+    // pair: full value plus both tokens' share of the refund
+    const paid = await received(alice, () => redeemPairIx(ev, alice, a.net).signers([alice]).rpc());
+    expect(paid).to.equal(a.net + share(2 * a.net));
+
+    // single sides: half each, no redeem fee on a void
+    const half = Math.floor(b.net / 2);
+    expect(await received(bob, () => redeemNoWinnerIx(ev, bob, SIDE_TRUE, b.net).signers([bob]).rpc())).to.equal(
+      half + share(b.net)
+    );
+    expect(await received(bob, () => redeemNoWinnerIx(ev, bob, SIDE_FALSE, b.net).signers([bob]).rpc())).to.equal(
+      half + share(b.net)
+    );
+  });
+
+  it("redeems token-collateral pairs at full value plus the commission share", async () => {
+    const mint = await createMint(creator, 6);
+    const userCollateral = await createAta(alice, alice.publicKey, mint);
+    await mintTo(creator, mint, userCollateral, new BN(1_000_000_000));
+
+    const tev = await createTokenEvent(creator, mint, { betSecs: 60 });
+    const v = tokenVaultPdas(tev.event);
+    await buyPairWithToken(tev, alice, mint, new BN(100_000_000));
+    await voidEvent(tev);
+
+    // the returned commission stays in the commission token vault
+    const event = await program.account.event.fetch(tev.event);
+    const refund = event.forfeitedCommission;
+    expect(refund.gtn(0)).to.equal(true);
+    expect((await tokenBalance(v.commissionTokenVault)).toString()).to.equal(refund.toString());
+
+    // the only holder gets the whole pair value and the whole refund
+    const units = await tokenBalance(ataAddress(alice.publicKey, tev.trueMint));
+    const before = await tokenBalance(userCollateral);
+    await program.methods
+      .redeemPairWhileActiveToken(units)
+      .accountsPartial({
+        user: alice.publicKey,
+        event: tev.event,
+        config: configPda,
+        collateralVault: v.collateralVault,
+        tokenVault: v.tokenVault,
+        commissionTokenVault: v.commissionTokenVault,
+        collateralMint: mint,
+        userCollateralAta: userCollateral,
+        trueMint: tev.trueMint,
+        falseMint: tev.falseMint,
+        userTrueAta: ataAddress(alice.publicKey, tev.trueMint),
+        userFalseAta: ataAddress(alice.publicKey, tev.falseMint),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([alice])
+      .rpc();
+
+    expect((await tokenBalance(userCollateral)).sub(before).toString()).to.equal(units.add(refund).toString());
+    expect((await tokenBalance(v.commissionTokenVault)).toNumber()).to.equal(0);
+  });
+});